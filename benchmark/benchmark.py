@@ -47,6 +47,22 @@ def benchmark_call_native_1pos():
     return y
 
 
+def benchmark_call_native_2pos():
+    y = False
+    xs = []
+    for _x in range(REPEAT_100M):
+        y = hasattr(xs, "append")
+    return y
+
+
+def benchmark_call_native_2pos_star_args():
+    y = False
+    args = ([], "append")
+    for _x in range(REPEAT_100M):
+        y = hasattr(*args)
+    return y
+
+
 def op4(_x):
     pass
 
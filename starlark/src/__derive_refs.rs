@@ -27,8 +27,10 @@ pub mod serde {
     pub use serde::Serializer;
 }
 pub use inventory;
+pub mod blocking {
+    pub use crate::eval::blocking::block_on;
+}
 pub mod components;
-pub mod invoke_macro_error;
 pub mod param_spec;
 pub mod parse_args;
 pub mod sig;
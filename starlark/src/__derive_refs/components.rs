@@ -32,7 +32,31 @@ use crate::typing::Ty;
 
 /// A wrapper for the parameters to `GlobalsBuilder::set_function` and `MethodBuilder::set_method`
 pub struct NativeCallableComponents {
+    /// Whether the function can be called speculatively at compile time.
+    ///
+    /// Functions taking `&mut Evaluator` cannot be marked speculative-safe
+    /// unless written as `speculative_exec_safe(allow_eval)`:
+    ///
+    /// ```compile_fail
+    /// use starlark::environment::GlobalsBuilder;
+    /// use starlark::eval::Evaluator;
+    /// use starlark::starlark_module;
+    /// use starlark::values::none::NoneType;
+    ///
+    /// #[starlark_module]
+    /// fn globals(builder: &mut GlobalsBuilder) {
+    ///     #[starlark(speculative_exec_safe)]
+    ///     fn f<'v>(eval: &mut Evaluator<'v, '_, '_>) -> anyhow::Result<NoneType> {
+    ///         Ok(NoneType)
+    ///     }
+    /// }
+    /// ```
     pub speculative_exec_safe: bool,
+    /// Whether the function fails when the evaluator
+    /// [enforces determinism](crate::eval::Evaluator::enforce_determinism).
+    pub nondeterministic: bool,
+    /// Name used for the function in call stacks and profiles, if different from its name.
+    pub profile_name: Option<&'static str>,
     pub rust_docstring: Option<&'static str>,
     pub param_spec: NativeCallableParamSpec,
     pub return_type: Ty,
@@ -52,6 +76,7 @@ impl NativeCallableComponents {
                         Some(PARAM_FMT_OPTIONAL.to_owned())
                     }
                     Some(NativeCallableParamDefaultValue::Value(v)) => Some(v.to_value().to_repr()),
+                    Some(NativeCallableParamDefaultValue::Expr(e)) => Some((*e).to_owned()),
                 },
             }
         }
@@ -28,6 +28,9 @@ use crate::values::FrozenValue;
 pub enum NativeCallableParamDefaultValue {
     /// Value is used for documentation only, not when the function is called.
     Value(FrozenValue),
+    /// Source text of a Rust default expression which cannot be represented as a `FrozenValue`.
+    /// Like `Value`, used for documentation only.
+    Expr(&'static str),
     Optional,
 }
 
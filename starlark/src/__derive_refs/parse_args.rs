@@ -19,6 +19,7 @@ use crate::eval::Arguments;
 use crate::eval::ParametersSpec;
 use crate::values::FrozenValue;
 use crate::values::Heap;
+use crate::values::unpack::unpack_named_param_at;
 use crate::values::UnpackValue;
 use crate::values::Value;
 use crate::values::ValueError;
@@ -35,6 +36,13 @@ pub fn parse_signature<'v, const N: usize>(
     parser.collect_into(args, heap)
 }
 
+/// Fail if any positional arguments are passed to a function
+/// which accepts only named arguments.
+#[inline(always)]
+pub fn no_positional_args(function: &str, args: &Arguments) -> crate::Result<()> {
+    args.no_positional_error(function)
+}
+
 /// Parse positional-only arguments, required and optional.
 #[inline(always)]
 pub fn parse_positional<'v, const R: usize, const O: usize>(
@@ -98,3 +106,38 @@ pub fn check_defaulted<'v, T: UnpackValue<'v>>(
 pub fn check_unpack<'v, T: UnpackValue<'v>>(name: &str, x: Value<'v>) -> anyhow::Result<T> {
     T::unpack_named_param(x, name)
 }
+
+/// Like [`check_unpack`], but for a positional-only parameter at `index` (0-based),
+/// which is also reported as `argument #N` (1-based) in errors.
+#[inline]
+pub fn check_unpack_positional<'v, T: UnpackValue<'v>>(
+    name: &str,
+    index: usize,
+    x: Value<'v>,
+) -> anyhow::Result<T> {
+    unpack_named_param_at(x, name, Some(index))
+}
+
+/// Like [`check_optional`], but for a positional-only parameter at `index`.
+#[inline]
+pub fn check_optional_positional<'v, T: UnpackValue<'v>>(
+    name: &str,
+    index: usize,
+    x: Option<Value<'v>>,
+) -> anyhow::Result<Option<T>> {
+    match x {
+        None => Ok(None),
+        Some(x) => Ok(Some(check_unpack_positional(name, index, x)?)),
+    }
+}
+
+/// Like [`check_defaulted`], but for a positional-only parameter at `index`.
+#[inline]
+pub fn check_defaulted_positional<'v, T: UnpackValue<'v>>(
+    name: &str,
+    index: usize,
+    x: Option<Value<'v>>,
+    default: impl FnOnce() -> T,
+) -> anyhow::Result<T> {
+    Ok(check_optional_positional(name, index, x)?.unwrap_or_else(default))
+}
@@ -17,24 +17,36 @@
 
 //! Linter.
 
+use std::collections::HashMap;
 use std::collections::HashSet;
+use std::path::Path;
 
+pub use debug_calls::DebugCalls;
 pub use lint_message::LintMessage;
+pub use missing_load_symbols::MissingLoadSymbols;
+pub use too_many_params::TooManyParams;
 pub use types::EvalMessage;
 pub use types::EvalSeverity;
+pub use types::Fix;
 pub use types::Lint;
 pub use unused_loads::remove::remove_unused_loads;
 
 use crate::analysis::types::LintT;
+use crate::environment::Globals;
 use crate::syntax::AstModule;
+use crate::syntax::Dialect;
+use crate::typing::AstModuleTypecheck;
 
+mod debug_calls;
 mod dubious;
 pub mod find_call_name;
 mod flow;
 mod incompatible;
 mod lint_message;
+mod missing_load_symbols;
 mod names;
 mod performance;
+mod too_many_params;
 mod types;
 mod underscore;
 mod unused_loads;
@@ -61,6 +73,50 @@ impl AstModuleLint for AstModule {
     }
 }
 
+/// Parsed module together with its type errors and lints.
+pub struct Analysis {
+    /// The parsed module.
+    pub module: AstModule,
+    /// Errors found by the typechecker.
+    pub type_errors: Vec<crate::Error>,
+    /// Lints, with names resolved against the globals.
+    pub lints: Vec<Lint>,
+}
+
+impl Analysis {
+    /// Parse, typecheck and lint a module.
+    ///
+    /// Fails only if the module cannot be parsed, in which case no other checks are run.
+    /// `load` statements are not followed, so loaded symbols are not typechecked.
+    pub fn run(
+        src: &str,
+        filename: &str,
+        dialect: &Dialect,
+        globals: &Globals,
+    ) -> crate::Result<Analysis> {
+        let module = AstModule::parse(filename, src.to_owned(), dialect)?;
+        let names: HashSet<String> = globals.names().map(|n| n.as_str().to_owned()).collect();
+        let lints = module.lint(Some(&names));
+        let (type_errors, ..) = module.clone().typecheck(globals, &HashMap::new());
+        Ok(Analysis {
+            module,
+            type_errors,
+            lints,
+        })
+    }
+
+    /// Type errors followed by lints, as a single list of messages.
+    pub fn diagnostics(&self) -> Vec<EvalMessage> {
+        let file_span = self.module.file_span(Default::default());
+        let path = Path::new(file_span.filename());
+        self.type_errors
+            .iter()
+            .map(|e| EvalMessage::from_error(path, e))
+            .chain(self.lints.iter().cloned().map(EvalMessage::from))
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -71,6 +127,50 @@ mod tests {
         AstModule::parse("X", x.to_owned(), &Dialect::AllOptionsInternal).unwrap()
     }
 
+    #[test]
+    fn test_analysis_reports_type_errors_and_lints() {
+        let globals = Globals::standard();
+        let analysis = Analysis::run(
+            r#"
+def f(x: int):
+    return x
+    x = 1
+
+def g():
+    f("s")
+"#,
+            "a.bzl",
+            &Dialect::AllOptionsInternal,
+            &globals,
+        )
+        .unwrap();
+        assert_eq!(1, analysis.type_errors.len());
+        assert!(
+            analysis.type_errors[0]
+                .to_string()
+                .contains("Expected type `int` but got `str`"),
+            "{}",
+            analysis.type_errors[0]
+        );
+        assert!(
+            analysis.lints.iter().any(|l| l.short_name == "unreachable"),
+            "{:?}",
+            analysis.lints
+        );
+
+        let diagnostics = analysis.diagnostics();
+        assert_eq!(1 + analysis.lints.len(), diagnostics.len());
+        assert!(diagnostics.iter().all(|d| d.path == "a.bzl"));
+        assert!(matches!(diagnostics[0].severity, EvalSeverity::Error));
+        assert!(diagnostics.iter().any(|d| d.name == "unreachable"));
+    }
+
+    #[test]
+    fn test_analysis_parse_error() {
+        let globals = Globals::standard();
+        assert!(Analysis::run("def f(:", "a.bzl", &Dialect::Standard, &globals).is_err());
+    }
+
     #[test]
     fn test_lint_suppressions_keyword_matching() {
         let m = module(
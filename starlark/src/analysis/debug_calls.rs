@@ -0,0 +1,138 @@
+/*
+ * Copyright 2019 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashSet;
+
+use starlark_syntax::syntax::ast::AstExpr;
+use starlark_syntax::syntax::ast::Expr;
+use starlark_syntax::syntax::module::AstModuleFields;
+use thiserror::Error;
+
+use crate::analysis::types::LintT;
+use crate::analysis::types::LintWarning;
+use crate::analysis::EvalSeverity;
+use crate::analysis::Lint;
+use crate::codemap::CodeMap;
+use crate::syntax::AstModule;
+
+#[derive(Error, Debug)]
+pub(crate) enum DebugCallWarning {
+    #[error("Call to debug function `{0}`")]
+    DebugCall(String),
+}
+
+impl LintWarning for DebugCallWarning {
+    fn severity(&self) -> EvalSeverity {
+        EvalSeverity::Advice
+    }
+
+    fn short_name(&self) -> &'static str {
+        match self {
+            DebugCallWarning::DebugCall(..) => "debug-call",
+        }
+    }
+}
+
+/// Lint calls to debugging functions such as `print`, which are usually not meant to be committed.
+///
+/// Calls to `print` are common in regular code, so this lint is not run by
+/// [`AstModuleLint::lint`](crate::analysis::AstModuleLint::lint) and has to be run explicitly.
+/// Only direct calls like `print(x)` are reported, method calls like `logger.print(x)` are not.
+#[derive(Debug, Clone)]
+pub struct DebugCalls {
+    /// Names of the functions to report.
+    pub names: HashSet<String>,
+}
+
+impl Default for DebugCalls {
+    fn default() -> Self {
+        DebugCalls {
+            names: ["print", "pprint"]
+                .iter()
+                .map(|x| (*x).to_owned())
+                .collect(),
+        }
+    }
+}
+
+impl DebugCalls {
+    /// Run this lint over the module.
+    pub fn lint(&self, module: &AstModule) -> Vec<Lint> {
+        let mut res = Vec::new();
+        module
+            .statement()
+            .visit_expr(|x| self.lint_expr(module.codemap(), x, &mut res));
+        res.into_iter()
+            .map(LintT::erase)
+            .filter(|issue| !module.is_suppressed(&issue.short_name, issue.location.span))
+            .collect()
+    }
+
+    fn lint_expr(&self, codemap: &CodeMap, x: &AstExpr, res: &mut Vec<LintT<DebugCallWarning>>) {
+        if let Expr::Call(fun, _) = &**x {
+            if let Expr::Identifier(name) = &***fun {
+                if self.names.contains(&name.node.ident) {
+                    res.push(LintT::new(
+                        codemap,
+                        x.span,
+                        DebugCallWarning::DebugCall(name.node.ident.clone()),
+                    ));
+                }
+            }
+        }
+        x.visit_expr(|x| self.lint_expr(codemap, x, res));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starlark_syntax::slice_vec_ext::SliceExt;
+
+    use super::*;
+    use crate::syntax::Dialect;
+
+    fn module(x: &str) -> AstModule {
+        AstModule::parse("X", x.to_owned(), &Dialect::AllOptionsInternal).unwrap()
+    }
+
+    #[test]
+    fn test_lint_debug_calls() {
+        let m = module(
+            r#"
+print("x")
+logger.print("y")
+def foo(x):
+    debug(x)
+    return [pprint(y) for y in x]
+"#,
+        );
+        assert_eq!(
+            DebugCalls::default().lint(&m).map(|x| x.to_string()),
+            &[
+                "X:2:1-11: Call to debug function `print`",
+                "X:6:13-22: Call to debug function `pprint`",
+            ]
+        );
+        let debug = DebugCalls {
+            names: HashSet::from(["debug".to_owned()]),
+        };
+        assert_eq!(
+            debug.lint(&m).map(|x| x.to_string()),
+            &["X:5:5-13: Call to debug function `debug`"]
+        );
+    }
+}
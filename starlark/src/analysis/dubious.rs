@@ -20,6 +20,7 @@ use std::collections::HashMap;
 use starlark_syntax::syntax::ast::AstExpr;
 use starlark_syntax::syntax::ast::AstLiteral;
 use starlark_syntax::syntax::ast::AstStmt;
+use starlark_syntax::syntax::ast::BinOp;
 use starlark_syntax::syntax::ast::Expr;
 use starlark_syntax::syntax::ast::Stmt;
 use starlark_syntax::syntax::module::AstModuleFields;
@@ -41,6 +42,8 @@ pub(crate) enum Dubious {
     DuplicateKey(String, FileSpan),
     #[error("Variable `{0}` will either do nothing or fail if uninitialised")]
     IdentifierAsStatement(String),
+    #[error("Comparison `{0}` between `{1}` and `{2}` is always {3}")]
+    IncompatibleComparison(String, &'static str, &'static str, bool),
 }
 
 impl LintWarning for Dubious {
@@ -52,6 +55,7 @@ impl LintWarning for Dubious {
         match self {
             Dubious::DuplicateKey(..) => "duplicate-key",
             Dubious::IdentifierAsStatement(..) => "ident-as-statement",
+            Dubious::IncompatibleComparison(..) => "incompatible-comparison",
         }
     }
 }
@@ -136,10 +140,57 @@ fn identifier_as_statement(module: &AstModule, res: &mut Vec<LintT<Dubious>>) {
     stmt(module.statement(), module.codemap(), res)
 }
 
+// Equality between literals of different types is known statically.
+// Identifiers (including `None`, `True` and `False`) have unknown types, so are never flagged.
+fn incompatible_comparison(module: &AstModule, res: &mut Vec<LintT<Dubious>>) {
+    fn literal_type(x: &AstExpr) -> Option<&'static str> {
+        match &**x {
+            Expr::Literal(AstLiteral::Int(_)) => Some("int"),
+            Expr::Literal(AstLiteral::Float(_)) => Some("float"),
+            Expr::Literal(AstLiteral::String(_)) | Expr::FString(_) => Some("string"),
+            Expr::List(_) | Expr::ListComprehension(..) => Some("list"),
+            Expr::Dict(_) | Expr::DictComprehension(..) => Some("dict"),
+            Expr::Set(_) => Some("set"),
+            Expr::Tuple(_) => Some("tuple"),
+            _ => None,
+        }
+    }
+
+    fn comparable(a: &str, b: &str) -> bool {
+        let is_number = |x| x == "int" || x == "float";
+        a == b || (is_number(a) && is_number(b))
+    }
+
+    fn expr(x: &AstExpr, codemap: &CodeMap, res: &mut Vec<LintT<Dubious>>) {
+        if let Expr::Op(lhs, op @ (BinOp::Equal | BinOp::NotEqual), rhs) = &**x {
+            if let (Some(l), Some(r)) = (literal_type(lhs), literal_type(rhs)) {
+                if !comparable(l, r) {
+                    res.push(LintT::new(
+                        codemap,
+                        x.span,
+                        Dubious::IncompatibleComparison(
+                            x.to_string(),
+                            l,
+                            r,
+                            *op == BinOp::NotEqual,
+                        ),
+                    ));
+                }
+            }
+        }
+        x.visit_expr(|x| expr(x, codemap, res));
+    }
+
+    module
+        .statement()
+        .visit_expr(|x| expr(x, module.codemap(), res))
+}
+
 pub(crate) fn lint(module: &AstModule) -> Vec<LintT<Dubious>> {
     let mut res = Vec::new();
     duplicate_dictionary_key(module, &mut res);
     identifier_as_statement(module, &mut res);
+    incompatible_comparison(module, &mut res);
     res
 }
 
@@ -159,6 +210,7 @@ mod tests {
             match self {
                 Dubious::DuplicateKey(x, _) => x,
                 Dubious::IdentifierAsStatement(x) => x,
+                Dubious::IncompatibleComparison(x, ..) => x,
             }
         }
     }
@@ -204,4 +256,30 @@ def foo():
         identifier_as_statement(&m, &mut res);
         assert_eq!(res.map(|x| x.problem.about()), &["no1", "no2"]);
     }
+
+    #[test]
+    fn test_lint_incompatible_comparison() {
+        let m = module(
+            r#"
+1 == "x"
+[] != 0
+def foo(x):
+    return {} == (1, 2)
+x == "x"
+1 == 1.0
+None == 1
+"a" == "b"
+"#,
+        );
+        let mut res = Vec::new();
+        incompatible_comparison(&m, &mut res);
+        assert_eq!(
+            res.map(|x| x.to_string()),
+            &[
+                "X:2:1-9: Comparison `(1 == \"x\")` between `int` and `string` is always false",
+                "X:3:1-8: Comparison `([] != 0)` between `list` and `int` is always true",
+                "X:5:12-24: Comparison `({} == (1, 2))` between `dict` and `tuple` is always false",
+            ]
+        );
+    }
 }
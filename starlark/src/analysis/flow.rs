@@ -23,6 +23,7 @@ use starlark_syntax::syntax::ast::DefP;
 use starlark_syntax::syntax::ast::Expr;
 use starlark_syntax::syntax::ast::ForP;
 use starlark_syntax::syntax::ast::Stmt;
+use starlark_syntax::syntax::ast::WithP;
 use starlark_syntax::syntax::module::AstModuleFields;
 use thiserror::Error;
 
@@ -107,7 +108,7 @@ fn has_effect(x: &AstExpr) -> bool {
             matches!(x, AstLiteral::String(_))
         }
         Expr::Lambda(_) => false,
-        Expr::If(_) | Expr::Tuple(_) | Expr::List(_) | Expr::Dict(_) => {
+        Expr::If(_) | Expr::Tuple(_) | Expr::List(_) | Expr::Dict(_) | Expr::Set(_) => {
             let mut res = false;
             x.visit_expr(|x| res = res || has_effect(x));
             res
@@ -128,6 +129,7 @@ fn final_return(x: &AstStmt) -> bool {
             let (x, y) = &**x_y;
             final_return(x) && final_return(y)
         }
+        Stmt::With(WithP { body, .. }) => final_return(body),
         _ => false,
     }
 }
@@ -222,6 +224,7 @@ fn reachable(codemap: &CodeMap, x: &AstStmt, res: &mut Vec<LintT<FlowIssue>>) ->
             let abort2 = reachable(codemap, y, res);
             abort1 && abort2
         }
+        Stmt::With(WithP { body, .. }) => reachable(codemap, body, res),
         // For all remaining constructs, visit their children to accumulate errors,
         // but even if they are present with returns, you don't guarantee the code with inner returns
         // gets executed.
@@ -254,6 +257,7 @@ fn redundant(codemap: &CodeMap, x: &AstStmt, res: &mut Vec<LintT<FlowIssue>>) {
                 check(is_loop, codemap, x, res);
                 check(is_loop, codemap, y, res);
             }
+            Stmt::With(WithP { body, .. }) => check(is_loop, codemap, body, res),
             _ => {}
         }
     }
@@ -32,6 +32,7 @@ use starlark_syntax::syntax::ast::Stmt;
 use starlark_syntax::syntax::module::AstModuleFields;
 use thiserror::Error;
 
+use crate::analysis::types::Fix;
 use crate::analysis::types::LintT;
 use crate::analysis::types::LintWarning;
 use crate::analysis::EvalSeverity;
@@ -43,7 +44,7 @@ use crate::syntax::AstModule;
 #[derive(Error, Debug)]
 pub(crate) enum Incompatibility {
     #[error("Type check `{0}` should be written `{1}`")]
-    IncompatibleTypeCheck(String, String),
+    IncompatibleTypeCheck(String, String, Span),
     #[error("Duplicate top-level assignment of `{0}`, first defined at {1}")]
     DuplicateTopLevelAssign(String, FileSpan),
 }
@@ -59,6 +60,16 @@ impl LintWarning for Incompatibility {
             Incompatibility::DuplicateTopLevelAssign(..) => "duplicate-top-level-assign",
         }
     }
+
+    fn fix(&self) -> Option<Fix> {
+        match self {
+            Incompatibility::IncompatibleTypeCheck(_, replacement, span) => Some(Fix {
+                span: *span,
+                replacement: replacement.clone(),
+            }),
+            Incompatibility::DuplicateTopLevelAssign(..) => None,
+        }
+    }
 }
 
 static TYPES: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
@@ -107,6 +118,7 @@ fn match_bad_type_equality(
                     Incompatibility::IncompatibleTypeCheck(
                         x.to_string(),
                         format!("{}{}type({})", lhs.node, op, replacement),
+                        x.span,
                     ),
                 ))
             }
@@ -211,6 +223,7 @@ pub(crate) fn lint(module: &AstModule) -> Vec<LintT<Incompatibility>> {
 #[cfg(test)]
 mod tests {
     use starlark_syntax::slice_vec_ext::SliceExt;
+    use starlark_syntax::slice_vec_ext::VecExt;
 
     use super::*;
     use crate::syntax::Dialect;
@@ -240,6 +253,39 @@ def foo():
         );
     }
 
+    #[test]
+    fn test_lint_incompatible_fix() {
+        let code = "def foo(x):\n    return type(x) != str\n";
+        let mut res = Vec::new();
+        bad_type_equality(&module(code), &mut res);
+        let fixes = res.into_map(|x| x.erase().fix.unwrap());
+        let fix = &fixes[0];
+        let mut fixed = code.to_owned();
+        fixed.replace_range(
+            fix.span.begin().get() as usize..fix.span.end().get() as usize,
+            &fix.replacement,
+        );
+        assert_eq!(fixed, "def foo(x):\n    return type(x) != type(\"\")\n");
+    }
+
+    #[test]
+    fn test_lint_diagnostic_json() {
+        let mut res = Vec::new();
+        bad_type_equality(&module("\nok = type(x) == str\n"), &mut res);
+        assert_eq!(
+            res.into_map(|x| x.erase().to_diagnostic_json()),
+            &[serde_json::json!({
+                "range": {
+                    "start": {"line": 1, "character": 5},
+                    "end": {"line": 1, "character": 19},
+                },
+                "severity": 2,
+                "message": "Type check `(type(x) == str)` should be written `type(x) == type(\"\")`",
+                "code": "incompatible-type-check",
+            })]
+        );
+    }
+
     #[test]
     fn test_lint_duplicate_top_level_assign() {
         let m = module(
@@ -0,0 +1,152 @@
+/*
+ * Copyright 2019 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use starlark_syntax::syntax::ast::LoadArgP;
+use starlark_syntax::syntax::ast::Stmt;
+use starlark_syntax::syntax::module::AstModuleFields;
+use starlark_syntax::syntax::top_level_stmts::top_level_stmts;
+use thiserror::Error;
+
+use crate::analysis::types::LintT;
+use crate::analysis::types::LintWarning;
+use crate::analysis::EvalSeverity;
+use crate::analysis::Lint;
+use crate::environment::FrozenModule;
+use crate::syntax::AstModule;
+
+#[derive(Error, Debug)]
+pub(crate) enum MissingLoadSymbolWarning {
+    #[error("Module `{0}` does not export `{1}`")]
+    MissingLoadSymbol(String, String),
+}
+
+impl LintWarning for MissingLoadSymbolWarning {
+    fn severity(&self) -> EvalSeverity {
+        EvalSeverity::Warning
+    }
+
+    fn short_name(&self) -> &'static str {
+        match self {
+            MissingLoadSymbolWarning::MissingLoadSymbol(..) => "missing-load-symbol",
+        }
+    }
+}
+
+/// Lint `load` statements asking for a symbol which the loaded module does not export.
+///
+/// The symbols exported by each module are given up front, so no module is evaluated by the lint.
+/// They can be collected from evaluated modules with
+/// [`add_frozen_module`](MissingLoadSymbols::add_frozen_module),
+/// or from parsed ones with [`AstModule::exported_symbols`].
+/// Loads of modules missing from [`exports`](MissingLoadSymbols::exports) are not checked.
+///
+/// Not run by [`AstModuleLint::lint`](crate::analysis::AstModuleLint::lint), which does not know
+/// about other modules.
+#[derive(Debug, Clone, Default)]
+pub struct MissingLoadSymbols {
+    /// Symbols exported by each module, keyed by the module name as written in `load`.
+    pub exports: HashMap<String, HashSet<String>>,
+}
+
+impl MissingLoadSymbols {
+    /// Record the symbols exported by an evaluated module.
+    pub fn add_frozen_module(&mut self, module_id: &str, module: &FrozenModule) {
+        self.exports.insert(
+            module_id.to_owned(),
+            module.names().map(|x| x.as_str().to_owned()).collect(),
+        );
+    }
+
+    /// Run this lint over the module.
+    pub fn lint(&self, module: &AstModule) -> Vec<Lint> {
+        let mut res = Vec::new();
+        for x in top_level_stmts(module.statement()) {
+            if let Stmt::Load(load) = &**x {
+                let Some(exports) = self.exports.get(&load.module.node) else {
+                    continue;
+                };
+                for LoadArgP { their, .. } in &load.args {
+                    if !exports.contains(&their.node) {
+                        res.push(LintT::new(
+                            module.codemap(),
+                            their.span,
+                            MissingLoadSymbolWarning::MissingLoadSymbol(
+                                load.module.node.clone(),
+                                their.node.clone(),
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+        res.into_iter()
+            .map(LintT::erase)
+            .filter(|issue| !module.is_suppressed(&issue.short_name, issue.location.span))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starlark_syntax::slice_vec_ext::SliceExt;
+
+    use super::*;
+    use crate::assert;
+    use crate::syntax::Dialect;
+
+    fn module(x: &str) -> AstModule {
+        AstModule::parse("X", x.to_owned(), &Dialect::AllOptionsInternal).unwrap()
+    }
+
+    #[test]
+    fn test_lint_missing_load_symbols() {
+        let lib = module("def public():\n    pass\n_private = 1\n");
+        let mut lint = MissingLoadSymbols::default();
+        lint.exports.insert(
+            "lib.star".to_owned(),
+            lib.exported_symbols().into_iter().map(|x| x.node).collect(),
+        );
+        let m = module(
+            r#"
+load("lib.star", "public", alias = "missing", "_private")
+load("unknown.star", "anything")
+"#,
+        );
+        assert_eq!(
+            lint.lint(&m).map(|x| x.to_string()),
+            &[
+                "X:2:36-45: Module `lib.star` does not export `missing`",
+                "X:2:47-57: Module `lib.star` does not export `_private`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_missing_load_symbols_frozen_module() {
+        let lib = assert::pass_module("def public():\n    pass\n_private = 1\n");
+        let mut lint = MissingLoadSymbols::default();
+        lint.add_frozen_module("lib.star", &lib);
+        let m = module("load('lib.star', 'public', '_private')\n");
+        assert_eq!(
+            lint.lint(&m).map(|x| x.problem.as_str()),
+            &["Module `lib.star` does not export `_private`"]
+        );
+    }
+}
@@ -43,6 +43,7 @@ use starlark_syntax::syntax::ast::ForClause;
 use starlark_syntax::syntax::ast::ForP;
 use starlark_syntax::syntax::ast::LoadArgP;
 use starlark_syntax::syntax::ast::Stmt;
+use starlark_syntax::syntax::ast::WithP;
 use starlark_syntax::syntax::module::AstModuleFields;
 use thiserror::Error;
 
@@ -505,6 +506,13 @@ impl<'a> State<'a> {
                     me.stmt(body);
                 });
             }
+            Stmt::With(WithP { expr, target, body }) => {
+                self.expr(expr);
+                if let Some(target) = target {
+                    self.assign(target);
+                }
+                self.stmt(body);
+            }
             Stmt::Def(x) => {
                 for p in &x.params {
                     p.node.visit_expr(|e| self.expr(e));
@@ -0,0 +1,172 @@
+/*
+ * Copyright 2019 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use starlark_syntax::syntax::ast::AstStmt;
+use starlark_syntax::syntax::ast::ParameterP;
+use starlark_syntax::syntax::ast::Stmt;
+use starlark_syntax::syntax::module::AstModuleFields;
+use thiserror::Error;
+
+use crate::analysis::types::LintT;
+use crate::analysis::types::LintWarning;
+use crate::analysis::EvalSeverity;
+use crate::analysis::Lint;
+use crate::codemap::CodeMap;
+use crate::syntax::AstModule;
+
+#[derive(Error, Debug)]
+pub(crate) enum TooManyParamsWarning {
+    #[error("Function `{0}` has {1} parameters, more than the maximum of {2}")]
+    TooManyParams(String, usize, usize),
+}
+
+impl LintWarning for TooManyParamsWarning {
+    fn severity(&self) -> EvalSeverity {
+        EvalSeverity::Warning
+    }
+
+    fn short_name(&self) -> &'static str {
+        match self {
+            TooManyParamsWarning::TooManyParams(..) => "too-many-params",
+        }
+    }
+}
+
+/// Lint `def` functions with more than [`max_params`](TooManyParams::max_params) parameters.
+///
+/// This lint is opt-in: it is not run by
+/// [`AstModuleLint::lint`](crate::analysis::AstModuleLint::lint),
+/// embedders call [`TooManyParams::lint`] explicitly.
+/// `/` and `*` markers are not counted as parameters.
+#[derive(Debug, Clone)]
+pub struct TooManyParams {
+    /// Maximum number of parameters allowed without a warning.
+    pub max_params: usize,
+}
+
+impl Default for TooManyParams {
+    fn default() -> Self {
+        TooManyParams { max_params: 8 }
+    }
+}
+
+impl TooManyParams {
+    /// Run this lint over the module.
+    pub fn lint(&self, module: &AstModule) -> Vec<Lint> {
+        let mut res = Vec::new();
+        self.lint_impl(module.codemap(), module.statement(), &mut res);
+        res.into_iter()
+            .map(LintT::erase)
+            .filter(|issue| !module.is_suppressed(&issue.short_name, issue.location.span))
+            .collect()
+    }
+
+    fn lint_impl(
+        &self,
+        codemap: &CodeMap,
+        x: &AstStmt,
+        res: &mut Vec<LintT<TooManyParamsWarning>>,
+    ) {
+        if let Stmt::Def(def) = &**x {
+            let count = def
+                .params
+                .iter()
+                .filter(|p| !matches!(p.node, ParameterP::Slash | ParameterP::NoArgs))
+                .count();
+            if count > self.max_params {
+                res.push(LintT::new(
+                    codemap,
+                    def.signature_span(),
+                    TooManyParamsWarning::TooManyParams(
+                        def.name.ident.clone(),
+                        count,
+                        self.max_params,
+                    ),
+                ));
+            }
+        }
+        x.visit_stmt(|x| self.lint_impl(codemap, x, res));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starlark_syntax::slice_vec_ext::SliceExt;
+
+    use super::*;
+    use crate::analysis::AstModuleLint;
+    use crate::syntax::Dialect;
+
+    fn module(x: &str) -> AstModule {
+        AstModule::parse("X", x.to_owned(), &Dialect::AllOptionsInternal).unwrap()
+    }
+
+    #[test]
+    fn test_lint_too_many_params() {
+        let m = module(
+            r#"
+def ok(a, b, /, c, *, d):
+    pass
+def bad(a, b, c, *args, **kwargs):
+    def nested_ok(a, b, c):
+        pass
+    def nested_bad(a, b, c, d = 1):
+        pass
+"#,
+        );
+        let res = TooManyParams { max_params: 4 }.lint(&m);
+        assert_eq!(
+            res.map(|x| x.problem.as_str()),
+            &["Function `bad` has 5 parameters, more than the maximum of 4"]
+        );
+        let res = TooManyParams { max_params: 3 }.lint(&m);
+        assert_eq!(
+            res.map(|x| x.problem.as_str()),
+            &[
+                "Function `ok` has 4 parameters, more than the maximum of 3",
+                "Function `bad` has 5 parameters, more than the maximum of 3",
+                "Function `nested_bad` has 4 parameters, more than the maximum of 3",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_too_many_params_default() {
+        let params = |n: usize| {
+            (0..n)
+                .map(|i| format!("p{i}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let m = module(&format!(
+            "def ok({}):\n    pass\ndef bad({}):\n    pass\n",
+            params(8),
+            params(9)
+        ));
+        let res = TooManyParams::default().lint(&m);
+        assert_eq!(
+            res.map(|x| x.problem.as_str()),
+            &["Function `bad` has 9 parameters, more than the maximum of 8"]
+        );
+        // Not run by the default linter.
+        assert!(
+            m.lint(None)
+                .iter()
+                .all(|x| x.short_name != "too-many-params")
+        );
+    }
+}
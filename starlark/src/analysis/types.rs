@@ -30,6 +30,19 @@ use crate::codemap::Span;
 pub(crate) trait LintWarning: Display {
     fn severity(&self) -> EvalSeverity;
     fn short_name(&self) -> &'static str;
+    /// A mechanical edit which resolves the problem, if there is one.
+    fn fix(&self) -> Option<Fix> {
+        None
+    }
+}
+
+/// An edit of the source code which resolves a lint, see [`Lint::fix`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    /// The code to replace.
+    pub span: Span,
+    /// The code to put in its place.
+    pub replacement: String,
 }
 
 /// A private version of lint without the inner trait erased, useful so we can test
@@ -42,7 +55,7 @@ pub(crate) struct LintT<T> {
 }
 
 /// A lint produced by `AstModule::lint`.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Lint {
     /// Which code location does this lint refer to.
     pub location: FileSpan,
@@ -55,6 +68,8 @@ pub struct Lint {
     pub problem: String,
     /// The source code at [`location`](Lint::location).
     pub original: String,
+    /// An edit which resolves the problem, if it can be done mechanically.
+    pub fix: Option<Fix>,
 }
 
 impl Display for Lint {
@@ -63,6 +78,25 @@ impl Display for Lint {
     }
 }
 
+impl Lint {
+    /// Convert to JSON in the shape of a Language Server Protocol diagnostic,
+    /// i.e. `{range: {start, end}, severity, message, code}`, where `code` is the
+    /// [`short_name`](Lint::short_name), positions are 0-based `{line, character}`,
+    /// and `severity` is the numeric LSP `DiagnosticSeverity`.
+    pub fn to_diagnostic_json(&self) -> serde_json::Value {
+        let span = self.location.resolve_span();
+        serde_json::json!({
+            "range": {
+                "start": {"line": span.begin.line, "character": span.begin.column},
+                "end": {"line": span.end.line, "character": span.end.column},
+            },
+            "severity": self.severity.to_lsp_diagnostic_severity(),
+            "message": self.problem,
+            "code": self.short_name,
+        })
+    }
+}
+
 impl<T: Display> Display for LintT<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}: {}", self.location, self.problem)
@@ -86,6 +120,7 @@ impl<T: LintWarning> LintT<T> {
             severity: self.problem.severity(),
             problem: self.problem.to_string(),
             original: self.original,
+            fix: self.problem.fix(),
         }
     }
 }
@@ -104,6 +139,19 @@ pub enum EvalSeverity {
     Disabled,
 }
 
+impl EvalSeverity {
+    /// Numeric LSP `DiagnosticSeverity`, matching the conversion done by `starlark_lsp`:
+    /// 1 is error, 2 warning, 3 information and 4 hint.
+    fn to_lsp_diagnostic_severity(self) -> u8 {
+        match self {
+            EvalSeverity::Error => 1,
+            EvalSeverity::Warning => 2,
+            EvalSeverity::Advice => 4,
+            EvalSeverity::Disabled => 3,
+        }
+    }
+}
+
 impl Display for EvalSeverity {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str(match self {
@@ -21,6 +21,7 @@
 #![allow(missing_docs)]
 
 pub mod code;
+mod json;
 pub mod markdown;
 pub mod multipage;
 mod parse;
@@ -257,6 +258,17 @@ impl DocItem {
         self.get_doc_string().map(|ds| ds.summary.as_str())
     }
 
+    /// Render this item as Markdown, using `name` for headings.
+    ///
+    /// Types are rendered with [`DocType::render_markdown`],
+    /// other items like [`render_doc_item_no_link`](markdown::render_doc_item_no_link).
+    pub fn render_markdown(&self, name: &str) -> String {
+        match self {
+            DocItem::Type(t) => t.render_markdown(name),
+            _ => markdown::render_doc_item_no_link(name, self),
+        }
+    }
+
     /// Converts to a doc member, if possible.
     ///
     /// This conversion is trivial, except in the case of objects - those are flattened into a
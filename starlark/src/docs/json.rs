@@ -0,0 +1,149 @@
+/*
+ * Copyright 2019 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Render documentation as JSON, e.g. for generating an API reference.
+//!
+//! Members are always sorted by name, so the output is reproducible.
+
+use serde_json::json;
+use serde_json::Map;
+use serde_json::Value as JsonValue;
+
+use crate::docs::DocFunction;
+use crate::docs::DocItem;
+use crate::docs::DocMember;
+use crate::docs::DocModule;
+use crate::docs::DocParam;
+use crate::docs::DocProperty;
+use crate::docs::DocString;
+use crate::docs::DocType;
+use crate::typing::ty::TypeRenderConfig;
+
+fn doc_string_to_json(docs: &Option<DocString>) -> JsonValue {
+    match docs {
+        None => JsonValue::Null,
+        Some(DocString { summary, details }) => json!({
+            "summary": summary,
+            "details": details,
+        }),
+    }
+}
+
+fn members_to_json<'a, T: 'a>(
+    members: impl IntoIterator<Item = (&'a String, &'a T)>,
+    to_json: impl Fn(&T) -> JsonValue,
+) -> JsonValue {
+    let mut members: Vec<_> = members.into_iter().collect();
+    members.sort_by_key(|(name, _)| *name);
+    JsonValue::Object(
+        members
+            .into_iter()
+            .map(|(name, member)| (name.clone(), to_json(member)))
+            .collect::<Map<_, _>>(),
+    )
+}
+
+impl DocParam {
+    fn to_json(&self, starred_name: String) -> JsonValue {
+        json!({
+            "name": starred_name,
+            "type": self.typ.to_string(),
+            "default": self.default_value,
+            "docs": doc_string_to_json(&self.docs),
+        })
+    }
+}
+
+impl DocFunction {
+    /// Render this function as JSON.
+    pub fn to_json(&self) -> JsonValue {
+        json!({
+            "kind": "function",
+            "signature": format!(
+                "({}) -> {}",
+                self.params.render_code(None, &TypeRenderConfig::Default),
+                self.ret.typ,
+            ),
+            "docs": doc_string_to_json(&self.docs),
+            "params": self
+                .params
+                .doc_params_with_starred_names()
+                .map(|(name, p)| p.to_json(name))
+                .collect::<Vec<_>>(),
+            "return": {
+                "type": self.ret.typ.to_string(),
+                "docs": doc_string_to_json(&self.ret.docs),
+            },
+        })
+    }
+}
+
+impl DocProperty {
+    /// Render this property as JSON.
+    pub fn to_json(&self) -> JsonValue {
+        json!({
+            "kind": "property",
+            "type": self.typ.to_string(),
+            "docs": doc_string_to_json(&self.docs),
+        })
+    }
+}
+
+impl DocMember {
+    /// Render this member as JSON.
+    pub fn to_json(&self) -> JsonValue {
+        match self {
+            DocMember::Function(f) => f.to_json(),
+            DocMember::Property(p) => p.to_json(),
+        }
+    }
+}
+
+impl DocType {
+    /// Render this type as JSON.
+    pub fn to_json(&self) -> JsonValue {
+        json!({
+            "kind": "type",
+            "type": self.ty.to_string(),
+            "docs": doc_string_to_json(&self.docs),
+            "constructor": self.constructor.as_ref().map(DocFunction::to_json),
+            "members": members_to_json(&self.members, DocMember::to_json),
+        })
+    }
+}
+
+impl DocModule {
+    /// Render this module as JSON.
+    pub fn to_json(&self) -> JsonValue {
+        json!({
+            "kind": "module",
+            "docs": doc_string_to_json(&self.docs),
+            "members": members_to_json(&self.members, DocItem::to_json),
+        })
+    }
+}
+
+impl DocItem {
+    /// Render this item as JSON.
+    pub fn to_json(&self) -> JsonValue {
+        match self {
+            DocItem::Module(m) => m.to_json(),
+            DocItem::Type(t) => t.to_json(),
+            DocItem::Member(m) => m.to_json(),
+        }
+    }
+}
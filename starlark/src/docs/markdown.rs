@@ -321,7 +321,63 @@ impl DocModule {
     }
 }
 
+/// Render one row of the members table of [`DocType::render_markdown`].
+fn render_member_table_row(name: &str, member: &DocMember) -> String {
+    let typ = match member {
+        DocMember::Property(p) => p.typ.to_string(),
+        DocMember::Function(f) => format!(
+            "({}){}",
+            f.params.render_code(None, &TypeRenderConfig::Default),
+            raw_type_prefix(" -> ", &f.ret.typ, &TypeRenderConfig::Default),
+        ),
+    };
+    let summary = member.get_doc_summary().unwrap_or_default();
+    // `|` is both common in types and the column separator.
+    format!(
+        "| `{}` | `{}` | {} |",
+        name,
+        typ.replace('|', "\\|"),
+        summary.replace('|', "\\|"),
+    )
+}
+
 impl DocType {
+    /// Render this type as a standalone Markdown page: a heading, the type docs,
+    /// a table of members with their types and summaries, and then the full docs of each member.
+    /// Members are sorted by name.
+    pub fn render_markdown(&self, name: &str) -> String {
+        let render_config = &TypeRenderConfig::Default;
+        let mut members: Vec<(&str, &DocMember)> =
+            self.members.iter().map(|(n, m)| (&**n, m)).collect();
+        members.sort_by_key(|(n, _)| *n);
+
+        let mut res = format!("# `{name}` type");
+        if let Some(docs) = render_doc_string(DSOpts::Combined, &self.docs) {
+            res.push_str("\n\n");
+            res.push_str(&docs);
+        }
+        if let Some(constructor) = &self.constructor {
+            res.push_str("\n\n");
+            res.push_str(&render_function(name, constructor, false, render_config));
+        }
+        if !members.is_empty() {
+            res.push_str("\n\n| Member | Type | Summary |\n| --- | --- | --- |");
+            for (member_name, member) in &members {
+                res.push('\n');
+                res.push_str(&render_member_table_row(member_name, member));
+            }
+            for (member_name, member) in &members {
+                res.push_str("\n\n---\n\n");
+                res.push_str(&render_doc_member(
+                    &format!("{name}.{member_name}"),
+                    member,
+                    render_config,
+                ));
+            }
+        }
+        res
+    }
+
     pub(super) fn render_markdown_page_for_multipage_render(
         &self,
         name: &str,
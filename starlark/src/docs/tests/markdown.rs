@@ -169,8 +169,16 @@ fn module(builder: &mut GlobalsBuilder) {
         #[starlark(default=UnpackList::default())] explicit_default: UnpackList<String>,
         hidden_default: Option<UnpackList<String>>,
         #[starlark(default = "my_default")] string_default: &str,
+        #[starlark(default = i32::MAX / 2)] expr_default: i32,
+        #[starlark(default = std::cmp::max(-1, [i32::MIN][0]))] call_default: i32,
     ) -> anyhow::Result<NoneType> {
-        let _unused = (explicit_default, hidden_default, string_default);
+        let _unused = (
+            explicit_default,
+            hidden_default,
+            string_default,
+            expr_default,
+            call_default,
+        );
         Ok(NoneType)
     }
 
@@ -283,6 +291,8 @@ fn native_docs_module() {
     );
     assert!(!res.contains("starlark::assert::all_true"));
     assert!(res.contains(r#"string_default: str = "my_default"#));
+    assert!(res.contains("expr_default: int = i32::MAX / 2"));
+    assert!(res.contains("call_default: int = std::cmp::max(-1, [i32::MIN][0])"));
 }
 
 fn test_globals_docs_render(with_linked_type: bool) {
@@ -332,3 +342,14 @@ fn golden_docs_object() {
     let res = docs_golden_test("object.golden.md", DocItem::Type(docs));
     assert!(res.contains(r#"name.\_\_exported\_\_"#));
 }
+
+#[test]
+fn golden_docs_object_render_markdown() {
+    let docs = DocType::from_starlark_value::<Obj>();
+    let res = docs.render_markdown("obj");
+    golden_test_template(
+        "src/docs/tests/golden/object_render_markdown.golden.md",
+        &res,
+    );
+    assert!(res.contains("| `func1` | `(foo: str) -> str` | Docs for func1 |"));
+}
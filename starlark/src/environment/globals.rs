@@ -60,6 +60,23 @@ struct GlobalsData {
     docstring: Option<String>,
 }
 
+/// What [`Globals::merge`] does when both sides define the same name.
+#[derive(Debug, Clone, Copy, Dupe, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// Fail the merge.
+    Error,
+    /// Keep the definition from `self`.
+    PreferSelf,
+    /// Keep the definition from `other`.
+    PreferOther,
+}
+
+#[derive(Debug, thiserror::Error)]
+enum GlobalsError {
+    #[error("Cannot merge globals: `{0}` is defined in both")]
+    MergeCollision(String),
+}
+
 /// Used to build a [`Globals`] value.
 #[derive(Debug)]
 pub struct GlobalsBuilder {
@@ -137,6 +154,39 @@ impl Globals {
         &self.0.heap
     }
 
+    /// Combine two [`Globals`], resolving names defined in both according to `policy`.
+    ///
+    /// Values are shared rather than copied, so their docs and types are preserved.
+    /// If `self` has no docstring, the docstring of `other` is used.
+    pub fn merge(self, other: Globals, policy: MergePolicy) -> anyhow::Result<Globals> {
+        let mut builder = GlobalsBuilder::new();
+        builder.heap.add_reference(&self.0.heap);
+        builder.heap.add_reference(&other.0.heap);
+        for (name, value) in self.0.variables.iter() {
+            builder.variables.insert(name.as_str(), value.clone());
+        }
+        // Iterate in sorted order so the reported collision is deterministic.
+        for name in &other.0.variable_names {
+            let value = other.0.variables.get_str(name.as_str()).unwrap();
+            if builder.contains(name.as_str()) {
+                match policy {
+                    MergePolicy::Error => {
+                        return Err(GlobalsError::MergeCollision(name.as_str().to_owned()).into());
+                    }
+                    MergePolicy::PreferSelf => continue,
+                    MergePolicy::PreferOther => {}
+                }
+            }
+            builder.variables.insert(name.as_str(), value.clone());
+        }
+        builder.docstring = self
+            .0
+            .docstring
+            .clone()
+            .or_else(|| other.0.docstring.clone());
+        Ok(builder.build())
+    }
+
     /// Print information about the values in this object.
     pub fn describe(&self) -> String {
         self.0
@@ -151,6 +201,15 @@ impl Globals {
         self.0.docstring.as_deref()
     }
 
+    /// Get the documentation for both the object itself, and its members, as JSON.
+    ///
+    /// The result has the shape `{"kind": "module", "docs": ..., "members": {...}}`,
+    /// where each member describes a function, property, type or nested namespace.
+    /// Members are sorted by name, so the output is stable.
+    pub fn describe_json(&self) -> serde_json::Value {
+        self.documentation().to_json()
+    }
+
     /// Get the documentation for both the object itself, and its members.
     pub fn documentation(&self) -> DocModule {
         let (docs, members) = common_documentation(
@@ -235,11 +294,22 @@ impl GlobalsBuilder {
     }
 
     /// A fluent API for modifying [`GlobalsBuilder`] using [`namespace`](GlobalsBuilder::namespace).
-    pub fn with_namespace(mut self, name: &str, f: impl Fn(&mut GlobalsBuilder)) -> Self {
+    pub fn with_namespace(mut self, name: &str, f: impl FnOnce(&mut GlobalsBuilder)) -> Self {
         self.namespace(name, f);
         self
     }
 
+    /// Names registered so far at the top level, in unspecified order.
+    /// Members of nested namespaces are not included.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.variables.keys().map(|x| x.as_str())
+    }
+
+    /// Is `name` already registered at the top level?
+    pub fn contains(&self, name: &str) -> bool {
+        self.variables.get_str(name).is_some()
+    }
+
     /// Called at the end to build a [`Globals`].
     pub fn build(self) -> Globals {
         let mut variable_names: Vec<_> = self
@@ -298,7 +368,9 @@ impl GlobalsBuilder {
             NativeFunction {
                 function: Box::new(f),
                 name: name.to_owned(),
+                profile_name: components.profile_name.map(|s| s.to_owned()),
                 speculative_exec_safe: components.speculative_exec_safe,
+                nondeterministic: components.nondeterministic,
                 as_type: as_type.as_ref().map(|x| x.0.dupe()),
                 ty: ty.unwrap_or_else(|| {
                     Ty::from_native_callable_components(
@@ -395,6 +467,8 @@ mod tests {
 
     use super::*;
     use crate as starlark;
+    use crate::assert::Assert;
+    use crate::docs::DocMember;
 
     #[test]
     fn test_send_sync()
@@ -410,6 +484,119 @@ mod tests {
         }
     }
 
+    #[starlark_module]
+    fn register_math(builder: &mut GlobalsBuilder) {
+        fn double(x: i32) -> anyhow::Result<i32> {
+            Ok(x * 2)
+        }
+
+        fn square(x: i32) -> anyhow::Result<i32> {
+            Ok(x * x)
+        }
+    }
+
+    #[test]
+    fn test_with_namespace() {
+        let globals = GlobalsBuilder::new()
+            .with_namespace("math", register_math)
+            .build();
+        assert_eq!(
+            globals.names().map(|n| n.as_str()).collect::<Vec<_>>(),
+            ["math"]
+        );
+
+        let mut a = Assert::new();
+        a.globals(globals);
+        a.eq("20", "math.square(math.double(5)) // 5");
+    }
+
+    #[starlark_module]
+    fn register_foo_bar(builder: &mut GlobalsBuilder) {
+        fn foo() -> anyhow::Result<i32> {
+            Ok(2)
+        }
+
+        fn bar() -> anyhow::Result<i32> {
+            Ok(3)
+        }
+    }
+
+    #[test]
+    fn test_builder_names() {
+        let globals = GlobalsBuilder::new().with(register_foo);
+        assert!(globals.contains("foo"));
+        assert!(!globals.contains("bar"));
+
+        let globals = globals.with(register_foo_bar);
+        assert!(globals.contains("foo"));
+        assert!(globals.contains("bar"));
+        assert_eq!(globals.names().sorted().collect::<Vec<_>>(), ["bar", "foo"]);
+
+        let mut a = Assert::new();
+        a.globals(globals.build());
+        a.eq("2", "foo()");
+    }
+
+    fn merged(policy: MergePolicy) -> anyhow::Result<Globals> {
+        let left = GlobalsBuilder::new().with(register_foo).build();
+        let right = GlobalsBuilder::new().with(register_foo_bar).build();
+        left.merge(right, policy)
+    }
+
+    #[test]
+    fn test_merge_error() {
+        let err = merged(MergePolicy::Error).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Cannot merge globals: `foo` is defined in both"
+        );
+    }
+
+    #[test]
+    fn test_merge_prefer_self() {
+        let globals = merged(MergePolicy::PreferSelf).unwrap();
+        assert_eq!(
+            globals.names().map(|n| n.as_str()).collect::<Vec<_>>(),
+            ["bar", "foo"]
+        );
+        let DocItem::Member(DocMember::Function(foo)) =
+            globals.documentation().members.shift_remove("foo").unwrap()
+        else {
+            unreachable!()
+        };
+        assert_eq!(foo.ret.typ, Ty::int());
+        let mut a = Assert::new();
+        a.globals(globals);
+        a.eq("1", "foo()");
+        a.eq("3", "bar()");
+    }
+
+    #[test]
+    fn test_merge_prefer_other() {
+        let mut a = Assert::new();
+        a.globals(merged(MergePolicy::PreferOther).unwrap());
+        a.eq("2", "foo()");
+        a.eq("3", "bar()");
+    }
+
+    #[test]
+    fn test_describe_json() {
+        let mut globals = GlobalsBuilder::new();
+        globals.set("x", FrozenValue::new_none());
+        globals.namespace("ns", register_foo);
+        register_foo(&mut globals);
+        let json = globals.build().describe_json();
+
+        let members = json["members"].as_object().unwrap();
+        assert_eq!(members.keys().collect::<Vec<_>>(), ["foo", "ns", "x"]);
+        assert_eq!(members["foo"]["kind"], "function");
+        assert_eq!(members["foo"]["signature"], "() -> int");
+        assert_eq!(members["x"]["kind"], "property");
+        assert_eq!(members["x"]["type"], "None");
+        assert_eq!(members["ns"]["kind"], "module");
+        assert_eq!(members["ns"]["members"]["foo"]["return"]["type"], "int");
+    }
+
     #[test]
     fn test_doc_hidden() {
         let mut globals = GlobalsBuilder::new();
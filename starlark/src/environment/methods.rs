@@ -94,6 +94,14 @@ impl Methods {
         self.members.keys().map(|x| x.as_str().to_owned()).collect()
     }
 
+    /// Members registered with `#[starlark(attribute)]`.
+    pub(crate) fn attributes(&self) -> impl Iterator<Item = (&str, &UnboundValue)> {
+        self.members
+            .iter()
+            .filter(|(_, v)| matches!(v, UnboundValue::Attr(..)))
+            .map(|(k, v)| (k.as_str(), v))
+    }
+
     pub(crate) fn members(&self) -> impl Iterator<Item = (&str, FrozenValue)> {
         self.members
             .iter()
@@ -227,6 +235,7 @@ impl MethodsBuilder {
                     function,
                     name: name.to_owned(),
                     speculative_exec_safe: components.speculative_exec_safe,
+                    nondeterministic: components.nondeterministic,
                     docs: components.into_docs(None),
                     ty,
                 }))
@@ -78,6 +78,10 @@ enum ModuleError {
 /// can be obtained using [`frozen_heap`](FrozenModule::frozen_heap). Be careful not to use
 /// these values after the [`FrozenModule`] has been released unless you obtain a reference
 /// to the frozen heap.
+///
+/// A [`FrozenModule`] is `Send + Sync` and cheap to [`dupe`](Dupe::dupe) (it is a pair of `Arc`s),
+/// so a module can be frozen once and then loaded by evaluations running concurrently
+/// on different threads.
 #[derive(Debug, Clone, Dupe, Allocative)]
 // We store the two elements separately since the FrozenHeapRef contains
 // a copy of the FrozenModuleData inside it.
@@ -458,6 +462,35 @@ impl Module {
         })
     }
 
+    /// Freeze the values bound to `names` into a new [`FrozenModule`] exporting only them.
+    ///
+    /// This module is not modified, so evaluation can continue on top of it.
+    /// The exported values and everything reachable from them are copied
+    /// (see [`Value::deep_freeze_clone`]), so later mutations in this module
+    /// are not visible in the frozen module.
+    /// Values which cannot be copied are an error,
+    /// in particular functions defined in this module, which refer to its mutable bindings.
+    pub fn freeze_exports(&self, names: &[&str]) -> anyhow::Result<FrozenModule> {
+        let start = Instant::now();
+        let exports = Module::new();
+        for name in names {
+            let value = self
+                .get(name)
+                .ok_or_else(|| EnvironmentError::ModuleHasNoSymbol((*name).to_owned()))?;
+            let value = value.deep_freeze_clone_to_heap(&exports.frozen_heap)?;
+            exports.set(name, value.to_value());
+        }
+        if let Some(extra_value) = self.extra_value() {
+            let extra_value = extra_value.deep_freeze_clone_to_heap(&exports.frozen_heap)?;
+            exports.set_extra_value(extra_value.to_value());
+        }
+        if let Some(docstring) = self.docstring.borrow().clone() {
+            exports.set_docstring(docstring);
+        }
+        exports.add_eval_duration(self.eval_duration.get() + start.elapsed());
+        exports.freeze()
+    }
+
     /// Set the value of a variable in the environment.
     /// Modifying these variables while executing is ongoing can have
     /// surprising effects.
@@ -566,6 +599,10 @@ where
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+    use std::thread;
+
+    use dupe::Dupe;
     use starlark_derive::starlark_module;
 
     use crate as starlark;
@@ -575,10 +612,57 @@ mod tests {
     use crate::environment::Module;
     use crate::eval::runtime::profile::mode::ProfileMode;
     use crate::eval::Evaluator;
+    use crate::eval::ReturnFileLoader;
     use crate::syntax::AstModule;
     use crate::syntax::Dialect;
     use crate::values::list::ListRef;
 
+    #[test]
+    fn test_frozen_module_shared_across_threads() {
+        let lib = Module::new();
+        {
+            let mut eval = Evaluator::new(&lib);
+            eval.eval_module(
+                AstModule::parse(
+                    "lib.star",
+                    "def f(x):\n    return [x] * 3\nN = {'a': 1}".to_owned(),
+                    &Dialect::Standard,
+                )
+                .unwrap(),
+                &Globals::standard(),
+            )
+            .unwrap();
+        }
+        let lib = lib.freeze().unwrap();
+
+        let threads: Vec<_> = (0..4)
+            .map(|i| {
+                let lib = lib.dupe();
+                thread::spawn(move || {
+                    let modules = HashMap::from([("lib.star", &lib)]);
+                    let loader = ReturnFileLoader { modules: &modules };
+                    let module = Module::new();
+                    let mut eval = Evaluator::new(&module);
+                    eval.set_loader(&loader);
+                    let res = eval
+                        .eval_module(
+                            AstModule::parse(
+                                "main.star",
+                                format!("load('lib.star', 'f', 'N')\nlen(f({i})) + N['a'] + {i}"),
+                                &Dialect::Standard,
+                            )
+                            .unwrap(),
+                            &Globals::standard(),
+                        )
+                        .unwrap();
+                    res.unpack_i32().unwrap()
+                })
+            })
+            .collect();
+        let results: Vec<i32> = threads.into_iter().map(|t| t.join().unwrap()).collect();
+        assert_eq!(vec![4, 5, 6, 7], results);
+    }
+
     #[test]
     fn test_gen_heap_summary_profile() {
         let module = Module::new();
@@ -609,6 +693,60 @@ x = f(1)
         assert!(heap_summary.contains("\"x.star.f\""), "{:?}", heap_summary);
     }
 
+    #[test]
+    fn test_freeze_exports() {
+        let module = Module::new();
+        let mut eval = Evaluator::new(&module);
+        eval.eval_module(
+            AstModule::parse(
+                "x.star",
+                "n = 1\nxs = [1, 2]\nd = {'xs': xs, 's': 'x'}\ndef f():\n    return n"
+                    .to_owned(),
+                &Dialect::Standard,
+            )
+            .unwrap(),
+            &Globals::standard(),
+        )
+        .unwrap();
+
+        // Functions refer to the mutable bindings of the module, so cannot be frozen.
+        assert!(module.freeze_exports(&["n", "f"]).is_err());
+        assert!(module.freeze_exports(&["missing"]).is_err());
+
+        let frozen = module.freeze_exports(&["n", "d"]).unwrap();
+        assert_eq!(
+            vec!["n", "d"],
+            frozen
+                .names()
+                .map(|x| x.as_str().to_owned())
+                .collect::<Vec<_>>()
+        );
+        assert!(frozen.get("xs").is_err());
+
+        // The module keeps all the bindings mutable for further evaluation,
+        // and its mutations are not visible in the frozen module.
+        let res = eval
+            .eval_module(
+                AstModule::parse(
+                    "y.star",
+                    "xs.append(3)\nf() + len(d['xs'])".to_owned(),
+                    &Dialect::Standard,
+                )
+                .unwrap(),
+                &Globals::standard(),
+            )
+            .unwrap();
+        assert_eq!(Some(4), res.unpack_i32());
+        drop(eval);
+        drop(module);
+
+        assert_eq!(Some(1), frozen.get("n").unwrap().unpack_i32());
+        assert_eq!(
+            "{\"xs\": [1, 2], \"s\": \"x\"}",
+            frozen.get("d").unwrap().value().to_repr()
+        );
+    }
+
     #[test]
     fn test_frozen_module_from_globals() {
         #[starlark_module]
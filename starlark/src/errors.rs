@@ -22,5 +22,7 @@ pub use starlark_syntax::frame::Frame;
 pub use crate::analysis::EvalMessage;
 pub use crate::analysis::EvalSeverity;
 pub use crate::analysis::Lint;
+pub use crate::errors::native_callable_error::NativeCallableError;
 
 pub(crate) mod did_you_mean;
+mod native_callable_error;
@@ -0,0 +1,68 @@
+/*
+ * Copyright 2018 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/// Error type which can be returned from native functions and methods
+/// defined with [`#[starlark_module]`](crate::starlark_module).
+///
+/// Implemented for [`anyhow::Error`] (converted to [`ErrorKind::Native`](crate::ErrorKind::Native))
+/// and [`starlark::Error`](crate::Error) (passed through unchanged).
+/// Implement it for custom error types to pick the [`ErrorKind`](crate::ErrorKind)
+/// reported to callers, which they can then `match` on:
+///
+/// ```
+/// use starlark::errors::NativeCallableError;
+/// use starlark::environment::GlobalsBuilder;
+/// use starlark::starlark_module;
+///
+/// #[derive(Debug, thiserror::Error)]
+/// #[error("negative value: {0}")]
+/// struct NegativeError(i32);
+///
+/// impl NativeCallableError for NegativeError {
+///     fn into_starlark_error(self) -> starlark::Error {
+///         starlark::Error::new_value(self)
+///     }
+/// }
+///
+/// #[starlark_module]
+/// fn globals(builder: &mut GlobalsBuilder) {
+///     fn check_positive(x: i32) -> Result<i32, NegativeError> {
+///         if x < 0 { Err(NegativeError(x)) } else { Ok(x) }
+///     }
+/// }
+/// ```
+pub trait NativeCallableError {
+    /// Convert into a starlark error.
+    fn into_starlark_error(self) -> crate::Error;
+}
+
+/// This implementation should not be used by starlark itself:
+/// starlark native functions should not return `anyhow::Error`,
+/// and should not convert to `ErrorKind::Native`.
+impl NativeCallableError for anyhow::Error {
+    #[cold]
+    fn into_starlark_error(self) -> crate::Error {
+        crate::Error::new_native(self)
+    }
+}
+
+impl NativeCallableError for crate::Error {
+    #[cold]
+    fn into_starlark_error(self) -> crate::Error {
+        self
+    }
+}
@@ -19,6 +19,7 @@
 //! [`eval_module`](Evaluator::eval_module).
 
 pub(crate) mod bc;
+pub(crate) mod blocking;
 pub(crate) mod compiler;
 mod params;
 pub(crate) mod runtime;
@@ -30,13 +31,16 @@ use std::mem;
 use std::time::Instant;
 
 use dupe::Dupe;
+pub use blocking::BlockingHandle;
 pub use runtime::arguments::Arguments;
 pub use runtime::before_stmt::BeforeStmtFuncDyn;
 pub use runtime::evaluator::Evaluator;
 pub use runtime::file_loader::FileLoader;
+pub use runtime::file_loader::InMemoryFileLoader;
 pub use runtime::file_loader::ReturnFileLoader;
 pub use runtime::params::parser::ParametersParser;
 pub use runtime::params::spec::ParametersSpec;
+pub use runtime::params::spec::ParametersSpecBuilder;
 pub use runtime::params::spec::ParametersSpecParam;
 pub use runtime::profile::data::ProfileData;
 pub use runtime::profile::mode::ProfileMode;
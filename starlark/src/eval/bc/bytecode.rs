@@ -26,6 +26,7 @@ use crate::eval::bc::for_loop::LoopDepth;
 use crate::eval::bc::frame::BcFramePtr;
 use crate::eval::bc::instr::BcInstr;
 use crate::eval::bc::instr::InstrControl;
+use crate::eval::bc::instr_impl::with_exit;
 use crate::eval::bc::instr_impl::InstrEnd;
 use crate::eval::bc::instrs::BcInstrs;
 use crate::eval::bc::opcode::BcOpcode;
@@ -155,6 +156,7 @@ pub(crate) fn run_block<'v, EC: EvaluationCallbacks>(
 ) -> Result<Value<'v>, EvalException> {
     // Copy frame pointer to local variable to generate more efficient code.
     let frame = eval.current_frame;
+    let with_depth = eval.with_stack.len();
 
     loop {
         // Note most functions called from here must be carefully annotated
@@ -165,7 +167,21 @@ pub(crate) fn run_block<'v, EC: EvaluationCallbacks>(
         ip = match step(eval, ec, frame, ip) {
             InstrControl::Next(ip) => ip,
             InstrControl::Return(v) => return Ok(v),
-            InstrControl::Err(e) => return Err(Bc::wrap_error_for_instr_ptr(ip, e, eval)),
+            InstrControl::Err(e) => {
+                let e = Bc::wrap_error_for_instr_ptr(ip, e, eval);
+                exit_with_blocks(eval, with_depth);
+                return Err(e);
+            }
         }
     }
 }
+
+/// Call `__exit__` of the `with` statements the error is propagating through.
+/// The errors of `__exit__` are ignored: the original error is more relevant.
+#[cold]
+#[inline(never)]
+fn exit_with_blocks(eval: &mut Evaluator, with_depth: usize) {
+    while eval.with_stack.len() > with_depth {
+        let _ignore = with_exit(eval);
+    }
+}
@@ -127,7 +127,7 @@ impl ExprCompiled {
             ExprCompiled::Local(local) => bc.mark_definitely_assigned(*local),
             ExprCompiled::LocalCaptured(_) => {}
             ExprCompiled::Module(_) => {}
-            ExprCompiled::Tuple(xs) | ExprCompiled::List(xs) => {
+            ExprCompiled::Tuple(xs) | ExprCompiled::List(xs) | ExprCompiled::Set(xs) => {
                 for x in xs {
                     x.mark_definitely_assigned_after(bc);
                 }
@@ -335,6 +335,15 @@ impl IrSpanned<ExprCompiled> {
                 }
             }
             ExprCompiled::Dict(ref xs) => Self::write_dict(span, xs, target, bc),
+            ExprCompiled::Set(ref xs) => {
+                let spans = xs.map(|x| x.span);
+                write_exprs(xs, bc, |xs, bc| {
+                    bc.write_instr_explicit::<InstrSetNPop>(
+                        BcInstrSlowArg { span, spans },
+                        (xs, target),
+                    );
+                });
+            }
             ExprCompiled::Compr(ref compr) => compr.write_bc(span, target, bc),
             ExprCompiled::Slice(l_start_stop_step) => {
                 let (l, start, stop, step) = &**l_start_stop_step;
@@ -73,6 +73,35 @@ pub(crate) fn write_for(
     bc.restore_definitely_assigned(definitely_assigned);
 }
 
+pub(crate) fn write_with(
+    expr: &IrSpanned<ExprCompiled>,
+    target: Option<&IrSpanned<AssignCompiledValue>>,
+    span: FrameSpan,
+    bc: &mut BcWriter,
+    body: impl FnOnce(&mut BcWriter),
+) {
+    expr.write_bc_cb(bc, |cm, bc| {
+        if let Some(var) = target.and_then(|target| target.as_local_non_captured()) {
+            // Typical case: `with ... as x: ...`,
+            // store the result of `__enter__` directly to a local variable.
+            bc.write_with(cm, var.to_bc_slot().to_out(), span, |bc| {
+                bc.mark_definitely_assigned(var);
+                body(bc);
+            })
+        } else {
+            bc.alloc_slot(|value_slot, bc| {
+                bc.write_with(cm, value_slot.to_out(), span, |bc| {
+                    if let Some(target) = target {
+                        target.write_bc(value_slot.to_in(), bc);
+                        target.mark_definitely_assigned_after(bc);
+                    }
+                    body(bc);
+                })
+            })
+        }
+    });
+}
+
 impl StmtsCompiled {
     pub(crate) fn write_bc(&self, compiler: &StmtCompileContext, bc: &mut BcWriter) {
         for stmt in self.stmts() {
@@ -119,6 +148,11 @@ impl StmtCompiled {
                 let (_var, over, _body) = &**var_over_body;
                 over.mark_definitely_assigned_after(bc);
             }
+            StmtCompiled::With(expr_target_body) => {
+                // Variables assigned in the body are marked while writing the body.
+                let (expr, _target, _body) = &**expr_target_body;
+                expr.mark_definitely_assigned_after(bc);
+            }
             StmtCompiled::Break => {}
             StmtCompiled::Continue => {}
         }
@@ -192,12 +226,15 @@ impl IrSpanned<StmtCompiled> {
         bc.write_iter_stop(span);
         if compiler.has_return_type {
             expr.write_bc_cb(bc, |slot, bc| {
+                bc.write_with_exit_all(span);
                 bc.write_instr::<InstrReturnCheckType>(span, slot);
             });
         } else if let Some(value) = expr.as_value() {
+            bc.write_with_exit_all(span);
             bc.write_instr::<InstrReturnConst>(span, value);
         } else {
             expr.write_bc_cb(bc, |slot, bc| {
+                bc.write_with_exit_all(span);
                 bc.write_instr::<InstrReturn>(span, slot);
             });
         }
@@ -244,6 +281,12 @@ impl IrSpanned<StmtCompiled> {
                 let (assign, over, body) = &**assign_over_body;
                 write_for(over, assign, span, bc, |bc| body.write_bc(compiler, bc));
             }
+            StmtCompiled::With(expr_target_body) => {
+                let (expr, target, body) = &**expr_target_body;
+                write_with(expr, target.as_ref(), span, bc, |bc| {
+                    body.write_bc(compiler, bc)
+                });
+            }
             StmtCompiled::Break => {
                 bc.write_break(span);
             }
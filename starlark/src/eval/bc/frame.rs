@@ -303,7 +303,10 @@ unsafe impl<'v> Trace<'v> for BcFrame<'v> {
 
 unsafe impl<'v> Trace<'v> for BcFramePtr<'v> {
     fn trace(&mut self, tracer: &Tracer<'v>) {
-        self.frame_mut().trace(tracer);
+        // There is no frame between evaluations.
+        if self.is_inititalized() {
+            self.frame_mut().trace(tracer);
+        }
     }
 }
 
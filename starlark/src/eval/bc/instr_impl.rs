@@ -73,6 +73,7 @@ use crate::eval::ParametersSpec;
 use crate::values::dict::Dict;
 use crate::values::int::pointer_i32::PointerI32;
 use crate::values::layout::value_not_special::FrozenValueNotSpecial;
+use crate::values::set::value::SetData;
 use crate::values::string::dot_format::format_one;
 use crate::values::string::interpolation::percent_s_one;
 use crate::values::types::known_methods::KnownMethod;
@@ -910,6 +911,7 @@ pub(crate) struct InstrListOfConstsImpl;
 pub(crate) struct InstrDictOfConstsImpl;
 pub(crate) struct InstrDictConstKeysImpl;
 pub(crate) struct InstrDictNPopImpl;
+pub(crate) struct InstrSetNPopImpl;
 pub(crate) struct InstrListNewImpl;
 pub(crate) struct InstrDictNewImpl;
 
@@ -921,6 +923,7 @@ pub(crate) type InstrDictNew = InstrNoFlow<InstrDictNewImpl>;
 pub(crate) type InstrDictOfConsts = InstrNoFlow<InstrDictOfConstsImpl>;
 pub(crate) type InstrDictConstKeys = InstrNoFlow<InstrDictConstKeysImpl>;
 pub(crate) type InstrDictNPop = InstrNoFlow<InstrDictNPopImpl>;
+pub(crate) type InstrSetNPop = InstrNoFlow<InstrSetNPopImpl>;
 
 impl InstrNoFlowImpl for InstrTupleNPopImpl {
     type Arg = (BcSlotInRange, BcSlotOut);
@@ -1045,6 +1048,33 @@ impl InstrNoFlowImpl for InstrDictConstKeysImpl {
     }
 }
 
+impl InstrNoFlowImpl for InstrSetNPopImpl {
+    type Arg = (BcSlotInRange, BcSlotOut);
+
+    fn run_with_args<'v>(
+        eval: &mut Evaluator<'v, '_, '_>,
+        frame: BcFramePtr<'v>,
+        ip: BcPtrAddr,
+        (npops, target): &(BcSlotInRange, BcSlotOut),
+    ) -> crate::Result<()> {
+        let items = frame.get_bc_slot_range(*npops);
+        let mut set = SetData::default();
+        for (i, x) in items.iter().enumerate() {
+            let x = match x.get_hashed() {
+                Ok(x) => x,
+                Err(e) => {
+                    let spans = &Bc::slow_arg_at_ptr(ip).spans;
+                    return Err(add_span_to_expr_error(e, spans[i], eval).into_error());
+                }
+            };
+            set.add_hashed(x);
+        }
+        let set = eval.heap().alloc(set);
+        frame.set_bc_slot(*target, set);
+        Ok(())
+    }
+}
+
 impl InstrNoFlowImpl for InstrListNewImpl {
     type Arg = BcSlotOut;
 
@@ -1276,6 +1306,9 @@ impl BcInstr for InstrContinue {
             BcAddrOffset,
         ),
     ) -> InstrControl<'v, 'b> {
+        if let Err(e) = eval.step() {
+            return InstrControl::Err(e);
+        }
         let iter = frame.get_bc_slot(*iter);
         let loop_depth = *loop_depth;
         let i = frame.get_iter_index(loop_depth);
@@ -1325,6 +1358,54 @@ impl BcInstr for InstrIterStop {
     }
 }
 
+pub(crate) struct InstrWithEnterImpl;
+pub(crate) struct InstrWithExitImpl;
+
+/// Call `__enter__` of the context manager of a `with` statement.
+pub(crate) type InstrWithEnter = InstrNoFlow<InstrWithEnterImpl>;
+/// Call `__exit__` of the context manager of the innermost `with` statement.
+pub(crate) type InstrWithExit = InstrNoFlow<InstrWithExitImpl>;
+
+impl InstrNoFlowImpl for InstrWithEnterImpl {
+    type Arg = (BcSlotIn, BcSlotOut);
+
+    fn run_with_args<'v>(
+        eval: &mut Evaluator<'v, '_, '_>,
+        frame: BcFramePtr<'v>,
+        _ip: BcPtrAddr,
+        (cm, target): &(BcSlotIn, BcSlotOut),
+    ) -> crate::Result<()> {
+        let cm = frame.get_bc_slot(*cm);
+        let value = cm
+            .get_attr_error("__enter__", eval.heap())?
+            .invoke_pos(&[], eval)?;
+        eval.with_stack.push(cm);
+        frame.set_bc_slot(*target, value);
+        Ok(())
+    }
+}
+
+impl InstrNoFlowImpl for InstrWithExitImpl {
+    type Arg = ();
+
+    fn run_with_args<'v>(
+        eval: &mut Evaluator<'v, '_, '_>,
+        _frame: BcFramePtr<'v>,
+        _ip: BcPtrAddr,
+        (): &(),
+    ) -> crate::Result<()> {
+        with_exit(eval)
+    }
+}
+
+/// Pop the innermost context manager and call its `__exit__`.
+pub(crate) fn with_exit(eval: &mut Evaluator) -> crate::Result<()> {
+    let cm = eval.with_stack.pop().unwrap();
+    cm.get_attr_error("__exit__", eval.heap())?
+        .invoke_pos(&[], eval)?;
+    Ok(())
+}
+
 pub(crate) struct InstrReturnConst;
 pub(crate) struct InstrReturn;
 pub(crate) struct InstrReturnCheckType;
@@ -56,6 +56,6 @@ impl BcNativeFunction {
         args: &Arguments<'v, '_>,
         eval: &mut Evaluator<'v, '_, '_>,
     ) -> crate::Result<Value<'v>> {
-        self.imp.invoke(eval, args)
+        eval.with_native_frame(self.to_value(), |eval| self.imp.invoke(eval, args))
     }
 }
@@ -88,6 +88,7 @@ pub(crate) enum BcOpcode {
     DictNPop,
     DictOfConsts,
     DictConstKeys,
+    SetNPop,
     ComprListAppend,
     ComprDictInsert,
     CheckType,
@@ -98,6 +99,8 @@ pub(crate) enum BcOpcode {
     Continue,
     Break,
     IterStop,
+    WithEnter,
+    WithExit,
     Return,
     ReturnConst,
     ReturnCheckType,
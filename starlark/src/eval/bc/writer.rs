@@ -38,6 +38,8 @@ use crate::eval::bc::instr_impl::InstrLoadLocal;
 use crate::eval::bc::instr_impl::InstrLoadLocalCaptured;
 use crate::eval::bc::instr_impl::InstrMov;
 use crate::eval::bc::instr_impl::InstrStoreLocalCaptured;
+use crate::eval::bc::instr_impl::InstrWithEnter;
+use crate::eval::bc::instr_impl::InstrWithExit;
 use crate::eval::bc::instrs::BcInstrsWriter;
 use crate::eval::bc::instrs::PatchAddr;
 use crate::eval::bc::repr::BC_INSTR_ALIGN;
@@ -137,6 +139,8 @@ pub(crate) struct BcWriter<'f> {
     for_loops: Vec<BcWriterForLoop>,
     /// Max observed loop depth.
     max_loop_depth: LoopDepth,
+    /// For each `with` block being written, the number of enclosing `for` loops.
+    with_blocks: Vec<usize>,
 
     /// Allocate various objects here.
     pub(crate) heap: &'f FrozenHeap,
@@ -166,6 +170,7 @@ impl<'f> BcWriter<'f> {
             heap,
             for_loops: Vec::new(),
             max_loop_depth: LoopDepth(0),
+            with_blocks: Vec::new(),
         }
     }
 
@@ -183,11 +188,13 @@ impl<'f> BcWriter<'f> {
             heap,
             for_loops,
             max_loop_depth,
+            with_blocks,
         } = self;
         let _ = heap;
         let _ = definitely_assigned;
         assert_eq!(stack_size, 0);
         assert!(for_loops.is_empty());
+        assert!(with_blocks.is_empty());
         // Drop lifetime.
         let local_names = unsafe {
             transmute!(
@@ -397,6 +404,7 @@ impl<'f> BcWriter<'f> {
     }
 
     pub(crate) fn write_continue(&mut self, span: FrameSpan) {
+        self.write_with_exit_loop(span);
         let loop_depth = LoopDepth(self.for_loops.len().checked_sub(1).unwrap() as u32);
         let for_loop = self.for_loops.last().unwrap();
         let jump_back = self.ip().offset_from(for_loop.inner_addr).neg();
@@ -417,6 +425,7 @@ impl<'f> BcWriter<'f> {
     }
 
     pub(crate) fn write_break(&mut self, span: FrameSpan) {
+        self.write_with_exit_loop(span);
         let for_loop = self.for_loops.last().unwrap();
         let (addr, arg) =
             self.write_instr_ret_arg::<InstrBreak>(span, (for_loop.iter, BcAddrOffset::FORWARD));
@@ -473,6 +482,41 @@ impl<'f> BcWriter<'f> {
         }
     }
 
+    /// Write `with` statement: call `__enter__` storing the result in `target`,
+    /// then the body, then `__exit__`.
+    pub(crate) fn write_with(
+        &mut self,
+        cm: BcSlotIn,
+        target: BcSlotOut,
+        span: FrameSpan,
+        body: impl FnOnce(&mut BcWriter),
+    ) {
+        self.write_instr::<InstrWithEnter>(span, (cm, target));
+        self.with_blocks.push(self.for_loops.len());
+        body(self);
+        self.with_blocks.pop().unwrap();
+        self.write_instr::<InstrWithExit>(span, ());
+    }
+
+    /// Write instructions to exit the `with` blocks inside the innermost loop.
+    /// This is done before `break` and `continue`.
+    fn write_with_exit_loop(&mut self, span: FrameSpan) {
+        for i in (0..self.with_blocks.len()).rev() {
+            if self.with_blocks[i] < self.for_loops.len() {
+                break;
+            }
+            self.write_instr::<InstrWithExit>(span, ());
+        }
+    }
+
+    /// Write instructions to exit all current `with` blocks.
+    /// This is done before `return`, after the returned value is computed.
+    pub(crate) fn write_with_exit_all(&mut self, span: FrameSpan) {
+        for _ in 0..self.with_blocks.len() {
+            self.write_instr::<InstrWithExit>(span, ());
+        }
+    }
+
     fn stack_add(&mut self, add: u32) {
         self.stack_size += add;
         self.max_stack_size = cmp::max(self.max_stack_size, self.stack_size);
@@ -0,0 +1,53 @@
+/*
+ * Copyright 2019 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::eval::Evaluator;
+
+#[derive(Debug, thiserror::Error)]
+enum BlockingError {
+    #[error("Async native function called, but no blocking handle is set on the evaluator")]
+    NoBlockingHandle,
+    #[error("Blocking handle returned before the future completed")]
+    FutureNotCompleted,
+}
+
+/// Executor used to run async native functions to completion.
+///
+/// Native functions declared as `async fn` in a `#[starlark_module]`
+/// are resolved by calling [`block_on`](BlockingHandle::block_on)
+/// on the handle set with [`Evaluator::set_blocking_handle`].
+pub trait BlockingHandle {
+    /// Drive the future to completion, blocking the current thread.
+    fn block_on<'f>(&self, future: Pin<Box<dyn Future<Output = ()> + 'f>>);
+}
+
+/// Run a future returned by an async native function.
+///
+/// This function is called by generated code.
+pub fn block_on<F: Future>(eval: &Evaluator, future: F) -> crate::Result<F::Output> {
+    let Some(handle) = eval.blocking_handle else {
+        return Err(crate::Error::new_other(BlockingError::NoBlockingHandle));
+    };
+    let mut output = None;
+    handle.block_on(Box::pin(async {
+        output = Some(future.await);
+    }));
+    output.ok_or_else(|| crate::Error::new_other(BlockingError::FutureNotCompleted))
+}
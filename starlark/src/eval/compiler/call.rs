@@ -220,9 +220,11 @@ impl CallCompiled {
         let eval = ctx.eval()?;
 
         // Only if all call arguments are frozen values.
-        args.all_values(|arguments| {
-            let v = fun.to_value().invoke(arguments.frozen_to_v(), eval).ok()?;
-            ExprCompiled::try_value(span, v, eval.module_env.frozen_heap())
+        eval.without_time_flame_profile(|eval| {
+            args.all_values(|arguments| {
+                let v = fun.to_value().invoke(arguments.frozen_to_v(), eval).ok()?;
+                ExprCompiled::try_value(span, v, eval.module_env.frozen_heap())
+            })
         })?
     }
 
@@ -48,14 +48,20 @@ pub(crate) struct Constants {
     pub(crate) fn_tuple: BuiltinFn,
     pub(crate) fn_isinstance: BuiltinFn,
     pub(crate) fn_set: BuiltinFn,
-    // Technically, this is not a function.
+    // Technically, these are not functions.
     pub(crate) typing_callable: BuiltinFn,
+    pub(crate) typing_literal: BuiltinFn,
 }
 
 impl Constants {
     pub fn get() -> &'static Constants {
         static RES: Lazy<Constants> = Lazy::new(|| {
             let g = Globals::extended_internal();
+            let typing = g
+                .get_frozen("typing")
+                .unwrap()
+                .downcast_frozen_ref::<FrozenNamespace>()
+                .unwrap();
             Constants {
                 fn_len: BuiltinFn(g.get_frozen("len").unwrap()),
                 fn_type: BuiltinFn(g.get_frozen("type").unwrap()),
@@ -64,14 +70,8 @@ impl Constants {
                 fn_tuple: BuiltinFn(g.get_frozen("tuple").unwrap()),
                 fn_isinstance: BuiltinFn(g.get_frozen("isinstance").unwrap()),
                 fn_set: BuiltinFn(g.get_frozen("set").unwrap()),
-                typing_callable: {
-                    let typing = g
-                        .get_frozen("typing")
-                        .unwrap()
-                        .downcast_frozen_ref::<FrozenNamespace>()
-                        .unwrap();
-                    BuiltinFn(typing.as_ref().get("Callable").unwrap())
-                },
+                typing_callable: BuiltinFn(typing.as_ref().get("Callable").unwrap()),
+                typing_literal: BuiltinFn(typing.as_ref().get("Literal").unwrap()),
             }
         });
         Lazy::force(&RES)
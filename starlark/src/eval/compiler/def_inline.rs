@@ -131,7 +131,7 @@ impl IsSafeToInlineExpr {
                 let _: &Builtin1 = un_op;
                 self.is_safe_to_inline_expr(arg)
             }
-            ExprCompiled::Tuple(xs) | ExprCompiled::List(xs) => {
+            ExprCompiled::Tuple(xs) | ExprCompiled::List(xs) | ExprCompiled::Set(xs) => {
                 xs.iter().all(|x| self.is_safe_to_inline_expr(x))
             }
             ExprCompiled::Dict(xs) => xs
@@ -301,6 +301,16 @@ impl InlineDefCallSite<'_, '_, '_, '_, '_> {
                     node: ExprCompiled::Dict(xs),
                 }
             }
+            ExprCompiled::Set(xs) => {
+                let xs = xs
+                    .iter()
+                    .map(|x| self.inline(x))
+                    .collect::<Result<Vec<_>, CannotInline>>()?;
+                IrSpanned {
+                    span,
+                    node: ExprCompiled::Set(xs),
+                }
+            }
             ExprCompiled::Builtin2(op, l_r) => {
                 let (l, r) = &**l_r;
                 let l = self.inline(l)?;
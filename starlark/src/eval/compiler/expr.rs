@@ -22,6 +22,8 @@ use std::cmp::Ordering;
 
 use dupe::Dupe;
 use starlark_derive::VisitSpanMut;
+use starlark_syntax::eval_exception::EvalException;
+use starlark_syntax::internal_error;
 use starlark_syntax::slice_vec_ext::SliceExt;
 use starlark_syntax::syntax::ast::AstExprP;
 use starlark_syntax::syntax::ast::AstLiteral;
@@ -243,6 +245,8 @@ pub(crate) enum ExprCompiled {
     Tuple(Vec<IrSpanned<ExprCompiled>>),
     List(Vec<IrSpanned<ExprCompiled>>),
     Dict(Vec<(IrSpanned<ExprCompiled>, IrSpanned<ExprCompiled>)>),
+    /// Set literal, never empty.
+    Set(Vec<IrSpanned<ExprCompiled>>),
     /// Comprehension.
     Compr(ComprCompiled),
     If(
@@ -512,6 +516,7 @@ impl IrSpanned<ExprCompiled> {
             ExprCompiled::Dict(kvs) => {
                 ExprCompiled::Dict(kvs.map(|(k, v)| (k.optimize(ctx), v.optimize(ctx))))
             }
+            ExprCompiled::Set(xs) => ExprCompiled::Set(xs.map(|e| e.optimize(ctx))),
             ExprCompiled::Compr(compr) => compr.optimize(ctx),
             ExprCompiled::If(cond_t_f) => {
                 let (cond, t, f) = &**cond_t_f;
@@ -1262,6 +1267,10 @@ impl<'v, 'a, 'e> Compiler<'v, 'a, 'e, '_> {
                     .collect::<Result<_, CompilerInternalError>>()?;
                 ExprCompiled::Dict(xs)
             }
+            ExprP::Set(exprs) => {
+                let xs = self.exprs(exprs)?;
+                ExprCompiled::Set(xs)
+            }
             ExprP::If(cond_then_expr_else_expr) => {
                 let (cond, then_expr, else_expr) = &**cond_then_expr_else_expr;
                 let cond = self.expr(cond)?;
@@ -1293,6 +1302,14 @@ impl<'v, 'a, 'e> Compiler<'v, 'a, 'e, '_> {
                 let index1 = self.expr(index1)?;
                 ExprCompiled::index2(array, index0, index1)
             }
+            ExprP::IndexN(..) => {
+                // The parser only allows it in type expressions.
+                return Err(CompilerInternalError::from_eval_exception(EvalException::new(
+                    internal_error!("Indexing with more than two indices"),
+                    expr.span,
+                    &self.codemap,
+                )));
+            }
             ExprP::Slice(collection, start, stop, stride) => {
                 let collection = self.expr(collection)?;
                 let start = start.as_ref().map(|x| self.expr(x)).transpose()?;
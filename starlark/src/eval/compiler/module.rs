@@ -69,7 +69,14 @@ impl<'v> Compiler<'v, '_, '_, '_> {
                     self.eval,
                 ));
             }
-            Some(loader) => expr_throw(loader.load(name), span, self.eval)?,
+            Some(loader) => {
+                let name = expr_throw(
+                    loader.resolve_load(self.codemap.filename(), name),
+                    span,
+                    self.eval,
+                )?;
+                expr_throw(loader.load(&name), span, self.eval)?
+            }
         };
 
         for load_arg in &load.node.args {
@@ -44,6 +44,7 @@ use starlark_syntax::syntax::ast::LoadArgP;
 use starlark_syntax::syntax::ast::Stmt;
 use starlark_syntax::syntax::ast::StmtP;
 use starlark_syntax::syntax::ast::Visibility;
+use starlark_syntax::syntax::ast::WithP;
 use starlark_syntax::syntax::top_level_stmts::top_level_stmts_mut;
 use starlark_syntax::syntax::uniplate::VisitMut;
 
@@ -884,6 +885,22 @@ impl StmtCollectDefines for Stmt {
                 );
                 StmtP::collect_defines(body, InLoop::Yes, scope_data, frozen_heap, result, dialect);
             }
+            StmtP::With(WithP {
+                expr: _,
+                target,
+                body,
+            }) => {
+                if let Some(target) = target {
+                    AssignTarget::collect_defines_lvalue(
+                        target,
+                        in_loop,
+                        scope_data,
+                        frozen_heap,
+                        result,
+                    );
+                }
+                StmtP::collect_defines(body, in_loop, scope_data, frozen_heap, result, dialect);
+            }
             StmtP::Def(DefP { name, .. }) => AssignIdent::collect_assign_ident(
                 name,
                 in_loop,
@@ -33,6 +33,7 @@ use starlark_syntax::syntax::ast::AssignTargetP;
 use starlark_syntax::syntax::ast::DefP;
 use starlark_syntax::syntax::ast::ForP;
 use starlark_syntax::syntax::ast::StmtP;
+use starlark_syntax::syntax::ast::WithP;
 use thiserror::Error;
 
 use crate::codemap::Span;
@@ -99,6 +100,14 @@ pub(crate) enum StmtCompiled {
             StmtsCompiled,
         )>,
     ),
+    /// `with` statement: context manager, optional target and body.
+    With(
+        Box<(
+            IrSpanned<ExprCompiled>,
+            Option<IrSpanned<AssignCompiledValue>>,
+            StmtsCompiled,
+        )>,
+    ),
     Break,
     Continue,
 }
@@ -168,6 +177,16 @@ impl IrSpanned<StmtCompiled> {
                 let body = body.optimize(ctx);
                 StmtsCompiled::for_stmt(span, var, over, body)
             }
+            StmtCompiled::With(expr_target_body) => {
+                let (expr, target, body) = &**expr_target_body;
+                let expr = expr.optimize(ctx);
+                let target = target.as_ref().map(|target| target.optimize(ctx));
+                let body = body.optimize(ctx);
+                StmtsCompiled::one(IrSpanned {
+                    span,
+                    node: StmtCompiled::With(Box::new((expr, target, body))),
+                })
+            }
             s @ (StmtCompiled::PossibleGc | StmtCompiled::Break | StmtCompiled::Continue) => {
                 StmtsCompiled::one(IrSpanned {
                     span,
@@ -546,7 +565,7 @@ impl Compiler<'_, '_, '_, '_> {
 // We also require that `extra_v` is None, since otherwise the user might have
 // additional values stashed somewhere.
 pub(crate) fn possible_gc(eval: &mut Evaluator) {
-    if !eval.disable_gc && eval.heap().allocated_bytes() >= eval.next_gc_level {
+    if !eval.disable_gc && !eval.gc_paused && eval.heap().allocated_bytes() >= eval.next_gc_level {
         // When we are at a module scope (as checked above) the eval contains
         // references to all values, so walking covers everything and the unsafe
         // is satisfied.
@@ -767,6 +786,18 @@ impl Compiler<'_, '_, '_, '_> {
                 let st = self.stmt(body, false)?;
                 Ok(StmtsCompiled::for_stmt(span, var, over, st))
             }
+            StmtP::With(WithP { expr, target, body }) => {
+                let expr = self.expr(expr)?;
+                let target = match target {
+                    Some(target) => Some(self.assign_target(target)?),
+                    None => None,
+                };
+                let body = self.stmt(body, false)?;
+                Ok(StmtsCompiled::one(IrSpanned {
+                    span,
+                    node: StmtCompiled::With(Box::new((expr, target, body))),
+                }))
+            }
             StmtP::Return(None) => Ok(StmtsCompiled::one(IrSpanned {
                 node: StmtCompiled::Return(IrSpanned {
                     span,
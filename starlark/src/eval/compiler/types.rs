@@ -50,10 +50,12 @@ enum TypesError {
     ModuleVariableNotSet(String),
     #[error("Type payload not set (internal error)")]
     TypePayloadNotSet,
-    #[error("[] can only be applied to list function in type expression")]
+    #[error("[] can only be applied to list, set or typing.Literal in type expression")]
     TypeIndexOnNonList,
     #[error("[,] can only be applied to dict or tuple functions in type expression")]
     TypeIndexOnNonDictOrTuple,
+    #[error("[,,] can only be applied to tuple or typing.Literal in type expression")]
+    TypeIndexNOnNonTupleOrLiteral,
 }
 
 impl<'v> Compiler<'v, '_, '_, '_> {
@@ -165,21 +167,25 @@ impl<'v> Compiler<'v, '_, '_, '_> {
                     .collect::<Result<_, _>>()?;
                 Ok(self.eval.heap().alloc_list(&values))
             }
+            TypeExprUnpackP::Literal(s) => Ok(self.eval.heap().alloc(s)),
             TypeExprUnpackP::Path(path) => self.eval_path(path),
             TypeExprUnpackP::Index(a, i) => {
-                let a = self.eval_ident_in_type_expr(a)?;
-                if !a.ptr_eq(Constants::get().fn_list.0.to_value())
-                    && !a.ptr_eq(Constants::get().fn_set.0.to_value())
+                let a = self.eval_path(a.node)?;
+                let i = if a.ptr_eq(Constants::get().typing_literal.0.to_value()) {
+                    self.eval_expr(*i)?
+                } else if a.ptr_eq(Constants::get().fn_list.0.to_value())
+                    || a.ptr_eq(Constants::get().fn_set.0.to_value())
                 {
+                    self.eval_expr_as_type(*i)?.to_inner()
+                } else {
                     return Err(EvalException::new_anyhow(
                         TypesError::TypeIndexOnNonList.into(),
                         expr.span,
                         &self.codemap,
                     ));
-                }
-                let i = self.eval_expr_as_type(*i)?;
+                };
                 a.get_ref()
-                    .at(i.to_inner(), self.eval.heap())
+                    .at(i, self.eval.heap())
                     .map_err(|e| EvalException::new(e, expr.span, &self.codemap))
             }
             TypeExprUnpackP::Index2(a, i0, i1) => {
@@ -187,6 +193,7 @@ impl<'v> Compiler<'v, '_, '_, '_> {
                 if a.ptr_eq(Constants::get().fn_dict.0.to_value())
                     || a.ptr_eq(Constants::get().fn_tuple.0.to_value())
                     || a.ptr_eq(Constants::get().typing_callable.0.to_value())
+                    || a.ptr_eq(Constants::get().typing_literal.0.to_value())
                 {
                     let i0 = self.eval_expr(*i0)?;
                     let i1 = self.eval_expr(*i1)?;
@@ -201,6 +208,26 @@ impl<'v> Compiler<'v, '_, '_, '_> {
                     ));
                 }
             }
+            TypeExprUnpackP::IndexN(a, is) => {
+                let a = self.eval_path(a.node)?;
+                // Same as indexing by a tuple, as in Python.
+                let is = if a.ptr_eq(Constants::get().fn_tuple.0.to_value()) {
+                    is.into_try_map(|i| {
+                        Ok::<_, EvalException>(self.eval_expr_as_type(i)?.to_inner())
+                    })?
+                } else if a.ptr_eq(Constants::get().typing_literal.0.to_value()) {
+                    is.into_try_map(|i| self.eval_expr(i))?
+                } else {
+                    return Err(EvalException::new_anyhow(
+                        TypesError::TypeIndexNOnNonTupleOrLiteral.into(),
+                        expr.span,
+                        &self.codemap,
+                    ));
+                };
+                a.get_ref()
+                    .at(self.eval.heap().alloc_tuple(&is), self.eval.heap())
+                    .map_err(|e| EvalException::new(e, expr.span, &self.codemap))
+            }
             TypeExprUnpackP::Union(xs) => {
                 let xs = xs.into_try_map(|x| self.eval_expr_as_type(x))?;
                 Ok(TypeCompiled::type_any_of(xs, self.eval.heap()).to_inner())
@@ -22,6 +22,7 @@ use std::marker::PhantomData;
 use dupe::Clone_;
 use dupe::Dupe_;
 use either::Either;
+use smallvec::SmallVec;
 use starlark_map::small_set::SmallSet;
 use starlark_syntax::value_error;
 use thiserror::Error;
@@ -47,6 +48,9 @@ use crate::values::ValueLike;
 pub(crate) enum FunctionError {
     #[error("Found {count} extra positional argument(s) for call to {function}")]
     ExtraPositionalArg { count: usize, function: String },
+    #[error("{function}() takes no positional arguments but {count} {} given",
+        if *count == 1 { "was" } else { "were" })]
+    NoPositionalArgs { count: usize, function: String },
     #[error("Found `{}` extra named parameter(s) for call to {function}", .names.join("` `"))]
     ExtraNamedArg {
         names: Vec<String>,
@@ -179,6 +183,9 @@ pub(crate) trait ArgumentsImpl<'v, 'a>: Debug {
 #[derive(Clone_, Dupe_, Debug)]
 pub(crate) struct ArgumentsFull<'v, 'a, S: ArgSymbol> {
     /// Positional arguments.
+    ///
+    /// When called from bytecode, this borrows the caller frame slots,
+    /// so passing positional arguments does not allocate.
     pub(crate) pos: &'a [Value<'v>],
     /// Named arguments.
     pub(crate) named: &'a [Value<'v>],
@@ -323,6 +330,20 @@ impl<'v, 'a> Arguments<'v, 'a> {
         }
     }
 
+    /// Named arguments (both explicit and in `**kwargs`) except those in `declared`,
+    /// i.e. the keyword arguments which are not consumed by a signature declaring `declared` parameters.
+    ///
+    /// Useful for pass-through wrappers. Like [`names_map`](Arguments::names_map),
+    /// this operation fails if named argument names are not unique.
+    pub fn extra_kwargs(&self, declared: &[&str]) -> crate::Result<SmallMap<String, Value<'v>>> {
+        Ok(self
+            .names_map()?
+            .into_iter()
+            .filter(|(k, _)| !declared.contains(&k.as_str()))
+            .map(|(k, v)| (k.as_str().to_owned(), v))
+            .collect())
+    }
+
     /// The number of arguments, where those inside a args/kwargs are counted as multiple arguments.
     ///
     /// This operation fails if the `kwargs` is not a dictionary, or `args` does not support `len`.
@@ -338,10 +359,22 @@ impl<'v, 'a> Arguments<'v, 'a> {
         Ok(self.0.pos.len() + self.0.named.len() + args + kwargs)
     }
 
+    /// Names of the explicitly named arguments, in call order.
+    ///
+    /// Names passed via `**kwargs` are not included, use [`names_map`](Arguments::names_map) for those.
+    pub fn names(&self) -> impl ExactSizeIterator<Item = &'v str> + 'a {
+        self.0.names.names().iter().map(|(_, s)| s.as_str())
+    }
+
+    /// The number of explicitly passed positional arguments, not counting those inside `*args`.
+    pub fn positional_len(&self) -> usize {
+        self.0.pos.len()
+    }
+
     /// Unwrap all named arguments (both explicit and in `**kwargs`) into a dictionary.
     ///
     /// This operation fails if named argument names are not unique.
-    pub(crate) fn names(&self) -> crate::Result<Dict<'v>> {
+    pub(crate) fn names_dict(&self) -> crate::Result<Dict<'v>> {
         Ok(Dict::new(coerce(self.names_map()?)))
     }
 
@@ -394,6 +427,34 @@ impl<'v, 'a> Arguments<'v, 'a> {
         Ok(())
     }
 
+    /// Like [`no_positional_args`](Arguments::no_positional_args),
+    /// but the error names the function and the number of positional arguments given.
+    pub(crate) fn no_positional_error(&self, function: &str) -> crate::Result<()> {
+        #[cold]
+        #[inline(never)]
+        fn bad(x: &Arguments, function: &str) -> crate::Result<()> {
+            let args = match x.0.args {
+                None => 0,
+                Some(v) => v.length()? as usize,
+            };
+            let count = x.0.pos.len() + args;
+            if count == 0 {
+                Ok(())
+            } else {
+                Err(crate::Error::from(FunctionError::NoPositionalArgs {
+                    count,
+                    function: function.to_owned(),
+                }))
+            }
+        }
+
+        if self.0.pos.is_empty() && self.0.args.is_none() {
+            Ok(())
+        } else {
+            bad(self, function)
+        }
+    }
+
     /// Produce [`Err`] if there are any named (i.e. non-positional) arguments.
     #[inline(always)]
     pub fn no_named_args(&self) -> crate::Result<()> {
@@ -451,13 +512,17 @@ impl<'v, 'a> Arguments<'v, 'a> {
             x: &Arguments<'v, '_>,
             heap: &'v Heap,
         ) -> crate::Result<([Value<'v>; REQUIRED], [Option<Value<'v>>; OPTIONAL])> {
-            // Very sad that we allocate into a vector, but I expect calling into a small positional argument
-            // with a *args is very rare.
+            // Collect into an inline buffer, so calls with few arguments do not allocate.
             let args = match x.0.args {
                 None => StarlarkIterator::empty(heap),
                 Some(args) => args.iterate(heap)?,
             };
-            let xs = x.0.pos.iter().copied().chain(args).collect::<Vec<_>>();
+            let xs =
+                x.0.pos
+                    .iter()
+                    .copied()
+                    .chain(args)
+                    .collect::<SmallVec<[_; 4]>>();
             if xs.len() >= REQUIRED && xs.len() <= REQUIRED + OPTIONAL {
                 let required = xs[0..REQUIRED].try_into().unwrap();
                 let mut optional = [None; OPTIONAL];
@@ -518,8 +583,13 @@ impl<'a> Arguments<'static, 'a> {
 
 #[cfg(test)]
 mod tests {
+    use starlark_derive::starlark_module;
+
     use super::*;
+    use crate as starlark;
+    use crate::assert::Assert;
     use crate::const_frozen_string;
+    use crate::environment::GlobalsBuilder;
     use crate::values::StringValueLike;
 
     #[test]
@@ -639,6 +709,59 @@ mod tests {
         assert_eq!(p.len().unwrap(), 1);
     }
 
+    #[test]
+    fn test_names_and_positional_len() {
+        #[starlark_module]
+        fn describe_args(builder: &mut GlobalsBuilder) {
+            fn describe<'v>(args: &Arguments<'v, '_>) -> anyhow::Result<String> {
+                Ok(format!(
+                    "{} {:?}",
+                    args.positional_len(),
+                    args.names().collect::<Vec<_>>()
+                ))
+            }
+        }
+
+        let mut a = Assert::new();
+        a.globals_add(describe_args);
+        a.eq(r#"'2 ["x"]'"#, "describe(1, 2, x=3)");
+        a.eq(r#"'0 ["b", "a"]'"#, "describe(b=1, a=2, **{'c': 3})");
+        a.eq(r#"'1 []'"#, "describe(1, *[2, 3])");
+    }
+
+    #[test]
+    fn test_extra_kwargs() {
+        #[starlark_module]
+        fn pass_through(builder: &mut GlobalsBuilder) {
+            fn wrapper<'v>(args: &Arguments<'v, '_>) -> starlark::Result<String> {
+                let extra = args.extra_kwargs(&["a", "b"])?;
+                Ok(format!("{:?}", extra.keys().collect::<Vec<_>>()))
+            }
+
+            fn positional_kwargs<'v>(
+                #[starlark(require = pos)] a: i32,
+                #[starlark(kwargs)] kwargs: SmallMap<String, Value<'v>>,
+            ) -> anyhow::Result<String> {
+                let _ = a;
+                Ok(format!("{:?}", kwargs.keys().collect::<Vec<_>>()))
+            }
+        }
+
+        let mut a = Assert::new();
+        a.globals_add(pass_through);
+        a.eq(r#"'["c", "d"]'"#, "wrapper(1, a=2, c=3, b=4, **{'d': 5})");
+        a.eq("'[]'", "wrapper(a=1)");
+        // Same extras as collected by `**kwargs` of a positional-only signature.
+        a.eq(
+            "wrapper(1, c=3, **{'d': 5})",
+            "positional_kwargs(1, c=3, **{'d': 5})",
+        );
+        a.fail(
+            "wrapper(c=1, **{'c': 2})",
+            "Argument `c` occurs more than once",
+        );
+    }
+
     #[test]
     fn test_names_map_repeated_name_in_arg_names() {
         let names = vec![
@@ -36,6 +36,7 @@ pub(crate) struct BeforeStmt<'a, 'e: 'a> {
 #[doc(hidden)]
 pub enum BeforeStmtFunc<'a, 'e: 'a> {
     Fn(&'a dyn for<'v1> Fn(FileSpanRef, &mut Evaluator<'v1, 'a, 'e>)),
+    FnMut(Box<dyn for<'v1> FnMut(FileSpanRef, &mut Evaluator<'v1, 'a, 'e>) + 'a>),
     Dyn(Box<dyn BeforeStmtFuncDyn<'a, 'e>>),
 }
 
@@ -50,6 +51,10 @@ impl<'a, 'e: 'a> BeforeStmtFunc<'a, 'e> {
                 f(span, eval);
                 Ok(())
             }
+            BeforeStmtFunc::FnMut(f) => {
+                f(span, eval);
+                Ok(())
+            }
             BeforeStmtFunc::Dyn(d) => d.call(span, eval),
         }
     }
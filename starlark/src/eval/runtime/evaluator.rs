@@ -42,6 +42,7 @@ use crate::eval::bc::bytecode::Bc;
 use crate::eval::bc::frame::BcFramePtr;
 use crate::eval::bc::opcode::BcOpcode;
 use crate::eval::bc::writer::BcStatementLocations;
+use crate::eval::blocking::BlockingHandle;
 use crate::eval::compiler::def::CopySlotFromParent;
 use crate::eval::compiler::def::Def;
 use crate::eval::compiler::def::DefInfo;
@@ -73,6 +74,7 @@ use crate::stdlib::breakpoint::BreakpointConsole;
 use crate::stdlib::breakpoint::RealBreakpointConsole;
 use crate::stdlib::extra::PrintHandler;
 use crate::stdlib::extra::StderrPrintHandler;
+use crate::stdlib::regex::RegexCache;
 use crate::values::function::NativeFunction;
 use crate::values::layout::value_captured::value_captured_get;
 use crate::values::layout::value_captured::FrozenValueCaptured;
@@ -105,6 +107,10 @@ enum EvaluatorError {
     CallstackSizeAlreadySet,
     #[error("Max callstack size cannot be zero")]
     ZeroCallstackSize,
+    #[error("Evaluation exceeded the budget of {0} steps")]
+    MaxStepsExceeded(u64),
+    #[error("Function `{0}` is nondeterministic and cannot be called when determinism is enforced")]
+    NondeterministicCall(String),
 }
 
 /// Number of bytes to allocate between GC's.
@@ -119,6 +125,8 @@ pub struct Evaluator<'v, 'a, 'e> {
     pub(crate) module_env: &'v Module,
     /// Current function (`def` or `lambda`) frame: locals and bytecode stack.
     pub(crate) current_frame: BcFramePtr<'v>,
+    /// Context managers of the `with` statements being executed, innermost last.
+    pub(crate) with_stack: Vec<Value<'v>>,
     // How we deal with a `load` function.
     pub(crate) loader: Option<&'a dyn FileLoader>,
     // `DefInfo` of currently executed module.
@@ -130,6 +138,8 @@ pub struct Evaluator<'v, 'a, 'e> {
     pub(crate) time_flame_profile: TimeFlameProfile<'v>,
     // Is GC disabled for some reason
     pub(crate) disable_gc: bool,
+    // Is GC suspended by `with_gc_disabled`
+    pub(crate) gc_paused: bool,
     // If true, the interpreter prints to stderr on GC.
     // This is used for debugging.
     pub(crate) verbose_gc: bool,
@@ -150,6 +160,8 @@ pub struct Evaluator<'v, 'a, 'e> {
     alloca: Alloca,
     // Another stack-like allocation
     pub(crate) string_pool: StringPool,
+    /// Compiled patterns used by the `re` module.
+    pub(crate) regex_cache: RegexCache,
     /// Field that can be used for any purpose you want (can store types you define).
     /// Typically accessed via native functions you also define.
     pub extra: Option<&'a dyn AnyLifetime<'e>>,
@@ -158,10 +170,20 @@ pub struct Evaluator<'v, 'a, 'e> {
         Option<Box<dyn Fn() -> anyhow::Result<Box<dyn BreakpointConsole>>>>,
     /// Use in implementation of `print` function.
     pub(crate) print_handler: &'a (dyn PrintHandler + 'a),
+    /// Used to resolve async native functions.
+    pub(crate) blocking_handle: Option<&'a (dyn BlockingHandle + 'a)>,
     /// Deprecation handler.
     pub(crate) soft_error_handler: &'a (dyn SoftErrorHandler + 'a),
     /// Max size of starlark stack
     pub(crate) max_callstack_size: Option<usize>,
+    /// Max number of loop iterations and function calls.
+    max_steps: Option<u64>,
+    /// Number of steps so far, counted only if `max_steps` is set.
+    steps: u64,
+    /// Whether the heap budget was set by this evaluator.
+    heap_budget_set: bool,
+    /// Fail on calls of nondeterministic native functions.
+    enforce_determinism: bool,
     // The Starlark-level call-stack of functions.
     // Must go last because it's quite a big structure
     pub(crate) call_stack: CheapCallStack<'v>,
@@ -203,7 +225,11 @@ impl<'a, 'e: 'a> EvaluationInstrumentation<'a, 'e> {
 // Implementing this forces users to be more careful about lifetimes that the Evaluator captures such that we could
 // add captures of types that implement Drop without needing changes to client code.
 impl Drop for Evaluator<'_, '_, '_> {
-    fn drop(&mut self) {}
+    fn drop(&mut self) {
+        if self.heap_budget_set {
+            self.heap().set_max_allocated_bytes(None);
+        }
+    }
 }
 
 impl<'v, 'a, 'e: 'a> Evaluator<'v, 'a, 'e> {
@@ -216,10 +242,12 @@ impl<'v, 'a, 'e: 'a> Evaluator<'v, 'a, 'e> {
             call_stack: CheapCallStack::default(),
             module_env: module,
             current_frame: BcFramePtr::null(),
+            with_stack: Vec::new(),
             loader: None,
             extra: None,
             next_gc_level: GC_THRESHOLD,
             disable_gc: false,
+            gc_paused: false,
             alloca: Alloca::new(),
             profile_or_instrumentation_mode: ProfileOrInstrumentationMode::None,
             heap_profile: HeapProfile::new(),
@@ -229,12 +257,18 @@ impl<'v, 'a, 'e: 'a> Evaluator<'v, 'a, 'e> {
             eval_instrumentation: EvaluationInstrumentation::new(),
             module_def_info: DefInfo::empty(), // Will be replaced before it is used
             string_pool: StringPool::default(),
+            regex_cache: RegexCache::default(),
             breakpoint_handler: None,
             print_handler: &StderrPrintHandler,
             soft_error_handler: &HardErrorSoftErrorHandler,
+            blocking_handle: None,
             verbose_gc: false,
             static_typechecking: false,
             max_callstack_size: None,
+            max_steps: None,
+            steps: 0,
+            heap_budget_set: false,
+            enforce_determinism: false,
         }
     }
 
@@ -245,6 +279,16 @@ impl<'v, 'a, 'e: 'a> Evaluator<'v, 'a, 'e> {
         self.disable_gc = true;
     }
 
+    /// Run `f` with automatic garbage collection suspended, for example for a phase
+    /// which allocates a lot of short-lived values. Collection resumes when `f` returns,
+    /// unless it was disabled with [`disable_gc`](Evaluator::disable_gc).
+    pub fn with_gc_disabled<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        let gc_paused = mem::replace(&mut self.gc_paused, true);
+        let res = f(self);
+        self.gc_paused = gc_paused;
+        res
+    }
+
     /// Enable GC logging.
     pub fn verbose_gc(&mut self) {
         self.verbose_gc = true;
@@ -306,7 +350,7 @@ impl<'v, 'a, 'e: 'a> Evaluator<'v, 'a, 'e> {
                 self.stmt_profile.enable();
                 self.before_stmt_fn(&|span, eval| eval.stmt_profile.before_stmt(span));
             }
-            ProfileMode::TimeFlame => {
+            ProfileMode::TimeFlame | ProfileMode::ChromeTrace => {
                 self.time_flame_profile.enable();
                 self.eval_instrumentation
                     .change(|v| v.enable_heap_or_flame_profile());
@@ -365,6 +409,7 @@ impl<'v, 'a, 'e: 'a> Evaluator<'v, 'a, 'e> {
             ProfileMode::Bytecode => self.gen_bc_profile(),
             ProfileMode::BytecodePairs => self.gen_bc_pairs_profile(),
             ProfileMode::TimeFlame => self.time_flame_profile.gen(),
+            ProfileMode::ChromeTrace => self.time_flame_profile.gen_chrome_trace(),
             ProfileMode::Typecheck => self.typecheck_profile.gen(),
             ProfileMode::None => Ok(ProfileData {
                 profile: ProfileDataImpl::None,
@@ -430,6 +475,20 @@ impl<'v, 'a, 'e: 'a> Evaluator<'v, 'a, 'e> {
             .change(|v| v.before_stmt.before_stmt.push(f))
     }
 
+    /// Add a hook invoked before each statement is executed, e.g. to implement a step debugger.
+    /// The hook receives the span of the statement and the evaluator,
+    /// which can be used to inspect the call stack or the local variables.
+    /// Multiple hooks are called in the order they were added.
+    ///
+    /// Statements are only instrumented if a hook is added before the code is evaluated,
+    /// so evaluation without hooks is not slowed down.
+    pub fn add_before_stmt_hook(
+        &mut self,
+        f: Box<dyn for<'v1> FnMut(FileSpanRef, &mut Evaluator<'v1, 'a, 'e>) + 'a>,
+    ) {
+        self.before_stmt(BeforeStmtFunc::FnMut(f))
+    }
+
     /// This function is used by DAP, and it is not public API.
     // TODO(nga): pull DAP into the crate, and hide this function.
     #[doc(hidden)]
@@ -442,6 +501,11 @@ impl<'v, 'a, 'e: 'a> Evaluator<'v, 'a, 'e> {
         self.print_handler = handler;
     }
 
+    /// Set the executor used to resolve futures returned by async native functions.
+    pub fn set_blocking_handle(&mut self, handle: &'a (dyn BlockingHandle + 'a)) {
+        self.blocking_handle = Some(handle);
+    }
+
     /// Set deprecation handler. If not set, deprecations are treated as hard errors.
     pub fn set_soft_error_handler(&mut self, handler: &'a (dyn SoftErrorHandler + 'a)) {
         self.soft_error_handler = handler;
@@ -466,11 +530,53 @@ impl<'v, 'a, 'e: 'a> Evaluator<'v, 'a, 'e> {
 
         self.call_stack.push(function, span)?;
         // Must always call .pop regardless
-        let res = within(self).map_err(|e| add_diagnostics(e, self));
+        let res = self
+            .step()
+            .and_then(|()| within(self))
+            .and_then(|r| {
+                // Report large allocations made by the call.
+                self.heap().check_budget()?;
+                Ok(r)
+            })
+            .map_err(|e| add_diagnostics(e, self));
         self.call_stack.pop();
         res
     }
 
+    /// Run `within` without recording calls in the time flame profile.
+    /// Used for speculative evaluation at compile time, which is not part of the program run.
+    pub(crate) fn without_time_flame_profile<R>(
+        &mut self,
+        within: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let profile = mem::replace(&mut self.time_flame_profile, TimeFlameProfile::new());
+        let res = within(self);
+        self.time_flame_profile = profile;
+        res
+    }
+
+    /// Record a native function call in the time flame profile (if enabled),
+    /// so native frames are labeled by their registered name.
+    /// Fails if the function is nondeterministic and determinism is enforced.
+    #[inline(always)]
+    pub(crate) fn with_native_frame<R>(
+        &mut self,
+        function: Value<'v>,
+        within: impl FnOnce(&mut Self) -> crate::Result<R>,
+    ) -> crate::Result<R> {
+        if self.enforce_determinism {
+            if let Some(name) = NativeFunction::nondeterministic_name(function) {
+                return Err(crate::Error::new_other(
+                    EvaluatorError::NondeterministicCall(name.to_owned()),
+                ));
+            }
+        }
+        self.time_flame_profile.record_call_enter(function);
+        let res = within(self);
+        self.time_flame_profile.record_call_exit();
+        res
+    }
+
     /// The active heap where [`Value`]s are allocated.
     pub fn heap(&self) -> &'v Heap {
         self.module_env.heap()
@@ -711,6 +817,7 @@ impl<'v, 'a, 'e: 'a> Evaluator<'v, 'a, 'e> {
     fn trace(&mut self, tracer: &Tracer<'v>) {
         self.module_env.trace(tracer);
         self.current_frame.trace(tracer);
+        self.with_stack.trace(tracer);
         self.call_stack.trace(tracer);
         self.time_flame_profile.trace(tracer);
     }
@@ -719,6 +826,9 @@ impl<'v, 'a, 'e: 'a> Evaluator<'v, 'a, 'e> {
     /// After this operation all [`Value`]s not reachable from the evaluator will be invalid,
     /// and using them will lead to a segfault.
     /// Do not call during Starlark evaluation.
+    ///
+    /// Can be called between evaluations (e.g. between phases of a long-running evaluator)
+    /// to reclaim values no longer reachable from the module, the call stack or profiling data.
     pub unsafe fn garbage_collect(&mut self) {
         if self.verbose_gc {
             eprintln!(
@@ -850,6 +960,49 @@ impl<'v, 'a, 'e: 'a> Evaluator<'v, 'a, 'e> {
         self.max_callstack_size = Some(stack_size);
         Ok(())
     }
+
+    /// Abort evaluation with an error once more than `max_steps` steps were executed.
+    ///
+    /// A step is a loop iteration (including iterations of comprehensions) or a function call,
+    /// so any unbounded computation eventually exceeds the budget.
+    pub fn set_max_steps(&mut self, max_steps: u64) {
+        self.max_steps = Some(max_steps);
+        self.steps = 0;
+    }
+
+    /// Abort evaluation with an error once the heap grows beyond `max_heap_bytes`.
+    ///
+    /// Allocations exceeding the budget are recorded by the heap,
+    /// and evaluation is aborted on the next loop iteration or function call.
+    /// Large allocations of known size (e.g. `[0] * n` or `"x" * n`) fail before allocating.
+    /// The budget applies to the heap of the module until the evaluator is dropped.
+    pub fn set_max_heap_bytes(&mut self, max_heap_bytes: usize) {
+        self.heap_budget_set = true;
+        self.heap().set_max_allocated_bytes(Some(max_heap_bytes));
+    }
+
+    /// Forbid calls of native functions declared with `#[starlark(nondeterministic)]`,
+    /// e.g. functions reading the time or the environment, so evaluation is reproducible.
+    ///
+    /// Everything else in the language is deterministic:
+    /// in particular, dicts and sets iterate in insertion order.
+    pub fn enforce_determinism(&mut self, enforce: bool) {
+        self.enforce_determinism = enforce;
+    }
+
+    /// Count a step and check the budgets.
+    #[inline(always)]
+    pub(crate) fn step(&mut self) -> crate::Result<()> {
+        if let Some(max_steps) = self.max_steps {
+            self.steps += 1;
+            if self.steps > max_steps {
+                return Err(crate::Error::new_other(EvaluatorError::MaxStepsExceeded(
+                    max_steps,
+                )));
+            }
+        }
+        self.heap().check_budget()
+    }
 }
 
 pub(crate) trait EvaluationCallbacks {
@@ -18,16 +18,34 @@
 //! Define variants of the evaluation function with different support
 //! for the `load(...)` statement.
 
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::collections::HashSet;
 
 use dupe::Dupe;
 
 use crate::environment::FrozenModule;
+use crate::environment::Globals;
+use crate::environment::Module;
+use crate::eval::Evaluator;
+use crate::syntax::AstModule;
+use crate::syntax::Dialect;
 
 /// A trait for turning a `path` given by a `load()` statement into a [`FrozenModule`].
 pub trait FileLoader {
     /// Open the file given by the load statement `path`.
     fn load(&self, path: &str) -> anyhow::Result<FrozenModule>;
+
+    /// Canonicalize the `requested` path of a `load()` statement in the module `current`
+    /// (as given to [`AstModule::parse`](crate::syntax::AstModule::parse)).
+    /// The result is passed to [`load`](FileLoader::load).
+    ///
+    /// Can be used to implement relative paths or aliases.
+    /// The default implementation returns `requested` unchanged.
+    fn resolve_load(&self, current: &str, requested: &str) -> anyhow::Result<String> {
+        let _ = current;
+        Ok(requested.to_owned())
+    }
 }
 
 /// [`FileLoader`] that looks up modules by name from a [`HashMap`].
@@ -52,6 +70,75 @@ impl<'a> FileLoader for ReturnFileLoader<'a> {
     }
 }
 
+#[derive(Debug, thiserror::Error)]
+enum InMemoryFileLoaderError {
+    #[error("InMemoryFileLoader does not know the module `{0}`")]
+    UnknownModule(String),
+    #[error("Cycle detected when loading module `{0}`")]
+    Cycle(String),
+}
+
+/// [`FileLoader`] that serves modules from source text held in memory.
+///
+/// Modules are parsed and evaluated on first `load()`, and the resulting
+/// [`FrozenModule`] is cached, so each module is evaluated at most once.
+/// Modules may themselves load other modules from the same loader.
+pub struct InMemoryFileLoader {
+    sources: HashMap<String, String>,
+    globals: Globals,
+    dialect: Dialect,
+    cache: RefCell<HashMap<String, FrozenModule>>,
+    loading: RefCell<HashSet<String>>,
+}
+
+impl InMemoryFileLoader {
+    /// Create a loader from a map of module path (first argument to `load` statement)
+    /// to module source. Modules are evaluated with the given globals and dialect.
+    pub fn new(sources: HashMap<String, String>, globals: Globals, dialect: Dialect) -> Self {
+        InMemoryFileLoader {
+            sources,
+            globals,
+            dialect,
+            cache: RefCell::new(HashMap::new()),
+            loading: RefCell::new(HashSet::new()),
+        }
+    }
+
+    fn eval(&self, path: &str, source: &str) -> anyhow::Result<FrozenModule> {
+        let ast = AstModule::parse(path, source.to_owned(), &self.dialect)
+            .map_err(crate::Error::into_anyhow)?;
+        let module = Module::new();
+        {
+            let mut eval = Evaluator::new(&module);
+            eval.set_loader(self);
+            eval.eval_module(ast, &self.globals)
+                .map_err(crate::Error::into_anyhow)?;
+        }
+        module.freeze()
+    }
+}
+
+impl FileLoader for InMemoryFileLoader {
+    fn load(&self, path: &str) -> anyhow::Result<FrozenModule> {
+        if let Some(module) = self.cache.borrow().get(path) {
+            return Ok(module.dupe());
+        }
+        let Some(source) = self.sources.get(path) else {
+            return Err(InMemoryFileLoaderError::UnknownModule(path.to_owned()).into());
+        };
+        if !self.loading.borrow_mut().insert(path.to_owned()) {
+            return Err(InMemoryFileLoaderError::Cycle(path.to_owned()).into());
+        }
+        let res = self.eval(path, source);
+        self.loading.borrow_mut().remove(path);
+        let module = res?;
+        self.cache
+            .borrow_mut()
+            .insert(path.to_owned(), module.dupe());
+        Ok(module)
+    }
+}
+
 /// Same as [`ReturnFileLoader`], but does not require fighting the borrow checker.
 #[cfg(test)]
 pub(crate) struct ReturnOwnedFileLoader {
@@ -70,3 +157,118 @@ impl FileLoader for ReturnOwnedFileLoader {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use dupe::Dupe;
+
+    use crate::environment::FrozenModule;
+    use crate::environment::Globals;
+    use crate::environment::Module;
+    use crate::eval::runtime::file_loader::FileLoader;
+    use crate::eval::runtime::file_loader::InMemoryFileLoader;
+    use crate::eval::Evaluator;
+    use crate::syntax::AstModule;
+    use crate::syntax::Dialect;
+
+    fn loader(sources: &[(&str, &str)]) -> InMemoryFileLoader {
+        InMemoryFileLoader::new(
+            sources
+                .iter()
+                .map(|(k, v)| ((*k).to_owned(), (*v).to_owned()))
+                .collect::<HashMap<_, _>>(),
+            Globals::standard(),
+            Dialect::Standard,
+        )
+    }
+
+    #[test]
+    fn test_in_memory_file_loader() {
+        let loader = loader(&[
+            ("b", "def f(x):\n    return x * 2\ny = 10"),
+            ("a", "load('b', 'f', 'y')\nz = f(y)"),
+        ]);
+        let a = loader.load("a").unwrap();
+        assert_eq!(20, a.get("z").unwrap().value().unpack_i32().unwrap());
+
+        // `b` was cached when `a` loaded it.
+        let b1 = loader.load("b").unwrap();
+        let b2 = loader.load("b").unwrap();
+        assert!(b1
+            .get("f")
+            .unwrap()
+            .value()
+            .ptr_eq(b2.get("f").unwrap().value()));
+
+        let module = Module::new();
+        let mut eval = Evaluator::new(&module);
+        eval.set_loader(&loader);
+        let ast = AstModule::parse(
+            "main.star",
+            "load('a', 'z')\nz".to_owned(),
+            &Dialect::Standard,
+        )
+        .unwrap();
+        let res = eval.eval_module(ast, &Globals::standard()).unwrap();
+        assert_eq!(Some(20), res.unpack_i32());
+    }
+
+    #[test]
+    fn test_in_memory_file_loader_errors() {
+        let loader = loader(&[("a", "load('a', 'x')")]);
+        assert_eq!(
+            "InMemoryFileLoader does not know the module `c`",
+            loader.load("c").unwrap_err().to_string()
+        );
+        assert!(format!("{:#}", loader.load("a").unwrap_err())
+            .contains("Cycle detected when loading module `a`"));
+    }
+
+    struct PackageLoader {
+        modules: HashMap<String, FrozenModule>,
+    }
+
+    impl FileLoader for PackageLoader {
+        fn load(&self, path: &str) -> anyhow::Result<FrozenModule> {
+            self.modules
+                .get(path)
+                .map(|m| m.dupe())
+                .ok_or_else(|| anyhow::anyhow!("Unknown module `{}`", path))
+        }
+
+        fn resolve_load(&self, current: &str, requested: &str) -> anyhow::Result<String> {
+            match requested.strip_prefix(':') {
+                Some(file) => {
+                    let (package, _) = current
+                        .split_once(':')
+                        .ok_or_else(|| anyhow::anyhow!("Not a label: `{}`", current))?;
+                    Ok(format!("{package}:{file}"))
+                }
+                None => Ok(requested.to_owned()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_resolve_load() {
+        let sibling = Module::new();
+        sibling.set("x", sibling.heap().alloc(17));
+        let loader = PackageLoader {
+            modules: HashMap::from([("//pkg:sibling.bzl".to_owned(), sibling.freeze().unwrap())]),
+        };
+
+        let module = Module::new();
+        let mut eval = Evaluator::new(&module);
+        eval.set_loader(&loader);
+        let ast = AstModule::parse(
+            "//pkg:main.bzl",
+            "load(':sibling.bzl', 'x')\nx".to_owned(),
+            &Dialect::Standard,
+        )
+        .unwrap();
+        let res = eval.eval_module(ast, &Globals::standard()).unwrap();
+        assert_eq!(Some(17), res.unpack_i32());
+    }
+}
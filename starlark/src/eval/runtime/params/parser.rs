@@ -86,6 +86,10 @@ impl<'v, 'a> ParametersParser<'v, 'a> {
 mod tests {
     use std::collections::HashMap;
 
+    use allocative::Allocative;
+    use starlark_derive::starlark_value;
+
+    use crate as starlark;
     use crate::assert::Assert;
     use crate::docs::DocParam;
     use crate::docs::DocParams;
@@ -94,9 +98,16 @@ mod tests {
     use crate::eval::compiler::def::FrozenDef;
     use crate::eval::runtime::params::display::PARAM_FMT_OPTIONAL;
     use crate::eval::runtime::params::spec::ParametersSpec;
+    use crate::eval::Arguments;
+    use crate::eval::Evaluator;
+    use crate::eval::ParametersSpecBuilder;
     use crate::eval::ParametersSpecParam;
     use crate::typing::Ty;
     use crate::values::FrozenValue;
+    use crate::values::NoSerialize;
+    use crate::values::ProvidesStaticType;
+    use crate::values::StarlarkValue;
+    use crate::values::Value;
 
     #[test]
     fn test_documentation() -> anyhow::Result<()> {
@@ -176,6 +187,37 @@ mod tests {
         test("a, **kwargs");
     }
 
+    #[test]
+    fn test_to_signature_string() {
+        let mut spec = ParametersSpecBuilder::<FrozenValue>::new("f");
+        spec.required("a");
+        spec.required("b");
+        spec.no_more_positional_only_args();
+        spec.required("c");
+        spec.args();
+        spec.required("d");
+        spec.kwargs();
+        assert_eq!(
+            "f(a, b, /, c, *args, d, **kwargs)",
+            spec.finish().to_signature_string()
+        );
+
+        let mut spec = ParametersSpecBuilder::<FrozenValue>::new("g");
+        spec.no_more_positional_only_args();
+        spec.required("x");
+        spec.optional("y");
+        spec.no_more_positional_args();
+        spec.optional("z");
+        assert_eq!("g(x, y = ..., *, z = ...)", spec.finish().to_signature_string());
+
+        assert_eq!(
+            "h()",
+            ParametersSpecBuilder::<FrozenValue>::new("h")
+                .finish()
+                .to_signature_string()
+        );
+    }
+
     #[test]
     fn test_can_fill_with_args() {
         fn test(sig: &str, pos: usize, names: &[&str], expected: bool) {
@@ -219,4 +261,61 @@ mod tests {
 
         // No test for positional-only args because we can't create them in starlark.
     }
+
+    #[test]
+    fn test_runtime_built_spec() {
+        #[derive(
+            Debug,
+            derive_more::Display,
+            ProvidesStaticType,
+            NoSerialize,
+            Allocative
+        )]
+        #[display("dyn_fun")]
+        struct DynFun {
+            spec: ParametersSpec<FrozenValue>,
+        }
+
+        #[starlark_value(type = "dyn_fun")]
+        impl<'v> StarlarkValue<'v> for DynFun {
+            fn invoke(
+                &self,
+                _me: Value<'v>,
+                args: &Arguments<'v, '_>,
+                eval: &mut Evaluator<'v, '_, '_>,
+            ) -> crate::Result<Value<'v>> {
+                self.spec.parser(args, eval, |p, eval| {
+                    let a: i32 = p.next()?;
+                    let b: Option<i32> = p.next_opt()?;
+                    let args: Value = p.next()?;
+                    let kwargs: Value = p.next()?;
+                    Ok(eval
+                        .heap()
+                        .alloc(format!("a={a} b={b:?} args={args} kwargs={kwargs}")))
+                })
+            }
+        }
+
+        let mut spec = ParametersSpecBuilder::new("f");
+        spec.no_more_positional_only_args();
+        spec.required("a");
+        spec.optional("b");
+        spec.args();
+        spec.kwargs();
+        let spec = spec.finish();
+        assert_eq!("a, b = ..., *args, **kwargs", spec.parameters_str());
+
+        let mut a = Assert::new();
+        a.globals_add(|builder| {
+            builder.set("f", builder.frozen_heap().alloc_simple(DynFun { spec }))
+        });
+        a.eq("'a=1 b=None args=() kwargs={}'", "f(1)");
+        a.eq(
+            "'a=1 b=Some(2) args=(3,) kwargs={\"c\": 4}'",
+            "f(1, 2, 3, c=4)",
+        );
+        a.eq("'a=1 b=Some(2) args=() kwargs={}'", "f(b=2, a=1)");
+        a.fail("f()", "Missing parameter `a` for call to `f`");
+        a.fail("f(1, a=2)", "Argument `a` occurs more than once");
+    }
 }
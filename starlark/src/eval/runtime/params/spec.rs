@@ -107,8 +107,12 @@ enum CurrentParameterStyle {
     NoMore,
 }
 
-/// Builder for [`ParametersSpec`]
-pub(crate) struct ParametersSpecBuilder<V> {
+/// Builder for [`ParametersSpec`], for functions whose signature is only known at runtime.
+///
+/// Parameters are added in the order they occur in the signature,
+/// starting with positional-only parameters.
+/// Adding parameters in an invalid order (e.g. after `**kwargs`) panics.
+pub struct ParametersSpecBuilder<V> {
     function_name: String,
     params: Vec<(String, ParameterKind<V>)>,
     names: SymbolMap<u32>,
@@ -145,6 +149,12 @@ pub struct ParametersSpec<V> {
 }
 
 impl<V: Copy> ParametersSpecBuilder<V> {
+    /// Start building a [`ParametersSpec`] for a function named `function_name`
+    /// (the name is used in error messages).
+    pub fn new(function_name: &str) -> Self {
+        ParametersSpec::with_capacity(function_name.to_owned(), 0)
+    }
+
     fn add(&mut self, name: &str, val: ParameterKind<V>) {
         assert!(
             !matches!(val, ParameterKind::Args | ParameterKind::KWargs),
@@ -186,21 +196,21 @@ impl<V: Copy> ParametersSpecBuilder<V> {
     /// Add a required parameter. Will be an error if the caller doesn't supply
     /// it. If you want to supply a position-only argument, prepend a `$` to
     /// the name.
-    pub(crate) fn required(&mut self, name: &str) {
+    pub fn required(&mut self, name: &str) {
         self.add(name, ParameterKind::Required);
     }
 
     /// Add an optional parameter. Will be None if the caller doesn't supply it.
     /// If you want to supply a position-only argument, prepend a `$` to the
     /// name.
-    pub(crate) fn optional(&mut self, name: &str) {
+    pub fn optional(&mut self, name: &str) {
         self.add(name, ParameterKind::Optional);
     }
 
     /// Add an optional parameter. Will be the default value if the caller
     /// doesn't supply it. If you want to supply a position-only argument,
     /// prepend a `$` to the name.
-    pub(crate) fn defaulted(&mut self, name: &str, val: V) {
+    pub fn defaulted(&mut self, name: &str, val: V) {
         self.add(name, ParameterKind::Defaulted(val));
     }
 
@@ -219,7 +229,7 @@ impl<V: Copy> ParametersSpecBuilder<V> {
     /// [`optional`](ParametersSpecBuilder::optional) or
     /// [`defaulted`](ParametersSpecBuilder::defaulted)
     /// parameters can _only_ be supplied by name.
-    pub(crate) fn args(&mut self) {
+    pub fn args(&mut self) {
         assert!(
             self.args.is_none(),
             "adding *args to `{}`",
@@ -241,7 +251,7 @@ impl<V: Copy> ParametersSpecBuilder<V> {
     }
 
     /// Following parameters can be filled positionally or by name.
-    pub(crate) fn no_more_positional_only_args(&mut self) {
+    pub fn no_more_positional_only_args(&mut self) {
         assert_eq!(
             self.current_style,
             CurrentParameterStyle::PosOnly,
@@ -257,7 +267,7 @@ impl<V: Copy> ParametersSpecBuilder<V> {
     /// [`optional`](ParametersSpecBuilder::optional) or
     /// [`defaulted`](ParametersSpecBuilder::defaulted)
     /// parameters can _only_ be supplied by name.
-    pub(crate) fn no_more_positional_args(&mut self) {
+    pub fn no_more_positional_args(&mut self) {
         assert!(self.args.is_none(), "adding * to `{}`", self.function_name);
         assert!(
             self.current_style < CurrentParameterStyle::NamedOnly,
@@ -279,7 +289,7 @@ impl<V: Copy> ParametersSpecBuilder<V> {
     /// [`optional`](ParametersSpecBuilder::optional) or
     /// [`defaulted`](ParametersSpecBuilder::defaulted)
     /// parameters can _only_ be supplied by position.
-    pub(crate) fn kwargs(&mut self) {
+    pub fn kwargs(&mut self) {
         assert!(
             self.kwargs.is_none(),
             "adding **kwargs to `{}`",
@@ -292,7 +302,7 @@ impl<V: Copy> ParametersSpecBuilder<V> {
     }
 
     /// Construct the parameters specification.
-    pub(crate) fn finish(self) -> ParametersSpec<V> {
+    pub fn finish(self) -> ParametersSpec<V> {
         let ParametersSpecBuilder {
             function_name,
             positional_only,
@@ -424,6 +434,12 @@ impl<V> ParametersSpec<V> {
         // in some contexts, so don't delete it.
     }
 
+    /// Python-like signature of the function, e.g. `f(a, b, /, c, *args, d, **kwargs)`,
+    /// with `/` and `*` separators for positional-only and named-only parameters.
+    pub fn to_signature_string(&self) -> String {
+        format!("{}({})", self.function_name, self.parameters_str())
+    }
+
     /// Function parameter as they would appear in `def`
     /// (excluding types, default values and formatting).
     pub fn parameters_str(&self) -> String {
@@ -16,6 +16,7 @@
  */
 
 pub(crate) mod bc;
+pub(crate) mod chrome_trace;
 pub(crate) mod csv;
 pub(crate) mod data;
 pub(crate) mod flamegraph;
@@ -0,0 +1,105 @@
+/*
+ * Copyright 2019 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Writer for the [Trace Event Format](https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU)
+//! understood by `chrome://tracing` and Perfetto.
+
+use dupe::Dupe;
+use serde::Serialize;
+
+use crate::eval::runtime::profile::data::ProfileDataImpl;
+use crate::eval::runtime::profile::profiler_type::ProfilerType;
+use crate::eval::ProfileMode;
+
+pub(crate) struct ChromeTraceProfilerType;
+
+impl ProfilerType for ChromeTraceProfilerType {
+    type Data = ChromeTraceData;
+    const PROFILE_MODE: ProfileMode = ProfileMode::ChromeTrace;
+
+    fn data_from_generic(profile_data: &ProfileDataImpl) -> Option<&Self::Data> {
+        match profile_data {
+            ProfileDataImpl::ChromeTrace(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    fn data_to_generic(data: Self::Data) -> ProfileDataImpl {
+        ProfileDataImpl::ChromeTrace(data)
+    }
+
+    fn merge_profiles_impl(profiles: &[&Self::Data]) -> starlark_syntax::Result<Self::Data> {
+        Ok(ChromeTraceData::merge(profiles.iter().copied()))
+    }
+}
+
+/// Phase of a trace event.
+#[derive(Debug, Clone, Copy, Dupe, PartialEq, Eq, Serialize)]
+pub(crate) enum ChromeTracePhase {
+    /// Function entry.
+    #[serde(rename = "B")]
+    Begin,
+    /// Function exit.
+    #[serde(rename = "E")]
+    End,
+}
+
+/// Single trace event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct ChromeTraceEvent {
+    pub(crate) name: String,
+    #[serde(rename = "ph")]
+    pub(crate) phase: ChromeTracePhase,
+    /// Microseconds since the start of the profile.
+    pub(crate) ts: u64,
+    pub(crate) pid: u32,
+    /// Profiles merged together are shown as separate threads.
+    pub(crate) tid: u32,
+}
+
+/// Timeline of function entries and exits.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ChromeTraceData {
+    pub(crate) events: Vec<ChromeTraceEvent>,
+}
+
+impl ChromeTraceData {
+    fn merge<'a>(profiles: impl IntoIterator<Item = &'a ChromeTraceData>) -> ChromeTraceData {
+        let mut events = Vec::new();
+        for (tid, profile) in profiles.into_iter().enumerate() {
+            events.extend(profile.events.iter().map(|e| ChromeTraceEvent {
+                tid: tid as u32,
+                ..e.clone()
+            }));
+        }
+        ChromeTraceData { events }
+    }
+
+    /// Write the `{"traceEvents": [...]}` JSON object, one event per line.
+    pub(crate) fn write(&self) -> String {
+        let mut res = "{\"traceEvents\":[".to_owned();
+        for (i, event) in self.events.iter().enumerate() {
+            if i != 0 {
+                res.push(',');
+            }
+            res.push('\n');
+            res.push_str(&serde_json::to_string(event).unwrap());
+        }
+        res.push_str("\n]}\n");
+        res
+    }
+}
@@ -16,12 +16,15 @@
  */
 
 use std::fs;
+use std::io;
 use std::path::Path;
 
 use crate::eval::runtime::profile::bc::BcPairsProfileData;
 use crate::eval::runtime::profile::bc::BcPairsProfilerType;
 use crate::eval::runtime::profile::bc::BcProfileData;
 use crate::eval::runtime::profile::bc::BcProfilerType;
+use crate::eval::runtime::profile::chrome_trace::ChromeTraceData;
+use crate::eval::runtime::profile::chrome_trace::ChromeTraceProfilerType;
 use crate::eval::runtime::profile::flamegraph::FlameGraphData;
 use crate::eval::runtime::profile::heap::HeapFlameAllocatedProfilerType;
 use crate::eval::runtime::profile::heap::HeapFlameRetainedProfilerType;
@@ -55,6 +58,7 @@ pub(crate) enum ProfileDataImpl {
     HeapSummaryAllocated(Box<AggregateHeapProfileInfo>),
     /// Flame graph data is in milliseconds.
     TimeFlameProfile(FlameGraphData),
+    ChromeTrace(ChromeTraceData),
     Statement(StmtProfileData),
     Coverage(StmtProfileData),
     Typecheck(TypecheckProfileData),
@@ -71,6 +75,7 @@ impl ProfileDataImpl {
             ProfileDataImpl::HeapSummaryRetained(_) => ProfileMode::HeapSummaryRetained,
             ProfileDataImpl::HeapSummaryAllocated(_) => ProfileMode::HeapSummaryAllocated,
             ProfileDataImpl::TimeFlameProfile(_) => ProfileMode::TimeFlame,
+            ProfileDataImpl::ChromeTrace(_) => ProfileMode::ChromeTrace,
             ProfileDataImpl::Statement(_) => ProfileMode::Statement,
             ProfileDataImpl::Coverage(_) => ProfileMode::Coverage,
             ProfileDataImpl::Typecheck(_) => ProfileMode::Typecheck,
@@ -106,6 +111,7 @@ impl ProfileData {
             ProfileDataImpl::HeapSummaryRetained(profile)
             | ProfileDataImpl::HeapSummaryAllocated(profile) => Ok(profile.gen_summary_csv()),
             ProfileDataImpl::TimeFlameProfile(data) => Ok(data.write()),
+            ProfileDataImpl::ChromeTrace(data) => Ok(data.write()),
             ProfileDataImpl::Statement(data) => Ok(data.write_to_string()),
             ProfileDataImpl::Coverage(data) => Ok(data.write_coverage()),
             ProfileDataImpl::Typecheck(data) => Ok(data.gen_csv()),
@@ -126,6 +132,19 @@ impl ProfileData {
         Ok(())
     }
 
+    /// Write profile data to a writer, e.g. folded stacks for
+    /// [`TimeFlame`](ProfileMode::TimeFlame) which can be consumed by `inferno` or `flamegraph.pl`.
+    pub fn write_to(&self, w: &mut dyn io::Write) -> crate::Result<()> {
+        w.write_all(self.gen()?.as_bytes()).map_err(|e| {
+            anyhow::anyhow!(
+                "Could not write profile `{}` data: {}",
+                self.profile.profile_mode(),
+                e,
+            )
+        })?;
+        Ok(())
+    }
+
     /// Merge profiles (aggregate).
     pub fn merge<'a>(
         profiles: impl IntoIterator<Item = &'a ProfileData>,
@@ -166,6 +185,7 @@ impl ProfileData {
                 HeapFlameRetainedProfilerType::merge_profiles(&profiles)?.profile
             }
             ProfileMode::TimeFlame => TimeFlameProfilerType::merge_profiles(&profiles)?.profile,
+            ProfileMode::ChromeTrace => ChromeTraceProfilerType::merge_profiles(&profiles)?.profile,
             ProfileMode::Typecheck => TypecheckProfilerType::merge_profiles(&profiles)?.profile,
             ProfileMode::Statement => StmtProfilerType::merge_profiles(&profiles)?.profile,
             ProfileMode::Coverage => CoverageProfileType::merge_profiles(&profiles)?.profile,
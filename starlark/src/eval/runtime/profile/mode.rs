@@ -47,6 +47,10 @@ pub enum ProfileMode {
     /// Provide output compatible with
     /// [flamegraph.pl](https://github.com/brendangregg/FlameGraph/blob/master/flamegraph.pl).
     TimeFlame,
+    /// Timeline of Starlark and native function calls in the
+    /// [Trace Event Format](https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU),
+    /// which can be viewed in `chrome://tracing` or [Perfetto](https://ui.perfetto.dev).
+    ChromeTrace,
     /// Profile runtime typechecking.
     Typecheck,
     /// Don't record any profile information.
@@ -60,7 +64,7 @@ impl Display for ProfileMode {
 }
 
 impl ProfileMode {
-    pub(crate) const ALL: [ProfileMode; 11] = [
+    pub(crate) const ALL: [ProfileMode; 12] = [
         ProfileMode::HeapSummaryAllocated,
         ProfileMode::HeapSummaryRetained,
         ProfileMode::HeapFlameAllocated,
@@ -70,6 +74,7 @@ impl ProfileMode {
         ProfileMode::Bytecode,
         ProfileMode::BytecodePairs,
         ProfileMode::TimeFlame,
+        ProfileMode::ChromeTrace,
         ProfileMode::Typecheck,
         ProfileMode::None,
     ];
@@ -85,6 +90,7 @@ impl ProfileMode {
             ProfileMode::Bytecode => "bytecode",
             ProfileMode::BytecodePairs => "bytecode-pairs",
             ProfileMode::TimeFlame => "time-flame",
+            ProfileMode::ChromeTrace => "chrome-trace",
             ProfileMode::Typecheck => "typecheck",
             ProfileMode::None => "none",
         }
@@ -392,6 +392,44 @@ xx(*[2])
         );
     }
 
+    #[test]
+    fn test_coverage_if_branch() {
+        let module = Module::new();
+        let mut eval = Evaluator::new(&module);
+
+        let module = AstModule::parse(
+            "cov.star",
+            r#"
+def f(x):
+    if x:
+        y = noop(1)
+    else:
+        y = noop(2)
+    return y
+
+f(True)
+"#
+            .to_owned(),
+            &Dialect::AllOptionsInternal,
+        )
+        .unwrap();
+        eval.enable_profile(&ProfileMode::Coverage).unwrap();
+        let mut globals = GlobalsBuilder::standard();
+        test_functions(&mut globals);
+        eval.eval_module(module, &globals.build()).unwrap();
+
+        let mut lines: Vec<usize> = eval
+            .coverage()
+            .unwrap()
+            .into_iter()
+            .map(|s| s.span.begin.line + 1)
+            .collect();
+        lines.sort();
+        lines.dedup();
+        // Line 6 is the `else` branch which was not executed.
+        assert_eq!([2, 3, 4, 7, 9].as_slice(), lines);
+    }
+
     #[test]
     fn test_merge() {
         let x = CodeMap::new("x.star".to_owned(), "def a(): pass".to_owned());
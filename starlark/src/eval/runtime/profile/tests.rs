@@ -17,10 +17,12 @@
 
 #![cfg(test)]
 
+use starlark_derive::starlark_module;
 use starlark_syntax::golden_test_template::golden_test_template;
 use starlark_syntax::syntax::AstModule;
 use starlark_syntax::syntax::Dialect;
 
+use crate as starlark;
 use crate::assert::test_functions;
 use crate::environment::GlobalsBuilder;
 use crate::environment::Module;
@@ -28,6 +30,7 @@ use crate::eval::runtime::profile::data::ProfileDataImpl;
 use crate::eval::Evaluator;
 use crate::eval::ProfileData;
 use crate::eval::ProfileMode;
+use crate::values::none::NoneType;
 
 fn test_profile_golden_for_mode(mode: ProfileMode) {
     let module = Module::new();
@@ -139,7 +142,147 @@ fn test_profile_golden_time_flame() {
     test_profile_golden_for_mode(ProfileMode::TimeFlame);
 }
 
+#[test]
+fn test_profile_golden_chrome_trace() {
+    test_profile_golden_for_mode(ProfileMode::ChromeTrace);
+}
+
 #[test]
 fn test_profile_golden_typecheck() {
     test_profile_golden_for_mode(ProfileMode::Typecheck);
 }
+
+#[test]
+fn test_time_flame_nested_calls() {
+    let module = Module::new();
+    let mut eval = Evaluator::new(&module);
+    eval.enable_profile(&ProfileMode::TimeFlame).unwrap();
+    eval.eval_module(
+        AstModule::parse(
+            "test.star",
+            r#"
+def inner():
+    return noop([1, 2, 3])
+
+def outer():
+    return inner()
+
+outer()
+"#
+            .to_owned(),
+            &Dialect::AllOptionsInternal,
+        )
+        .unwrap(),
+        &GlobalsBuilder::extended().with(test_functions).build(),
+    )
+    .unwrap();
+
+    let mut folded = Vec::new();
+    eval.gen_profile().unwrap().write_to(&mut folded).unwrap();
+    let folded = String::from_utf8(folded).unwrap();
+    assert!(
+        folded
+            .lines()
+            .any(|line| line.starts_with(r#"root;"module";test.star.outer;test.star.inner;noop "#)),
+        "{}",
+        folded
+    );
+}
+
+#[test]
+fn test_chrome_trace_nested_calls() {
+    let module = Module::new();
+    let mut eval = Evaluator::new(&module);
+    eval.enable_profile(&ProfileMode::ChromeTrace).unwrap();
+    eval.eval_module(
+        AstModule::parse(
+            "test.star",
+            r#"
+def inner():
+    return noop([1, 2, 3])
+
+def outer():
+    return inner()
+
+outer()
+"#
+            .to_owned(),
+            &Dialect::AllOptionsInternal,
+        )
+        .unwrap(),
+        &GlobalsBuilder::extended().with(test_functions).build(),
+    )
+    .unwrap();
+
+    let trace = eval.gen_profile().unwrap().gen().unwrap();
+    let trace: serde_json::Value = serde_json::from_str(&trace).unwrap();
+    let mut stack = Vec::new();
+    let mut nested = Vec::new();
+    let mut last_ts = 0;
+    for event in trace["traceEvents"].as_array().unwrap() {
+        let name = event["name"].as_str().unwrap();
+        let ts = event["ts"].as_u64().unwrap();
+        assert!(ts >= last_ts, "{}", trace);
+        last_ts = ts;
+        match event["ph"].as_str().unwrap() {
+            "B" => {
+                stack.push(name);
+                if name == "noop" {
+                    nested = stack.clone();
+                }
+            }
+            "E" => assert_eq!(Some(name), stack.pop(), "{}", trace),
+            ph => panic!("unexpected phase `{}`", ph),
+        }
+    }
+    assert!(stack.is_empty(), "{}", trace);
+    assert_eq!(
+        vec!["test.star.outer", "test.star.inner", "noop"],
+        nested[nested.len() - 3..],
+        "{}",
+        trace
+    );
+}
+
+#[starlark_module]
+fn profile_name_functions(globals: &mut GlobalsBuilder) {
+    #[starlark(profile_name = "custom_label")]
+    fn labelled() -> anyhow::Result<NoneType> {
+        Ok(NoneType)
+    }
+}
+
+#[test]
+fn test_time_flame_profile_name() {
+    let module = Module::new();
+    let mut eval = Evaluator::new(&module);
+    eval.enable_profile(&ProfileMode::TimeFlame).unwrap();
+    eval.eval_module(
+        AstModule::parse(
+            "test.star",
+            r#"
+def outer():
+    labelled()
+
+outer()
+"#
+            .to_owned(),
+            &Dialect::AllOptionsInternal,
+        )
+        .unwrap(),
+        &GlobalsBuilder::new().with(profile_name_functions).build(),
+    )
+    .unwrap();
+
+    let mut folded = Vec::new();
+    eval.gen_profile().unwrap().write_to(&mut folded).unwrap();
+    let folded = String::from_utf8(folded).unwrap();
+    assert!(
+        folded
+            .lines()
+            .any(|line| line.starts_with(r#"root;"module";test.star.outer;custom_label "#)),
+        "{}",
+        folded
+    );
+    assert!(!folded.contains("labelled"), "{}", folded);
+}
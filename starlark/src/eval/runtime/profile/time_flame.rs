@@ -24,6 +24,9 @@ use starlark_map::StarlarkHasherBuilder;
 use starlark_syntax::slice_vec_ext::SliceExt;
 
 use crate as starlark;
+use crate::eval::runtime::profile::chrome_trace::ChromeTraceData;
+use crate::eval::runtime::profile::chrome_trace::ChromeTraceEvent;
+use crate::eval::runtime::profile::chrome_trace::ChromeTracePhase;
 use crate::eval::runtime::profile::data::ProfileData;
 use crate::eval::runtime::profile::data::ProfileDataImpl;
 use crate::eval::runtime::profile::flamegraph::FlameGraphData;
@@ -33,6 +36,7 @@ use crate::eval::runtime::profile::profiler_type::ProfilerType;
 use crate::eval::runtime::small_duration::SmallDuration;
 use crate::eval::ProfileMode;
 use crate::util::arc_str::ArcStr;
+use crate::values::function::NativeFunction;
 use crate::values::layout::pointer::RawPointer;
 use crate::values::FrozenValue;
 use crate::values::Trace;
@@ -271,12 +275,60 @@ impl<'v> TimeFlameProfile<'v> {
         }
     }
 
+    /// Same recorded calls as [`gen`](Self::gen), but as a timeline.
+    pub(crate) fn gen_chrome_trace(&self) -> crate::Result<ProfileData> {
+        match &self.0 {
+            None => Err(crate::Error::new_other(FlameProfileError::NotEnabled)),
+            Some(x) => Ok(Self::gen_chrome_trace_profile(x)),
+        }
+    }
+
+    fn names(x: &FlameData) -> (Vec<String>, Vec<String>) {
+        let name = |x: Value| {
+            NativeFunction::profile_name(x).map_or_else(|| x.to_repr(), str::to_owned)
+        };
+        let mutable_names = x.index.mutable_values.map(|x| name(*x));
+        let frozen_names = x.index.frozen_values.map(|x| name(x.to_value()));
+        (mutable_names, frozen_names)
+    }
+
+    fn gen_chrome_trace_profile(x: &FlameData) -> ProfileData {
+        let (mutable_names, frozen_names) = Self::names(x);
+        let mut events = Vec::with_capacity(x.frames.len());
+        // Names of the open frames, `Pop` does not record which frame it closes.
+        let mut stack = Vec::new();
+        if let Some(start) = x.frames.first().map(|x| x.1) {
+            for (frame, time) in &x.frames {
+                let (name, phase) = match frame {
+                    Frame::Push(i) => {
+                        let name = i.lookup(&mutable_names, &frozen_names).as_str();
+                        stack.push(name);
+                        (name, ChromeTracePhase::Begin)
+                    }
+                    Frame::Pop => match stack.pop() {
+                        Some(name) => (name, ChromeTracePhase::End),
+                        None => continue,
+                    },
+                };
+                events.push(ChromeTraceEvent {
+                    name: name.to_owned(),
+                    phase,
+                    ts: time.duration_since(start).as_micros() as u64,
+                    pid: 0,
+                    tid: 0,
+                });
+            }
+        }
+        ProfileData {
+            profile: ProfileDataImpl::ChromeTrace(ChromeTraceData { events }),
+        }
+    }
+
     fn gen_profile(x: &FlameData) -> ProfileData {
         // Need to write out lines which look like:
         // root;calls1;calls2 1
         // All the numbers at the end must be whole numbers (we use milliseconds)
-        let mutable_names = x.index.mutable_values.map(|x| x.to_repr());
-        let frozen_names = x.index.frozen_values.map(|x| x.to_value().to_repr());
+        let (mutable_names, frozen_names) = Self::names(x);
         ProfileData {
             profile: ProfileDataImpl::TimeFlameProfile(
                 Stacks::new(&mutable_names, &frozen_names, &x.frames).render(),
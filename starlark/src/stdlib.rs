@@ -30,6 +30,7 @@ mod funcs;
 pub(crate) mod internal;
 pub(crate) mod json;
 pub(crate) mod partial;
+pub(crate) mod regex;
 
 pub use extra::PrintHandler;
 
@@ -98,6 +99,8 @@ pub enum LibraryExtension {
     CallStack,
     /// Definitions to support the `set` type, the `set()` constructor.
     SetType,
+    /// Add a namespace `re` with `match`, `search`, `findall` and `sub` functions.
+    Regex,
     // Make sure if you add anything new, you add it to `all` below.
 }
 
@@ -124,6 +127,7 @@ impl LibraryExtension {
             Internal,
             CallStack,
             SetType,
+            Regex,
         ]
     }
 
@@ -149,6 +153,7 @@ impl LibraryExtension {
             Typing => typing::globals::register_typing(builder),
             Internal => register_internal(builder),
             CallStack => call_stack::global(builder),
+            Regex => regex::regex(builder),
         }
     }
 }
@@ -267,4 +272,48 @@ getattr(True2, "invert2")() == False2
 "#,
         );
     }
+
+    #[test]
+    fn test_native_attribute_dir_getattr_hasattr_agree() {
+        #[derive(Debug, Display, ProvidesStaticType, NoSerialize, Allocative)]
+        #[display("Point")]
+        struct Point(i32);
+        starlark_simple_value!(Point);
+
+        #[starlark_value(type = "Point")]
+        impl<'v> StarlarkValue<'v> for Point {
+            fn get_methods() -> Option<&'static Methods> {
+                static RES: MethodsStatic = MethodsStatic::new();
+                RES.methods(methods)
+            }
+        }
+
+        #[starlark_module]
+        fn globals(builder: &mut GlobalsBuilder) {
+            const POINT: Point = Point(1);
+        }
+
+        #[starlark_module]
+        fn methods(builder: &mut MethodsBuilder) {
+            #[starlark(attribute)]
+            fn x(this: &Point) -> anyhow::Result<i32> {
+                Ok(this.0)
+            }
+        }
+
+        let mut a = Assert::new();
+        a.globals_add(globals);
+        a.all_true(
+            r#"
+dir(POINT) == ["x"]
+hasattr(POINT, "x")
+getattr(POINT, "x") == 1
+getattr(POINT, "x", 2) == 1
+not hasattr(POINT, "y")
+getattr(POINT, "y", 2) == 2
+getattr(POINT, "y", None) == None
+"#,
+        );
+        a.fail("getattr(POINT, 'y')", "Operation `.y` not supported");
+    }
 }
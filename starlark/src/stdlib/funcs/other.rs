@@ -23,6 +23,7 @@ use std::cmp::Ordering;
 use starlark_derive::starlark_module;
 
 use crate as starlark;
+use crate::collections::SmallMap;
 use crate::environment::GlobalsBuilder;
 use crate::eval::Evaluator;
 use crate::values::list::AllocList;
@@ -37,6 +38,7 @@ use crate::values::Heap;
 use crate::values::Value;
 use crate::values::ValueError;
 use crate::values::ValueLike;
+use crate::FailWithContext;
 
 #[starlark_module]
 pub(crate) fn register_other(builder: &mut GlobalsBuilder) {
@@ -50,18 +52,42 @@ pub(crate) fn register_other(builder: &mut GlobalsBuilder) {
     /// fail("oops", 1, False)  # fail: oops 1 False
     /// # "#, "oops 1 False");
     /// ```
-    fn fail(#[starlark(args)] args: UnpackTuple<Value>) -> starlark::Result<StarlarkNever> {
-        let mut s = String::new();
-        for x in args.items {
-            s.push(' ');
+    ///
+    /// Named arguments are not part of the message,
+    /// they are available from Rust with [`Error::fail_context`](crate::Error::fail_context).
+    fn fail<'v>(
+        #[starlark(args)] args: UnpackTuple<Value<'v>>,
+        #[starlark(kwargs)] context: SmallMap<String, Value<'v>>,
+    ) -> starlark::Result<StarlarkNever> {
+        fn push_str(s: &mut String, x: Value) {
             match x.unpack_str() {
                 Some(x) => s.push_str(x),
-                None => x.collect_repr(&mut s),
+                None => x.collect_repr(s),
             }
         }
-        Err(starlark::Error::new_kind(starlark::ErrorKind::Fail(
-            anyhow::Error::msg(s),
-        )))
+
+        let mut s = String::new();
+        for x in args.items {
+            s.push(' ');
+            push_str(&mut s, x);
+        }
+        let e = if context.is_empty() {
+            anyhow::Error::msg(s)
+        } else {
+            let context = context
+                .into_iter()
+                .map(|(k, v)| {
+                    let mut s = String::new();
+                    push_str(&mut s, v);
+                    (k, s)
+                })
+                .collect();
+            anyhow::Error::new(FailWithContext {
+                message: s,
+                context,
+            })
+        };
+        Err(starlark::Error::new_kind(starlark::ErrorKind::Fail(e)))
     }
 
     /// [any](
@@ -154,6 +180,7 @@ pub(crate) fn register_other(builder: &mut GlobalsBuilder) {
     /// # starlark::assert::all_true(r#"
     /// enumerate(["zero", "one", "two"]) == [(0, "zero"), (1, "one"), (2, "two")]
     /// enumerate(["one", "two"], 1) == [(1, "one"), (2, "two")]
+    /// enumerate(["one", "two"], start = 1) == [(1, "one"), (2, "two")]
     /// # "#);
     /// ```
     #[starlark(speculative_exec_safe)]
@@ -180,11 +207,12 @@ pub(crate) fn register_other(builder: &mut GlobalsBuilder) {
     /// `getattr(x, "f")` is equivalent to `x.f`.
     ///
     /// `getattr(x, "f", d)` is equivalent to `x.f if hasattr(x, "f") else d`
-    /// and will never raise an error.
+    /// and will not raise an error if the attribute is missing.
     ///
     /// ```
     /// # starlark::assert::all_true(r#"
     /// getattr("banana", "split")("a") == ["b", "n", "n", ""] # equivalent to "banana".split("a")
+    /// getattr("banana", "missing", 42) == 42
     /// # "#);
     /// ```
     #[starlark(speculative_exec_safe)]
@@ -426,6 +454,29 @@ mod tests {
         assert::fail("chr(-1)", "negative");
     }
 
+    #[test]
+    fn test_fail_context() {
+        let e = assert::fail(r#"fail("bad input", line = 7, file = "a.star")"#, "bad input");
+        let context = e.fail_context().unwrap();
+        assert_eq!(
+            vec![("line", "7"), ("file", "a.star")],
+            context
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str()))
+                .collect::<Vec<_>>()
+        );
+        assert!(assert::fail("fail('bad input')", "bad input")
+            .fail_context()
+            .is_none());
+    }
+
+    #[test]
+    fn test_enumerate() {
+        assert::eq("[(1, 'a'), (2, 'b')]", "enumerate(['a', 'b'], start = 1)");
+        assert::eq("[(-1, 'a'), (0, 'b')]", "enumerate(['a', 'b'], -1)");
+        assert::eq("[]", "enumerate([], start = 5)");
+    }
+
     #[test]
     fn test_hash() {
         assert::eq("0", "hash('')");
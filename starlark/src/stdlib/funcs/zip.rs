@@ -25,9 +25,11 @@ use crate::typing::call_args::TyCallArgs;
 use crate::typing::callable::TyCallable;
 use crate::typing::error::TypingOrInternalError;
 use crate::typing::function::TyCustomFunctionImpl;
+use crate::typing::ParamIsRequired;
 use crate::typing::ParamSpec;
 use crate::typing::Ty;
 use crate::typing::TypingOracleCtx;
+use crate::util::arc_str::ArcStr;
 use crate::values::tuple::UnpackTuple;
 use crate::values::typing::StarlarkIter;
 use crate::values::FrozenValue;
@@ -41,7 +43,17 @@ struct ZipType;
 impl TyCustomFunctionImpl for ZipType {
     fn as_callable(&self) -> TyCallable {
         // TODO(nga): this should be obtained from function signature from function definition.
-        TyCallable::new(ParamSpec::args(Ty::iter(Ty::any())), Ty::list(Ty::any()))
+        TyCallable::new(
+            ParamSpec::new_parts(
+                [],
+                [],
+                Some(Ty::iter(Ty::any())),
+                [(ArcStr::new_static("strict"), ParamIsRequired::No, Ty::bool())],
+                None,
+            )
+            .expect("Cannot fail"),
+            Ty::list(Ty::any()),
+        )
     }
 
     fn validate_call(
@@ -76,21 +88,26 @@ pub(crate) fn register_zip(globals: &mut GlobalsBuilder) {
     /// of the sequences, and so on.  The result list is only as long as the
     /// shortest of the input sequences.
     ///
+    /// With `strict = True`, all the sequences must have the same length,
+    /// and it is an error if they don't.
+    ///
     /// ```
     /// # starlark::assert::all_true(r#"
     /// zip()                           == []
     /// zip(range(5))                   == [(0,), (1,), (2,), (3,), (4,)]
     /// zip(range(5), "abc".elems())    == [(0, "a"), (1, "b"), (2, "c")]
+    /// zip([1, 2], "ab".elems(), strict = True) == [(1, "a"), (2, "b")]
     /// # "#);
     /// ```
     #[starlark(speculative_exec_safe, ty_custom_function = ZipType)]
     fn zip<'v>(
         #[starlark(args)] args: UnpackTuple<ValueOfUnchecked<'v, StarlarkIter<FrozenValue>>>,
+        #[starlark(require = named, default = false)] strict: bool,
         heap: &'v Heap,
     ) -> starlark::Result<Vec<Value<'v>>> {
         let mut v = Vec::new();
         let mut first = true;
-        for arg in args.items {
+        for (i, arg) in args.items.into_iter().enumerate() {
             let mut idx = 0;
             for e in arg.get().iterate(heap)? {
                 if first {
@@ -99,11 +116,46 @@ pub(crate) fn register_zip(globals: &mut GlobalsBuilder) {
                 } else if idx < v.len() {
                     v[idx] = v[idx].add(heap.alloc((e,)), heap)?;
                     idx += 1;
+                } else if strict {
+                    return Err(anyhow::anyhow!(
+                        "zip() argument {} is longer than argument 1, lengths diverge at index {}",
+                        i + 1,
+                        idx
+                    )
+                    .into());
                 }
             }
+            if strict && idx < v.len() {
+                return Err(anyhow::anyhow!(
+                    "zip() argument {} is shorter than argument 1, lengths diverge at index {}",
+                    i + 1,
+                    idx
+                )
+                .into());
+            }
             v.truncate(idx);
             first = false;
         }
         Ok(v)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::assert;
+
+    #[test]
+    fn test_zip_strict() {
+        assert::eq("[(1, 'a'), (2, 'b')]", "zip([1, 2], ['a', 'b'], strict = True)");
+        assert::eq("[]", "zip(strict = True)");
+        assert::eq("[(1, 'a')]", "zip([1, 2], ['a'], strict = False)");
+        assert::fail(
+            "zip([1, 2, 3], ['a'], strict = True)",
+            "zip() argument 2 is shorter than argument 1, lengths diverge at index 1",
+        );
+        assert::fail(
+            "zip([1], ['a'], ['x', 'y'], strict = True)",
+            "zip() argument 3 is longer than argument 1, lengths diverge at index 1",
+        );
+    }
+}
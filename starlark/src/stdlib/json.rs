@@ -22,6 +22,8 @@ use std::str::FromStr;
 
 use either::Either;
 use num_bigint::BigInt;
+use serde::Serialize;
+use serde_json::ser::PrettyFormatter;
 use starlark_derive::starlark_module;
 
 use crate as starlark;
@@ -29,6 +31,7 @@ use crate::collections::SmallMap;
 use crate::environment::GlobalsBuilder;
 use crate::typing::Ty;
 use crate::values::dict::AllocDict;
+use crate::values::none::NoneOr;
 use crate::values::type_repr::StarlarkTypeRepr;
 use crate::values::types::int::int_or_big::StarlarkInt;
 use crate::values::AllocFrozenValue;
@@ -208,18 +211,72 @@ impl AllocFrozenValue for serde_json::Value {
     }
 }
 
+/// Replace all numbers with their floating point approximation.
+fn numbers_to_floats(x: &mut serde_json::Value) -> anyhow::Result<()> {
+    match x {
+        serde_json::Value::Number(n) => {
+            if !n.is_f64() {
+                let f = n.as_f64().unwrap_or(f64::INFINITY);
+                *n = serde_json::Number::from_f64(f).ok_or_else(|| {
+                    anyhow::anyhow!("Number `{n}` cannot be represented as float")
+                })?;
+            }
+        }
+        serde_json::Value::Array(xs) => {
+            for x in xs {
+                numbers_to_floats(x)?;
+            }
+        }
+        serde_json::Value::Object(xs) => {
+            for x in xs.values_mut() {
+                numbers_to_floats(x)?;
+            }
+        }
+        serde_json::Value::Null | serde_json::Value::Bool(_) | serde_json::Value::String(_) => {}
+    }
+    Ok(())
+}
+
 pub(crate) fn json(globals: &mut GlobalsBuilder) {
     #[starlark_module]
     fn json_members(globals: &mut GlobalsBuilder) {
-        fn encode(#[starlark(require = pos)] x: Value) -> anyhow::Result<String> {
-            x.to_json()
+        /// Encode a value as JSON.
+        ///
+        /// If `indent` is given, the output is pretty-printed,
+        /// with each nesting level indented by that many spaces.
+        fn encode(
+            #[starlark(require = pos)] x: Value,
+            #[starlark(require = named, default = NoneOr::None)] indent: NoneOr<u32>,
+        ) -> anyhow::Result<String> {
+            match indent {
+                NoneOr::None => x.to_json(),
+                NoneOr::Other(indent) => {
+                    let indent = " ".repeat(indent as usize);
+                    let mut buf = Vec::new();
+                    let mut ser = serde_json::Serializer::with_formatter(
+                        &mut buf,
+                        PrettyFormatter::with_indent(indent.as_bytes()),
+                    );
+                    x.serialize(&mut ser)?;
+                    Ok(String::from_utf8(buf)?)
+                }
+            }
         }
 
+        /// Decode a JSON string.
+        ///
+        /// Objects are decoded as dicts and arrays as lists.
+        /// If `int_as_float` is `True`, all numbers are decoded as floats.
         fn decode<'v>(
             #[starlark(require = pos)] x: &str,
+            #[starlark(require = named, default = false)] int_as_float: bool,
             heap: &'v Heap,
         ) -> anyhow::Result<Value<'v>> {
-            Ok(heap.alloc(serde_json::from_str::<serde_json::Value>(x)?))
+            let mut x = serde_json::from_str::<serde_json::Value>(x)?;
+            if int_as_float {
+                numbers_to_floats(&mut x)?;
+            }
+            Ok(heap.alloc(x))
         }
     }
 
@@ -253,4 +310,52 @@ mod tests {
             "json.decode('123456789123456789123456789')",
         );
     }
+
+    #[test]
+    fn test_json_encode_nested() {
+        let a = Assert::new();
+        a.eq(
+            r#"'{"a":[1,"x",true,null],"b":{}}'"#,
+            "json.encode({'a': [1, 'x', True, None], 'b': {}})",
+        );
+    }
+
+    #[test]
+    fn test_json_encode_indent() {
+        let a = Assert::new();
+        a.eq(
+            r#"'{\n  "a": [\n    1,\n    2\n  ]\n}'"#,
+            "json.encode({'a': [1, 2]}, indent = 2)",
+        );
+        a.eq("'[]'", "json.encode([], indent = 2)");
+    }
+
+    #[test]
+    fn test_json_encode_function_fails() {
+        let a = Assert::new();
+        a.fail("json.encode([len])", "not supported");
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let a = Assert::new();
+        a.pass(
+            r#"
+x = {"a": [1, 2.5, "s", None, True], "b": {"c": []}}
+assert_eq(x, json.decode(json.encode(x)))
+assert_eq(x, json.decode(json.encode(x, indent = 4)))
+"#,
+        );
+    }
+
+    #[test]
+    fn test_json_decode_int_as_float() {
+        let a = Assert::new();
+        a.eq(
+            "[1.0, {'k': 2.5}]",
+            "json.decode('[1, {\"k\": 2.5}]', int_as_float = True)",
+        );
+        a.eq("'float'", "type(json.decode('1', int_as_float = True))");
+        a.eq("'int'", "type(json.decode('1'))");
+    }
 }
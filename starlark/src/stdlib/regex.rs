@@ -0,0 +1,336 @@
+/*
+ * Copyright 2018 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! The `re` module, a small subset of Python's `re` backed by the `regex` crate.
+
+use regex::Captures;
+use regex::Regex;
+use starlark_derive::starlark_module;
+use thiserror::Error;
+
+use crate as starlark;
+use crate::collections::SmallMap;
+use crate::environment::GlobalsBuilder;
+use crate::eval::Evaluator;
+use crate::values::none::NoneOr;
+use crate::values::tuple::AllocTuple;
+use crate::values::Heap;
+use crate::values::Value;
+
+#[derive(Error, Debug)]
+enum RegexError {
+    #[error("Invalid regex pattern `{0}`: {1}")]
+    InvalidPattern(String, regex::Error),
+    #[error("Invalid group reference in replacement string `{0}`")]
+    InvalidReplacement(String),
+}
+
+/// Compiled patterns used by the `re` module during one evaluation, keyed by pattern string.
+///
+/// Holds at most [`RegexCache::CAPACITY`] patterns, evicting the least recently used,
+/// so scripts generating many distinct patterns do not grow memory without bound.
+#[derive(Default)]
+pub(crate) struct RegexCache {
+    /// Ordered from least to most recently used.
+    regexes: SmallMap<String, Regex>,
+}
+
+impl RegexCache {
+    pub(crate) const CAPACITY: usize = 64;
+
+    fn get_or_compile(&mut self, pattern: &str) -> anyhow::Result<&Regex> {
+        let regex = match self.regexes.shift_remove(pattern) {
+            Some(regex) => regex,
+            None => {
+                if self.regexes.len() >= Self::CAPACITY {
+                    self.regexes.shift_remove_index(0);
+                }
+                Regex::new(pattern)
+                    .map_err(|e| RegexError::InvalidPattern(pattern.to_owned(), e))?
+            }
+        };
+        self.regexes.insert(pattern.to_owned(), regex);
+        Ok(self.regexes.last().unwrap().1)
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.regexes.len()
+    }
+}
+
+/// Compile `pattern`, reusing a previously compiled regex from this evaluation if there is one.
+fn compile<'e>(eval: &'e mut Evaluator, pattern: &str) -> anyhow::Result<&'e Regex> {
+    eval.regex_cache.get_or_compile(pattern)
+}
+
+/// Tuple of the whole match followed by all the groups, with `None` for groups which did not match.
+fn alloc_groups<'v>(captures: &Captures, heap: &'v Heap) -> Value<'v> {
+    heap.alloc(AllocTuple(captures.iter().map(|m| match m {
+        Some(m) => heap.alloc_str(m.as_str()).to_value(),
+        None => Value::new_none(),
+    })))
+}
+
+/// Convert Python replacement syntax (`\1`, `\g<1>`, `\g<name>`) into `regex` crate syntax.
+fn convert_replacement(repl: &str) -> anyhow::Result<String> {
+    let invalid = || RegexError::InvalidReplacement(repl.to_owned());
+    let mut res = String::with_capacity(repl.len());
+    let mut chars = repl.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '$' => res.push_str("$$"),
+            '\\' => match chars.next() {
+                Some(d) if d.is_ascii_digit() => {
+                    let mut group = d.to_string();
+                    if let Some(&e) = chars.peek() {
+                        if e.is_ascii_digit() {
+                            group.push(e);
+                            chars.next();
+                        }
+                    }
+                    res.push_str(&format!("${{{}}}", group));
+                }
+                Some('g') => {
+                    if chars.next() != Some('<') {
+                        return Err(invalid().into());
+                    }
+                    let mut group = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('>') => break,
+                            Some(e) => group.push(e),
+                            None => return Err(invalid().into()),
+                        }
+                    }
+                    if group.is_empty() {
+                        return Err(invalid().into());
+                    }
+                    res.push_str(&format!("${{{}}}", group));
+                }
+                Some('\\') => res.push('\\'),
+                Some('n') => res.push('\n'),
+                Some('t') => res.push('\t'),
+                Some(d) => {
+                    res.push('\\');
+                    res.push(d);
+                }
+                None => return Err(invalid().into()),
+            },
+            c => res.push(c),
+        }
+    }
+    Ok(res)
+}
+
+pub(crate) fn regex(globals: &mut GlobalsBuilder) {
+    #[starlark_module]
+    fn regex_members(globals: &mut GlobalsBuilder) {
+        /// Match `pattern` at the start of `string`.
+        ///
+        /// Returns `None` if there is no match, otherwise a tuple of the whole match
+        /// followed by each capture group, with `None` for groups which did not participate.
+        ///
+        /// ```
+        /// # starlark::assert::all_true(r#"
+        /// re.match("a(b)?", "ac") == ("a", None)
+        /// re.match("b", "ab") == None
+        /// # "#);
+        /// ```
+        fn r#match<'v>(
+            #[starlark(require = pos)] pattern: &str,
+            #[starlark(require = pos)] string: &str,
+            eval: &mut Evaluator<'v, '_, '_>,
+        ) -> anyhow::Result<NoneOr<Value<'v>>> {
+            let heap = eval.heap();
+            Ok(match compile(eval, pattern)?.captures(string) {
+                Some(c) if c.get(0).is_some_and(|m| m.start() == 0) => {
+                    NoneOr::Other(alloc_groups(&c, heap))
+                }
+                _ => NoneOr::None,
+            })
+        }
+
+        /// Find the first match of `pattern` anywhere in `string`.
+        ///
+        /// Returns `None` if there is no match, otherwise the same tuple as `re.match`.
+        ///
+        /// ```
+        /// # starlark::assert::all_true(r#"
+        /// re.search("b(c)", "abcd") == ("bc", "c")
+        /// # "#);
+        /// ```
+        fn search<'v>(
+            #[starlark(require = pos)] pattern: &str,
+            #[starlark(require = pos)] string: &str,
+            eval: &mut Evaluator<'v, '_, '_>,
+        ) -> anyhow::Result<NoneOr<Value<'v>>> {
+            let heap = eval.heap();
+            Ok(match compile(eval, pattern)?.captures(string) {
+                Some(c) => NoneOr::Other(alloc_groups(&c, heap)),
+                None => NoneOr::None,
+            })
+        }
+
+        /// Return all non-overlapping matches of `pattern` in `string`.
+        ///
+        /// As in Python, if the pattern has no groups the result is a list of matched strings,
+        /// if it has one group a list of that group's strings,
+        /// and otherwise a list of tuples of groups.
+        ///
+        /// ```
+        /// # starlark::assert::all_true(r#"
+        /// re.findall("[0-9]+", "a1b22") == ["1", "22"]
+        /// re.findall("(\\w)=(\\d)", "a=1 b=2") == [("a", "1"), ("b", "2")]
+        /// # "#);
+        /// ```
+        fn findall<'v>(
+            #[starlark(require = pos)] pattern: &str,
+            #[starlark(require = pos)] string: &str,
+            eval: &mut Evaluator<'v, '_, '_>,
+        ) -> anyhow::Result<Vec<Value<'v>>> {
+            let heap = eval.heap();
+            let regex = compile(eval, pattern)?;
+            let group = |c: &Captures, i: usize| match c.get(i) {
+                Some(m) => heap.alloc_str(m.as_str()).to_value(),
+                None => heap.alloc_str("").to_value(),
+            };
+            Ok(regex
+                .captures_iter(string)
+                .map(|c| match c.len() {
+                    1 => group(&c, 0),
+                    2 => group(&c, 1),
+                    n => heap.alloc(AllocTuple((1..n).map(|i| group(&c, i)))),
+                })
+                .collect())
+        }
+
+        /// Replace matches of `pattern` in `string` with `repl`.
+        ///
+        /// `repl` may refer to groups with `\1` or `\g<name>`.
+        /// If `count` is positive, at most `count` replacements are made.
+        ///
+        /// ```
+        /// # starlark::assert::all_true(r#"
+        /// re.sub("(\\w+)@(\\w+)", "\\2 at \\1", "me@host") == "host at me"
+        /// re.sub("a", "b", "aaa", count = 2) == "bba"
+        /// # "#);
+        /// ```
+        fn sub(
+            #[starlark(require = pos)] pattern: &str,
+            #[starlark(require = pos)] repl: &str,
+            #[starlark(require = pos)] string: &str,
+            #[starlark(require = named, default = 0)] count: i32,
+            eval: &mut Evaluator,
+        ) -> anyhow::Result<String> {
+            let repl = convert_replacement(repl)?;
+            let regex = compile(eval, pattern)?;
+            Ok(regex
+                .replacen(string, count.max(0) as usize, repl.as_str())
+                .into_owned())
+        }
+    }
+
+    globals.namespace("re", regex_members);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RegexCache;
+    use crate::assert::Assert;
+    use crate::environment::Globals;
+    use crate::environment::Module;
+    use crate::eval::Evaluator;
+    use crate::syntax::AstModule;
+    use crate::syntax::Dialect;
+
+    #[test]
+    fn test_match_search() {
+        let a = Assert::new();
+        a.eq("('ab', 'b')", "re.match('a(b)', 'abc')");
+        a.eq("None", "re.match('b', 'abc')");
+        a.eq("('b',)", "re.search('b', 'abc')");
+        a.eq("None", "re.search('z', 'abc')");
+    }
+
+    #[test]
+    fn test_findall_groups() {
+        let a = Assert::new();
+        a.eq("['a', 'b']", "re.findall('[a-z]', 'a1b2')");
+        a.eq("['1', '2']", "re.findall('[a-z]([0-9])', 'a1b2')");
+        a.eq(
+            "[('a', '1'), ('b', '')]",
+            "re.findall('([a-z])([0-9])?', 'a1b')",
+        );
+        a.eq("[]", "re.findall('x', 'abc')");
+    }
+
+    #[test]
+    fn test_sub_backreferences() {
+        let a = Assert::new();
+        a.eq("'b-a d-c'", r#"re.sub("(\\w)(\\w)", "\\2-\\1", "ab cd")"#);
+        a.eq(
+            "'[k]'",
+            r#"re.sub("(?P<key>\\w)=\\w", "[\\g<key>]", "k=v")"#,
+        );
+        a.eq("'$1'", r#"re.sub("a", "$1", "a")"#);
+        a.eq("'xxa'", r#"re.sub("a", "x", "aaa", count = 2)"#);
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let a = Assert::new();
+        a.fail("re.match('(', 'a')", "Invalid regex pattern `(`");
+        a.fail("re.sub('a', '\\\\g<', 'a')", "Invalid group reference");
+    }
+
+    #[test]
+    fn test_pattern_cache() {
+        let module = Module::new();
+        let mut eval = Evaluator::new(&module);
+        let ast = AstModule::parse(
+            "x.star",
+            r#"
+for i in range(100):
+    re.findall("[0-9]", str(i))
+    re.search("x{}".format(i), "x")
+"#
+            .to_owned(),
+            &Dialect::AllOptionsInternal,
+        )
+        .unwrap();
+        eval.eval_module(ast, &Globals::extended_internal())
+            .unwrap();
+        // One repeated pattern and 100 distinct ones, bounded by the capacity.
+        assert_eq!(eval.regex_cache.len(), RegexCache::CAPACITY);
+    }
+
+    #[test]
+    fn test_pattern_cache_evicts_least_recently_used() {
+        let mut cache = RegexCache::default();
+        for i in 0..RegexCache::CAPACITY {
+            cache.get_or_compile(&format!("a{i}")).unwrap();
+        }
+        // Touch the oldest pattern, so the next insert evicts `a1` instead.
+        cache.get_or_compile("a0").unwrap();
+        cache.get_or_compile("new").unwrap();
+        assert_eq!(cache.len(), RegexCache::CAPACITY);
+        assert!(cache.regexes.contains_key("a0"));
+        assert!(!cache.regexes.contains_key("a1"));
+        assert!(cache.regexes.contains_key("new"));
+    }
+}
@@ -22,6 +22,7 @@ mod call;
 mod comprehension;
 mod def;
 mod derive;
+mod desugar_comprehensions;
 mod for_loop;
 mod freeze_access_value;
 mod fstring;
@@ -32,4 +33,6 @@ mod replace_binary;
 mod runtime;
 mod type_annot;
 mod uncategorized;
+mod unparse;
 pub(crate) mod util;
+mod with_stmt;
@@ -47,3 +47,29 @@ f()            # 2 + 1
     evaluator.eval_module(ast, &globals).unwrap();
     assert_eq!(7, counter.get());
 }
+
+#[test]
+fn before_stmt_hook() {
+    let module = Module::new();
+    let globals = Globals::new();
+    let mut lines = Vec::new();
+    {
+        let mut evaluator = Evaluator::new(&module);
+        evaluator.add_before_stmt_hook(Box::new(|span, _eval| {
+            lines.push(span.resolve_span().begin.line + 1);
+        }));
+
+        let program = "\
+def f(x):
+  y = x + 1
+  return y
+f(1)
+f(2)
+";
+        let ast =
+            AstModule::parse("a.star", program.to_owned(), &Dialect::AllOptionsInternal).unwrap();
+        evaluator.eval_module(ast, &globals).unwrap();
+    }
+    // Top-level statements are also reported once more for the possible gc.
+    assert_eq!(lines, [1, 1, 4, 4, 2, 3, 5, 5, 2, 3]);
+}
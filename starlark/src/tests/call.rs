@@ -340,3 +340,28 @@ G_F_PTR = g([])
         frame_native_size,
     );
 }
+
+#[test]
+fn test_error_call_stack() {
+    let err = assert::fail(
+        r#"
+def b():
+    fail("oops")
+
+def a():
+    b()
+
+a()
+"#,
+        "oops",
+    );
+    let frames = &err.call_stack().frames;
+    let names: Vec<&str> = frames.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, ["a", "b", "fail"]);
+    // Each frame records the location of its call site.
+    let lines: Vec<String> = frames
+        .iter()
+        .map(|f| f.location.as_ref().unwrap().resolve_span().to_string())
+        .collect();
+    assert_eq!(lines, ["8:1-4", "6:5-8", "3:5-17"]);
+}
@@ -20,6 +20,8 @@ mod attrs;
 mod docs;
 mod freeze;
 mod module;
+mod starlark_value;
 mod trace;
+mod unpack_enum;
 mod unpack_value;
 mod unpack_value_attr;
@@ -18,13 +18,18 @@
 use allocative::Allocative;
 use derive_more::Display;
 use starlark_derive::starlark_attrs;
+use starlark_derive::starlark_module;
 use starlark_derive::starlark_value;
 use starlark_derive::NoSerialize;
 
 use crate as starlark;
 use crate::any::ProvidesStaticType;
 use crate::assert::Assert;
+use crate::environment::Methods;
+use crate::environment::MethodsBuilder;
+use crate::environment::MethodsStatic;
 use crate::starlark_simple_value;
+use crate::values::Heap;
 use crate::values::StarlarkAttrs;
 use crate::values::StarlarkValue;
 
@@ -112,3 +117,57 @@ fn test_derive_attrs() {
     a.is_true("hasattr(example, \"escaped\")");
     a.is_true("not hasattr(example, \"r#escaped\")");
 }
+
+#[test]
+fn test_get_attrs() {
+    #[derive(
+        Debug,
+        StarlarkAttrs,
+        Display,
+        ProvidesStaticType,
+        NoSerialize,
+        Allocative
+    )]
+    #[display("{:?}", self)]
+    struct Point {
+        y: i32,
+        x: i32,
+    }
+    starlark_simple_value!(Point);
+
+    #[starlark_module]
+    fn point_methods(builder: &mut MethodsBuilder) {
+        #[starlark(attribute)]
+        fn sum(this: &Point) -> anyhow::Result<i32> {
+            Ok(this.x + this.y)
+        }
+    }
+
+    #[starlark_value(type = "point")]
+    impl<'v> StarlarkValue<'v> for Point {
+        starlark_attrs!();
+
+        fn get_methods() -> Option<&'static Methods> {
+            static RES: MethodsStatic = MethodsStatic::new();
+            RES.methods(point_methods)
+        }
+    }
+
+    let heap = Heap::new();
+    let point = heap.alloc(Point { y: 2, x: 1 });
+    let attrs: Vec<(String, String)> = point
+        .get_attrs(&heap)
+        .unwrap()
+        .unwrap()
+        .into_iter()
+        .map(|(k, v)| (k, v.to_repr()))
+        .collect();
+    assert_eq!(
+        vec![
+            ("sum".to_owned(), "3".to_owned()),
+            ("y".to_owned(), "2".to_owned()),
+            ("x".to_owned(), "1".to_owned()),
+        ],
+        attrs
+    );
+}
@@ -15,11 +15,16 @@
  * limitations under the License.
  */
 
+mod async_fn;
 mod basic;
+mod const_value;
+mod custom_error;
 mod default_value;
 mod kwargs;
 mod methods;
 mod named_positional;
+mod nondeterministic;
+mod none_or;
 mod other_attributes;
 mod return_impl;
 mod special_params;
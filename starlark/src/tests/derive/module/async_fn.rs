@@ -0,0 +1,78 @@
+/*
+ * Copyright 2019 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Waker;
+
+use starlark_derive::starlark_module;
+
+use crate as starlark;
+use crate::assert::Assert;
+use crate::environment::GlobalsBuilder;
+use crate::eval::BlockingHandle;
+
+/// Future which is pending on the first poll.
+struct YieldOnce(bool);
+
+impl Future for YieldOnce {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            Poll::Pending
+        }
+    }
+}
+
+struct PollLoop;
+
+impl BlockingHandle for PollLoop {
+    fn block_on<'f>(&self, mut future: Pin<Box<dyn Future<Output = ()> + 'f>>) {
+        let mut cx = Context::from_waker(Waker::noop());
+        while future.as_mut().poll(&mut cx).is_pending() {}
+    }
+}
+
+#[starlark_module]
+fn async_functions(globals: &mut GlobalsBuilder) {
+    async fn fetch_double(x: i32) -> anyhow::Result<i32> {
+        YieldOnce(false).await;
+        Ok(x * 2)
+    }
+}
+
+#[test]
+fn test_async_fn() {
+    let mut a = Assert::new();
+    a.globals_add(async_functions);
+    a.setup_eval(|eval| eval.set_blocking_handle(&PollLoop));
+    a.eq("42", "fetch_double(21)");
+    a.eq("'int'", "type(fetch_double(1))");
+}
+
+#[test]
+fn test_async_fn_no_blocking_handle() {
+    let mut a = Assert::new();
+    a.globals_add(async_functions);
+    a.fail("fetch_double(21)", "no blocking handle is set");
+}
@@ -0,0 +1,55 @@
+/*
+ * Copyright 2019 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use starlark_derive::starlark_module;
+
+use crate as starlark;
+use crate::assert::Assert;
+use crate::environment::GlobalsBuilder;
+
+static COMPUTE_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+fn compute_answer() -> i32 {
+    COMPUTE_CALLS.fetch_add(1, Ordering::SeqCst);
+    (1..=6).sum::<i32>() * 2
+}
+
+fn compute_greeting() -> String {
+    ["hello", "world"].join(" ")
+}
+
+#[starlark_module]
+fn computed_consts(globals: &mut GlobalsBuilder) {
+    const ANSWER: i32 = compute_answer();
+    const GREETING: String = compute_greeting();
+}
+
+#[test]
+fn test_computed_const() {
+    let globals = GlobalsBuilder::new().with(computed_consts).build();
+    assert_eq!(1, COMPUTE_CALLS.load(Ordering::SeqCst));
+
+    let mut a = Assert::new();
+    a.globals_add(computed_consts);
+    a.eq("42", "ANSWER");
+    a.eq("'hello world'", "GREETING");
+    a.eq("'int'", "type(ANSWER)");
+    assert_eq!(42, globals.get("ANSWER").unwrap().unpack_i32().unwrap());
+}
@@ -0,0 +1,73 @@
+/*
+ * Copyright 2018 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use starlark_derive::starlark_module;
+
+use crate as starlark;
+use crate::environment::GlobalsBuilder;
+use crate::environment::Module;
+use crate::errors::NativeCallableError;
+use crate::eval::Evaluator;
+use crate::syntax::AstModule;
+use crate::syntax::Dialect;
+use crate::ErrorKind;
+
+#[derive(Debug, thiserror::Error)]
+enum MyError {
+    #[error("bad value: {0}")]
+    BadValue(i32),
+    #[error("bad call")]
+    BadCall,
+}
+
+impl NativeCallableError for MyError {
+    fn into_starlark_error(self) -> crate::Error {
+        match self {
+            MyError::BadValue(_) => crate::Error::new_value(self),
+            MyError::BadCall => crate::Error::new_kind(ErrorKind::Function(self.into())),
+        }
+    }
+}
+
+#[starlark_module]
+fn functions(globals: &mut GlobalsBuilder) {
+    fn check(x: i32) -> Result<i32, MyError> {
+        match x {
+            0 => Err(MyError::BadCall),
+            x if x < 0 => Err(MyError::BadValue(x)),
+            x => Ok(x),
+        }
+    }
+}
+
+fn eval_err(code: &str) -> crate::Error {
+    let globals = GlobalsBuilder::standard().with(functions).build();
+    let module = Module::new();
+    let mut eval = Evaluator::new(&module);
+    let ast = AstModule::parse("x.star", code.to_owned(), &Dialect::Standard).unwrap();
+    eval.eval_module(ast, &globals).unwrap_err()
+}
+
+#[test]
+fn test_custom_error_kind() {
+    let err = eval_err("check(-1)");
+    assert!(matches!(err.kind(), ErrorKind::Value(_)), "{err:?}");
+    assert!(err.to_string().contains("bad value: -1"), "{err}");
+
+    let err = eval_err("def f():\n  check(0)\nf()");
+    assert!(matches!(err.kind(), ErrorKind::Function(_)), "{err:?}");
+}
@@ -28,6 +28,15 @@ fn named_positional_functions(globals: &mut GlobalsBuilder) {
         Ok(x)
     }
 
+    fn positional_many(
+        #[starlark(require = pos)] x: i32,
+        #[starlark(require = pos)] y: i32,
+        #[starlark(require = pos)] z: Option<i32>,
+        #[starlark(require = pos, default = 0)] w: i32,
+    ) -> anyhow::Result<i32> {
+        Ok(x + y + z.unwrap_or_default() + w)
+    }
+
     fn named(x: i32) -> anyhow::Result<i32> {
         Ok(x)
     }
@@ -60,6 +69,19 @@ fn test_positional_only() {
     a.fail("noop(positional)(x=19)", "extra named parameter");
 }
 
+#[test]
+fn test_positional_only_type_error_names_index() {
+    let mut a = Assert::new();
+    a.globals_add(named_positional_functions);
+    a.eq("10", "positional_many(1, 2, 3, 4)");
+    a.fail(
+        "noop(positional_many)(1, 'x')",
+        "Type of parameter `y` doesn't match, expected `int`, actual `string (repr: \"x\")` (argument #2)",
+    );
+    a.fail("noop(positional_many)(1, 2, 'x')", "(argument #3)");
+    a.fail("noop(positional_many)(1, 2, 3, 'x')", "(argument #4)");
+}
+
 #[test]
 fn test_named_can_be_called_as_both_named_and_positional() {
     let mut a = Assert::new();
@@ -73,7 +95,14 @@ fn test_named_only() {
     let mut a = Assert::new();
     a.globals_add(named_positional_functions);
     a.eq("31", "named_only(x=31)");
-    a.fail("noop(named_only)(37)", "Missing named-only parameter");
+    a.fail(
+        "noop(named_only)(37)",
+        "named_only() takes no positional arguments but 1 was given",
+    );
+    a.fail(
+        "noop(named_only)(x=3, *[1, 2])",
+        "named_only() takes no positional arguments but 2 were given",
+    );
 }
 
 #[test]
@@ -0,0 +1,58 @@
+/*
+ * Copyright 2018 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use starlark_derive::starlark_module;
+
+use crate as starlark;
+use crate::environment::GlobalsBuilder;
+use crate::environment::Module;
+use crate::eval::Evaluator;
+use crate::syntax::AstModule;
+use crate::syntax::Dialect;
+
+#[starlark_module]
+fn functions(globals: &mut GlobalsBuilder) {
+    #[starlark(nondeterministic)]
+    fn now() -> anyhow::Result<i32> {
+        Ok(17)
+    }
+
+    fn answer() -> anyhow::Result<i32> {
+        Ok(42)
+    }
+}
+
+fn eval(code: &str, enforce_determinism: bool) -> crate::Result<i32> {
+    let globals = GlobalsBuilder::standard().with(functions).build();
+    let module = Module::new();
+    let mut eval = Evaluator::new(&module);
+    eval.enforce_determinism(enforce_determinism);
+    let ast = AstModule::parse("x.star", code.to_owned(), &Dialect::Standard).unwrap();
+    Ok(eval.eval_module(ast, &globals)?.unpack_i32().unwrap())
+}
+
+#[test]
+fn test_nondeterministic() {
+    assert_eq!(17, eval("now()", false).unwrap());
+    assert_eq!(42, eval("answer()", true).unwrap());
+    let err = eval("def f():\n  return now()\nf()", true).unwrap_err();
+    assert!(
+        err.to_string().contains(
+            "Function `now` is nondeterministic and cannot be called when determinism is enforced"
+        ),
+        "{err}"
+    );
+}
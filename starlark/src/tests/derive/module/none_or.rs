@@ -0,0 +1,68 @@
+/*
+ * Copyright 2019 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use starlark_derive::starlark_module;
+
+use crate as starlark;
+use crate::assert::Assert;
+use crate::environment::GlobalsBuilder;
+use crate::values::none::NoneOr;
+
+#[starlark_module]
+fn none_or_functions(globals: &mut GlobalsBuilder) {
+    fn describe(x: NoneOr<i32>) -> anyhow::Result<String> {
+        Ok(match x {
+            NoneOr::None => "none".to_owned(),
+            NoneOr::Other(x) => format!("int {x}"),
+        })
+    }
+
+    fn describe_defaulted(
+        #[starlark(default = NoneOr::None)] x: NoneOr<i32>,
+    ) -> anyhow::Result<String> {
+        Ok(format!("{:?}", x.into_option()))
+    }
+}
+
+#[test]
+fn test_none_or_param() {
+    let mut a = Assert::new();
+    a.globals_add(none_or_functions);
+    a.eq("'none'", "describe(None)");
+    a.eq("'int 5'", "describe(5)");
+    a.eq("'int 5'", "describe(x=5)");
+    a.fail("noop(describe)()", "Missing parameter `x`");
+}
+
+#[test]
+fn test_none_or_param_defaulted() {
+    let mut a = Assert::new();
+    a.globals_add(none_or_functions);
+    a.eq("'None'", "describe_defaulted()");
+    a.eq("'None'", "describe_defaulted(None)");
+    a.eq("'Some(5)'", "describe_defaulted(5)");
+}
+
+#[test]
+fn test_none_or_param_type() {
+    let mut a = Assert::new();
+    a.globals_add(none_or_functions);
+    a.fail(
+        "noop(describe)('x')",
+        "Type of parameter `x` doesn't match, expected `None | int`",
+    );
+}
@@ -19,7 +19,9 @@ use starlark_derive::starlark_module;
 
 use crate as starlark;
 use crate::assert::Assert;
+use crate::codemap::FileSpan;
 use crate::environment::GlobalsBuilder;
+use crate::eval::Evaluator;
 use crate::values::Heap;
 use crate::values::StringValue;
 
@@ -31,6 +33,18 @@ fn functions(builder: &mut GlobalsBuilder) {
     ) -> anyhow::Result<StringValue<'v>> {
         Ok(starlark_heap.alloc_str_concat(heap, "!"))
     }
+
+    fn warning_line<'v>(
+        message: &str,
+        eval: &mut Evaluator<'v, '_, '_>,
+        call_site: Option<FileSpan>,
+    ) -> anyhow::Result<StringValue<'v>> {
+        let line = match call_site {
+            Some(call_site) => call_site.resolve_span().begin.line + 1,
+            None => 0,
+        };
+        Ok(eval.heap().alloc_str(&format!("line {line}: {message}")))
+    }
 }
 
 #[test]
@@ -39,3 +53,17 @@ fn test_non_standard_param_names() {
     a.globals_add(functions);
     a.eq("'x!'", "non_standard_heap_name('x')");
 }
+
+#[test]
+fn test_call_site() {
+    let mut a = Assert::new();
+    a.globals_add(functions);
+    a.pass(
+        r#"
+assert_eq("line 2: top", warning_line("top"))
+def f():
+    return warning_line("nested")
+assert_eq("line 4: nested", f())
+"#,
+    );
+}
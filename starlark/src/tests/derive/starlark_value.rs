@@ -0,0 +1,229 @@
+/*
+ * Copyright 2018 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fmt::Write;
+use std::hash::Hash;
+
+use allocative::Allocative;
+use derive_more::Display;
+use starlark_derive::starlark_value;
+use starlark_derive::NoSerialize;
+
+use crate as starlark;
+use crate::any::ProvidesStaticType;
+use crate::assert::Assert;
+use crate::collections::StarlarkHasher;
+use crate::starlark_simple_value;
+use crate::values::Heap;
+use crate::values::StarlarkValue;
+use crate::values::Value;
+
+#[derive(Debug, Display, ProvidesStaticType, NoSerialize, Allocative)]
+#[display("{}", name)]
+struct Color {
+    name: String,
+}
+
+starlark_simple_value!(Color);
+
+fn color_repr(color: &Color, collector: &mut String) {
+    write!(collector, "color({:?})", color.name).unwrap();
+}
+
+#[starlark_value(type = "color", repr = color_repr)]
+impl<'v> StarlarkValue<'v> for Color {}
+
+#[test]
+fn test_custom_repr() {
+    let mut a = Assert::new();
+    a.globals_add(|gb| {
+        gb.set(
+            "red",
+            Color {
+                name: "red".to_owned(),
+            },
+        )
+    });
+    a.eq("'red'", "str(red)");
+    a.eq("'color(\"red\")'", "repr(red)");
+    a.is_true("str(red) != repr(red)");
+    a.eq("'[color(\"red\")]'", "repr([red])");
+}
+
+#[test]
+fn test_custom_repr_in_format() {
+    let mut a = Assert::new();
+    a.globals_add(|gb| {
+        gb.set(
+            "red",
+            Color {
+                name: "red".to_owned(),
+            },
+        )
+    });
+    a.eq("'red color(\"red\")'", "'%s %r' % (red, red)");
+    a.eq("'red'", "'{}'.format(red)");
+}
+
+#[derive(Debug, Display, ProvidesStaticType, NoSerialize, Allocative)]
+#[display("rgb({}, {}, {})", r, g, b)]
+struct Rgb {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+
+starlark_simple_value!(Rgb);
+
+fn rgb_eq(x: &Rgb, y: &Rgb) -> bool {
+    (x.r, x.g, x.b) == (y.r, y.g, y.b)
+}
+
+#[starlark_value(type = "rgb", eq = rgb_eq)]
+impl<'v> StarlarkValue<'v> for Rgb {
+    fn write_hash(&self, hasher: &mut StarlarkHasher) -> crate::Result<()> {
+        (self.r, self.g, self.b).hash(hasher);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_custom_eq() {
+    let mut a = Assert::new();
+    a.globals_add(|gb| {
+        gb.set("red1", Rgb { r: 255, g: 0, b: 0 });
+        gb.set("red2", Rgb { r: 255, g: 0, b: 0 });
+        gb.set("blue", Rgb { r: 0, g: 0, b: 255 });
+    });
+    a.is_true("red1 == red2");
+    a.is_true("red1 != blue");
+    a.is_true("red1 != 'rgb(255, 0, 0)'");
+    a.is_true("[red1] == [red2]");
+}
+
+#[test]
+fn test_custom_eq_hash() {
+    let heap = Heap::new();
+    let red1 = heap.alloc_simple(Rgb { r: 255, g: 0, b: 0 });
+    let red2 = heap.alloc_simple(Rgb { r: 255, g: 0, b: 0 });
+    assert!(red1.equals(red2).unwrap());
+    assert_eq!(
+        red1.get_hashed().unwrap().hash(),
+        red2.get_hashed().unwrap().hash()
+    );
+
+    let mut a = Assert::new();
+    a.globals_add(|gb| {
+        gb.set("red1", Rgb { r: 255, g: 0, b: 0 });
+        gb.set("red2", Rgb { r: 255, g: 0, b: 0 });
+    });
+    a.eq("'red'", "{red1: 'red'}[red2]");
+    a.pass(
+        r#"
+d = {red1: 1}
+d[red2] = 2
+assert_eq({red1: 2}, d)
+"#,
+    );
+}
+
+#[derive(Debug, Display, ProvidesStaticType, NoSerialize, Allocative)]
+#[display("bag")]
+struct Bag {
+    items: Vec<i32>,
+}
+
+starlark_simple_value!(Bag);
+
+fn bag_iterate<'a, 'v: 'a>(bag: &'a Bag, heap: &'v Heap) -> impl Iterator<Item = Value<'v>> + 'a {
+    bag.items.iter().map(|x| heap.alloc(*x))
+}
+
+#[starlark_value(type = "bag", iterate = bag_iterate)]
+impl<'v> StarlarkValue<'v> for Bag {}
+
+#[test]
+fn test_custom_iterate() {
+    let mut a = Assert::new();
+    a.globals_add(|gb| {
+        gb.set(
+            "bag",
+            Bag {
+                items: vec![1, 2, 3, 4],
+            },
+        )
+    });
+    a.pass(
+        r#"
+def sum_bag(b):
+    total = 0
+    for x in b:
+        total += x
+    return total
+
+assert_eq(10, sum_bag(bag))
+assert_eq([1, 2, 3, 4], list(bag))
+assert_eq([2, 4], [x for x in bag if x % 2 == 0])
+"#,
+    );
+}
+
+#[derive(Debug, Display, ProvidesStaticType, NoSerialize, Allocative)]
+#[display("vec2({}, {})", x, y)]
+struct Vec2 {
+    x: i32,
+    y: i32,
+}
+
+starlark_simple_value!(Vec2);
+
+fn vec2_add<'v>(a: &Vec2, b: &Vec2, heap: &'v Heap) -> crate::Result<Value<'v>> {
+    Ok(heap.alloc(Vec2 {
+        x: a.x + b.x,
+        y: a.y + b.y,
+    }))
+}
+
+fn vec2_sub<'v>(a: &Vec2, b: &Vec2, heap: &'v Heap) -> crate::Result<Value<'v>> {
+    Ok(heap.alloc(Vec2 {
+        x: a.x - b.x,
+        y: a.y - b.y,
+    }))
+}
+
+#[starlark_value(type = "vec2", add = vec2_add, sub = vec2_sub)]
+impl<'v> StarlarkValue<'v> for Vec2 {}
+
+#[test]
+fn test_custom_bin_ops() {
+    let mut a = Assert::new();
+    a.globals_add(|gb| {
+        gb.set("v1", Vec2 { x: 1, y: 2 });
+        gb.set("v2", Vec2 { x: 10, y: 20 });
+    });
+    a.eq("'vec2(11, 22)'", "str(v1 + v2)");
+    a.eq("'vec2(9, 18)'", "str(v2 - v1)");
+    a.fail(
+        "noop(v1) + 1",
+        "Operation `+` not supported for types `vec2` and `int`",
+    );
+    a.fail(
+        "noop(v1) - 1",
+        "Operation `-` not supported for types `vec2` and `int`",
+    );
+    a.fail("noop(v1) * v2", "Operation `*` not supported");
+}
@@ -15,6 +15,7 @@
  * limitations under the License.
  */
 
+mod allocative_skip;
 mod bounds;
 mod enums;
 mod statics;
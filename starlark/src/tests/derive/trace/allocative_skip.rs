@@ -0,0 +1,46 @@
+/*
+ * Copyright 2018 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use allocative::Allocative;
+
+use crate as starlark;
+use crate::values::Heap;
+use crate::values::Trace;
+use crate::values::Value;
+
+#[derive(Trace, Allocative)]
+struct AllocativeSkip<'v> {
+    // Not measured, but still must be traced.
+    #[allocative(skip)]
+    value: Value<'v>,
+}
+
+#[test]
+fn test_allocative_skip_is_traced() {
+    let heap = Heap::new();
+    let mut x = AllocativeSkip {
+        value: heap.alloc("kept"),
+    };
+    for i in 0..100 {
+        heap.alloc(format!("garbage{i}"));
+    }
+    let before = heap.allocated_bytes();
+    // Safe: `x` holds the only value used after the GC.
+    unsafe { heap.garbage_collect(|tracer| x.trace(tracer)) };
+    assert!(heap.allocated_bytes() < before);
+    assert_eq!(x.value.unpack_str(), Some("kept"));
+}
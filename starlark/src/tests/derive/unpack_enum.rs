@@ -0,0 +1,87 @@
+/*
+ * Copyright 2018 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use either::Either;
+use starlark_derive::starlark_module;
+
+use crate as starlark;
+use crate::assert::Assert;
+use crate::environment::GlobalsBuilder;
+use crate::values::UnpackEnum;
+
+#[derive(UnpackEnum, Debug, PartialEq)]
+enum Mode {
+    Fast,
+    Slow,
+    ExtraCareful,
+    HTTPServer,
+    #[starlark(name = "YOLO")]
+    Reckless,
+}
+
+#[starlark_module]
+fn mode_functions(globals: &mut GlobalsBuilder) {
+    fn mode_round_trip(mode: Mode) -> anyhow::Result<Mode> {
+        Ok(mode)
+    }
+
+    fn mode_is_fast(mode: Mode) -> anyhow::Result<bool> {
+        Ok(mode == Mode::Fast)
+    }
+
+    fn mode_or_string(mode: Either<Mode, String>) -> anyhow::Result<String> {
+        Ok(match mode {
+            Either::Left(mode) => format!("{mode:?}"),
+            Either::Right(s) => format!("other: {s}"),
+        })
+    }
+}
+
+#[test]
+fn test_unpack_enum_round_trip() {
+    let mut a = Assert::new();
+    a.globals_add(mode_functions);
+    a.eq("'fast'", "mode_round_trip('fast')");
+    a.eq("'slow'", "mode_round_trip('slow')");
+    a.eq("'extra_careful'", "mode_round_trip('extra_careful')");
+    a.eq("'http_server'", "mode_round_trip('http_server')");
+    a.eq("'YOLO'", "mode_round_trip('YOLO')");
+    a.is_true("mode_is_fast('fast')");
+    a.is_true("not mode_is_fast('slow')");
+}
+
+#[test]
+fn test_unpack_enum_error() {
+    let mut a = Assert::new();
+    a.globals_add(mode_functions);
+    a.fail(
+        "noop(mode_round_trip)('Fast')",
+        "Type of parameter `mode` doesn't match, expected `str`, actual `string (repr: \"Fast\")`",
+    );
+    a.fail(
+        "noop(mode_round_trip)(1)",
+        "Type of parameter `mode` doesn't match, expected `str`",
+    );
+}
+
+#[test]
+fn test_unpack_enum_either() {
+    let mut a = Assert::new();
+    a.globals_add(mode_functions);
+    a.eq("'Slow'", "mode_or_string('slow')");
+    a.eq("'other: Fast'", "mode_or_string('Fast')");
+}
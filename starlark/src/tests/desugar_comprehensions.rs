@@ -0,0 +1,69 @@
+/*
+ * Copyright 2019 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use starlark_syntax::syntax::AstModule;
+use starlark_syntax::syntax::Dialect;
+
+use crate::environment::Globals;
+use crate::environment::Module;
+use crate::eval::Evaluator;
+
+const PROGRAM: &str = r#"
+x = "outer"
+xs = [[1, 2], [3], []]
+
+def nested(ys):
+    return [[y * k for y in ys if y != 2] for k in range(1, 3)]
+
+def keyed(zs):
+    return {z: [i for i in range(z)] for z in zs if z > 0}
+
+(
+    [x + 1 for x in [x for x in range(3)]],
+    [y for x in xs for y in x if y % 2 == 1],
+    nested([1, 2, 3]),
+    keyed([0, 1, 2]),
+    [(lambda v: [v for _ in range(2)])(x) for x in ["a"]],
+    x,
+)
+"#;
+
+fn eval(ast: AstModule) -> String {
+    let module = Module::new();
+    let mut eval = Evaluator::new(&module);
+    eval.eval_module(ast, &Globals::standard())
+        .unwrap()
+        .to_repr()
+}
+
+#[test]
+fn test_desugar_comprehensions() {
+    let parse = || AstModule::parse("file.star", PROGRAM.to_owned(), &Dialect::Extended).unwrap();
+
+    let mut desugared = parse();
+    desugared.desugar_comprehensions();
+    let unparsed = desugared.unparse();
+    assert!(unparsed.contains("def __comprehension_0("), "{unparsed}");
+    assert!(!unparsed.contains(" for x in [x for"), "{unparsed}");
+
+    let expected = eval(parse());
+    assert_eq!(
+        r#"([1, 2, 3], [1, 3], [[1, 3], [2, 6]], {1: [0], 2: [0, 1]}, [["a", "a"]], "outer")"#,
+        expected
+    );
+    assert_eq!(expected, eval(desugared));
+}
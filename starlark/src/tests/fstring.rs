@@ -100,6 +100,16 @@ f"{x}" == '("x",)'
         );
     }
 
+    #[test]
+    fn expression() {
+        assert().eq("'a3b'", "f'a{1+2}b'");
+        assert().eq("'a3b'", "f'a{ 1 + 2 }b'");
+        assert().eq("'[2]'", "x = [1, 2]; f'{x[1:]}'");
+        assert().eq("'\"k\" 3'", r#"d = {"k": 3}; f'{"k"!r} {d["k"]}'"#);
+        assert().eq("'{3}'", "f'{{{1+2}}}'");
+        assert().eq("'foo x'", "bar = {123: 'x'}; f'foo {bar[123]}'");
+    }
+
     #[test]
     fn conv() {
         assert().is_true(r#"x = 'a'; f"{x}" == 'a'"#);
@@ -140,11 +150,6 @@ mod fail {
         fstring_golden_test("invalid_identifier", "f'foo {bar baz}'");
     }
 
-    #[test]
-    fn invalid_identifier_expression() {
-        fstring_golden_test("invalid_identifier_expression", "f'foo {bar[123]}'");
-    }
-
     #[test]
     fn invalid_identifier_triple_quotes() {
         fstring_golden_test("invalid_identifier_triple_quotes", "f'''foo {bar baz}'''");
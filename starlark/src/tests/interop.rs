@@ -31,6 +31,7 @@ use crate::any::ProvidesStaticType;
 use crate::assert;
 use crate::assert::Assert;
 use crate::collections::SmallMap;
+use crate::environment::Globals;
 use crate::environment::GlobalsBuilder;
 use crate::environment::Module;
 use crate::eval::Evaluator;
@@ -260,3 +261,61 @@ fun
         .unwrap();
     assert_eq!(v.unpack_str(), Some("(8, \"hello\", 1)"))
 }
+
+#[test]
+fn test_eval_function_after_eval_module() {
+    let ast = AstModule::parse(
+        "test.star",
+        "def add(a, b):\n    return a + b\n".to_owned(),
+        &Dialect::Standard,
+    )
+    .unwrap();
+    let globals = Globals::standard();
+    let env = Module::new();
+    let mut eval = Evaluator::new(&env);
+    eval.eval_module(ast, &globals).unwrap();
+    let add = env.get("add").unwrap();
+    let v = eval
+        .eval_function(
+            add,
+            &[Value::testing_new_int(1)],
+            &[("b", Value::testing_new_int(2))],
+        )
+        .unwrap();
+    assert_eq!(v.unpack_i32(), Some(3));
+}
+
+#[test]
+fn test_extra_host_state() {
+    #[derive(Debug, ProvidesStaticType)]
+    struct Config {
+        name: String,
+    }
+
+    #[starlark_module]
+    fn config_globals(builder: &mut GlobalsBuilder) {
+        fn config_name(eval: &mut Evaluator) -> anyhow::Result<String> {
+            let config = eval
+                .extra
+                .and_then(|extra| extra.downcast_ref::<Config>())
+                .ok_or_else(|| anyhow::anyhow!("no config"))?;
+            Ok(config.name.clone())
+        }
+    }
+
+    let ast = AstModule::parse(
+        "test.star",
+        "name = config_name()".to_owned(),
+        &Dialect::Standard,
+    )
+    .unwrap();
+    let globals = GlobalsBuilder::new().with(config_globals).build();
+    let env = Module::new();
+    let config = Config {
+        name: "release".to_owned(),
+    };
+    let mut eval = Evaluator::new(&env);
+    eval.extra = Some(&config);
+    eval.eval_module(ast, &globals).unwrap();
+    assert_eq!(env.get("name").unwrap().unpack_str(), Some("release"));
+}
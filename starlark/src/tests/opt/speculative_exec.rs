@@ -53,3 +53,14 @@ def test(x):
 "#,
     );
 }
+
+#[test]
+fn test_builtins_taking_evaluator_invoked_speculatively() {
+    bc_golden_test(
+        "speculative_exec_builtins_taking_evaluator",
+        r#"
+def test():
+    return (max(1, 2), min("a", "b"), repr("x"), str(1.5))
+"#,
+    );
+}
@@ -29,8 +29,12 @@ use starlark_derive::starlark_module;
 use crate as starlark;
 use crate::assert;
 use crate::assert::Assert;
+use crate::environment::Globals;
 use crate::environment::GlobalsBuilder;
+use crate::environment::Module;
 use crate::eval::Evaluator;
+use crate::syntax::AstModule;
+use crate::syntax::Dialect;
 use crate::values::any::StarlarkAny;
 use crate::values::FrozenHeap;
 use crate::values::Heap;
@@ -47,6 +51,62 @@ assert_eq(y, str(x))
     );
 }
 
+#[test]
+fn test_garbage_collect_between_phases() {
+    let globals = Globals::standard();
+    let module = Module::new();
+    let mut eval = Evaluator::new(&module);
+    eval.disable_gc();
+
+    let phase1 = AstModule::parse(
+        "phase1.star",
+        "tmp = [str(i) for i in range(10000)]\nkeep = tmp[:10]".to_owned(),
+        &Dialect::Standard,
+    )
+    .unwrap();
+    eval.eval_module(phase1, &globals).unwrap();
+    let phase2 =
+        AstModule::parse("phase2.star", "tmp = None".to_owned(), &Dialect::Standard).unwrap();
+    eval.eval_module(phase2, &globals).unwrap();
+
+    let before = module.heap().allocated_bytes();
+    // Safe: we are between evaluations, so all live values are reachable from the module.
+    unsafe { eval.garbage_collect() };
+    let after = module.heap().allocated_bytes();
+    assert!(after * 10 < before, "before: {before}, after: {after}");
+    assert_eq!(
+        "[\"0\", \"1\", \"2\", \"3\", \"4\", \"5\", \"6\", \"7\", \"8\", \"9\"]",
+        module.get("keep").unwrap().to_repr()
+    );
+}
+
+#[test]
+fn test_with_gc_disabled() {
+    let globals = Globals::standard();
+    let module = Module::new();
+    let mut eval = Evaluator::new(&module);
+    let parse =
+        |code: &str| AstModule::parse("gc.star", code.to_owned(), &Dialect::Standard).unwrap();
+
+    eval.with_gc_disabled(|eval| {
+        // Without the pause, every top-level statement below would collect.
+        eval.trigger_gc();
+        eval.eval_module(
+            parse("tmp = [str(i) for i in range(10000)]\ntmp = None\nx = 1\ny = 2"),
+            &globals,
+        )
+        .unwrap();
+    });
+    let during = module.heap().allocated_bytes();
+
+    // The garbage is still there, and is collected once the pause is over.
+    eval.trigger_gc();
+    eval.eval_module(parse("z = 3\nw = 4"), &globals).unwrap();
+    let after = module.heap().allocated_bytes();
+    assert!(after * 10 < during, "during: {during}, after: {after}");
+    assert_eq!(module.get("y").unwrap().unpack_i32(), Some(2));
+}
+
 #[test]
 fn test_deallocation() {
     // Check that we really do deallocate values we create
@@ -233,3 +293,169 @@ fn test_display_debug() {
     assert_eq!(format!("{:?}", v), "FrozenValue(\"test\")");
     assert_eq!(format!("{:#?}", v), "FrozenValue(\n    \"test\",\n)");
 }
+
+fn eval_with_budget(program: &str, budget: impl FnOnce(&mut Evaluator)) -> crate::Error {
+    let ast = AstModule::parse("budget.star", program.to_owned(), &Dialect::Extended).unwrap();
+    let globals = Globals::standard();
+    let module = Module::new();
+    let mut eval = Evaluator::new(&module);
+    budget(&mut eval);
+    eval.eval_module(ast, &globals).unwrap_err()
+}
+
+#[test]
+fn test_max_steps() {
+    let err = eval_with_budget(
+        r#"
+def f():
+    x = 0
+    for i in range(1000000000):
+        x += i
+f()
+"#,
+        |eval| eval.set_max_steps(1000),
+    );
+    assert!(
+        err.to_string()
+            .contains("Evaluation exceeded the budget of 1000 steps"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_max_heap_bytes() {
+    let err = eval_with_budget(
+        r#"
+def f():
+    xs = []
+    for i in range(1000000000):
+        xs.append(str(i))
+f()
+"#,
+        |eval| eval.set_max_heap_bytes(1_000_000),
+    );
+    assert!(
+        err.to_string()
+            .contains("Evaluation exceeded the heap budget of 1000000 bytes"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_max_steps_comprehension() {
+    let err = eval_with_budget("[x for x in range(100000000)]", |eval| {
+        eval.set_max_steps(1000)
+    });
+    assert!(
+        err.to_string()
+            .contains("Evaluation exceeded the budget of 1000 steps"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_max_steps_precompiled() {
+    // Functions compiled before the budget is set are limited too.
+    let globals = Globals::standard();
+    let module = Module::new();
+    let mut eval = Evaluator::new(&module);
+    let ast = AstModule::parse(
+        "loop.star",
+        "def f():\n    for _ in range(100000000):\n        pass\n".to_owned(),
+        &Dialect::Standard,
+    )
+    .unwrap();
+    eval.eval_module(ast, &globals).unwrap();
+    eval.set_max_steps(1000);
+    let f = module.get("f").unwrap();
+    let err = eval.eval_function(f, &[], &[]).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Evaluation exceeded the budget of 1000 steps"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_max_heap_bytes_single_allocation() {
+    for program in ["x = [None] * 100000000", "x = 'x' * 1000000000"] {
+        let err = eval_with_budget(program, |eval| eval.set_max_heap_bytes(1_000_000));
+        assert!(
+            err.to_string()
+                .contains("Evaluation exceeded the heap budget of 1000000 bytes"),
+            "{}",
+            err
+        );
+    }
+}
+
+#[test]
+fn test_max_heap_bytes_native_call() {
+    // Reported when the call returns, even if no loop follows.
+    let err = eval_with_budget("x = list(range(1000000))", |eval| {
+        eval.set_max_heap_bytes(1_000_000)
+    });
+    assert!(
+        err.to_string()
+            .contains("Evaluation exceeded the heap budget of 1000000 bytes"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_max_callstack_size() {
+    let program = r#"
+def rec(n):
+    return 0 if n == 0 else 1 + rec(n - 1)
+"#;
+    let globals = Globals::standard();
+    let module = Module::new();
+    let mut eval = Evaluator::new(&module);
+    eval.set_max_callstack_size(20).unwrap();
+    let ast = AstModule::parse("rec.star", program.to_owned(), &Dialect::Standard).unwrap();
+    eval.eval_module(ast, &globals).unwrap();
+    let rec = module.get("rec").unwrap();
+    let heap = module.heap();
+    assert_eq!(
+        eval.eval_function(rec, &[heap.alloc(10)], &[])
+            .unwrap()
+            .unpack_i32(),
+        Some(10)
+    );
+    let err = eval
+        .eval_function(rec, &[heap.alloc(100)], &[])
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("Starlark call stack overflow"),
+        "{}",
+        err
+    );
+    // The evaluator is still usable after the error.
+    assert_eq!(
+        eval.eval_function(rec, &[heap.alloc(5)], &[])
+            .unwrap()
+            .unpack_i32(),
+        Some(5)
+    );
+}
+
+#[test]
+fn test_budget_not_exceeded() {
+    let ast = AstModule::parse(
+        "budget.star",
+        "x = [i for i in range(10)]".to_owned(),
+        &Dialect::Standard,
+    )
+    .unwrap();
+    let globals = Globals::standard();
+    let module = Module::new();
+    let mut eval = Evaluator::new(&module);
+    eval.set_max_steps(100);
+    eval.set_max_heap_bytes(1_000_000);
+    eval.eval_module(ast, &globals).unwrap();
+}
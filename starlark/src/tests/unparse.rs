@@ -0,0 +1,73 @@
+/*
+ * Copyright 2019 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tests that [`AstModule::unparse`] produces a program with the same behaviour.
+
+use crate::assert;
+use crate::syntax::AstModule;
+use crate::syntax::Dialect;
+
+/// Evaluate `program` and its unparsed form, and check both produce the same value.
+fn assert_unparse_same_result(program: &str) {
+    let unparsed = AstModule::parse(
+        "unparse.star",
+        program.to_owned(),
+        &Dialect::AllOptionsInternal,
+    )
+    .unwrap()
+    .unparse();
+    let expected = assert::pass(program);
+    let actual = assert::pass(&unparsed);
+    assert_eq!(
+        expected.value().to_repr(),
+        actual.value().to_repr(),
+        "unparsed program:\n{unparsed}"
+    );
+}
+
+#[test]
+fn test_unparse_same_result() {
+    assert_unparse_same_result(
+        r#"
+def f(a, b = 1.0, *args, c: int = -3, **kwargs) -> list:
+    """Docs."""
+    r = []
+    if a and not b:
+        return ["x\n"]
+    elif a[1:] or a[::2]:
+        pass
+    else:
+        for i, j in kwargs.items():
+            c += len(i) * (j - 1) // 2
+            if c > 10:
+                break
+    r.append(c)
+    r.append([k for k in a if k] + [v for (k, v) in {"p": 1}.items()])
+    r.append((lambda q: q * 2)(b))
+    r.append(-2 * 3 if args else 1 - (2 - 3))
+    r.append(not a == b)
+    return r
+
+[f([0, 1, 2], x = 3, y = 4), f([], 2.5, 1), (1,) + ((2,) if True else {"a": [1, 2]})]
+"#,
+    );
+}
+
+#[test]
+fn test_unparse_float_literals() {
+    assert_unparse_same_result("[1.0, 1e10, 1e300, 1e999, -1e999, 0.5]");
+}
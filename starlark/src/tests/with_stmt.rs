@@ -0,0 +1,182 @@
+/*
+ * Copyright 2019 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tests for the `with` statement.
+
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use allocative::Allocative;
+use derive_more::Display;
+use starlark_derive::starlark_module;
+use starlark_derive::starlark_value;
+use starlark_derive::NoSerialize;
+
+use crate as starlark;
+use crate::any::ProvidesStaticType;
+use crate::environment::Globals;
+use crate::environment::Methods;
+use crate::environment::MethodsBuilder;
+use crate::environment::MethodsStatic;
+use crate::environment::Module;
+use crate::eval::Evaluator;
+use crate::starlark_simple_value;
+use crate::syntax::AstModule;
+use crate::syntax::Dialect;
+use crate::values::none::NoneType;
+use crate::values::StarlarkValue;
+use crate::values::ValueLike;
+
+/// Context manager counting `__enter__` and `__exit__` calls.
+#[derive(Debug, Display, Default, ProvidesStaticType, NoSerialize, Allocative)]
+#[display("resource")]
+struct Resource {
+    #[allocative(skip)]
+    entered: AtomicUsize,
+    #[allocative(skip)]
+    exited: AtomicUsize,
+}
+
+starlark_simple_value!(Resource);
+
+#[starlark_module]
+fn resource_methods(builder: &mut MethodsBuilder) {
+    fn __enter__(this: &Resource) -> anyhow::Result<i32> {
+        Ok(this.entered.fetch_add(1, Ordering::SeqCst) as i32 + 1)
+    }
+
+    fn __exit__(this: &Resource) -> anyhow::Result<NoneType> {
+        this.exited.fetch_add(1, Ordering::SeqCst);
+        Ok(NoneType)
+    }
+}
+
+#[starlark_value(type = "resource")]
+impl<'v> StarlarkValue<'v> for Resource {
+    fn get_methods() -> Option<&'static Methods> {
+        static RES: MethodsStatic = MethodsStatic::new();
+        RES.methods(resource_methods)
+    }
+}
+
+/// Run the program with `r` bound to a fresh `Resource`,
+/// return the error if any, and the number of enters and exits.
+fn run(program: &str) -> (Option<crate::Error>, usize, usize) {
+    let module = Module::new();
+    let r = module.heap().alloc(Resource::default());
+    module.set("r", r);
+    let ast = AstModule::parse(
+        "with.star",
+        program.to_owned(),
+        &Dialect::AllOptionsInternal,
+    )
+    .unwrap();
+    let mut eval = Evaluator::new(&module);
+    let err = eval.eval_module(ast, &Globals::standard()).err();
+    let r = r.downcast_ref::<Resource>().unwrap();
+    (
+        err,
+        r.entered.load(Ordering::SeqCst),
+        r.exited.load(Ordering::SeqCst),
+    )
+}
+
+fn run_pass(program: &str) -> (usize, usize) {
+    let (err, entered, exited) = run(program);
+    if let Some(err) = err {
+        panic!("{}", err);
+    }
+    (entered, exited)
+}
+
+#[test]
+fn test_with_normal() {
+    let program = r#"
+with r as x:
+    if x != 1:
+        fail(x)
+with r as x:
+    if x != 2:
+        fail(x)
+"#;
+    assert_eq!((2, 2), run_pass(program));
+}
+
+#[test]
+fn test_with_error() {
+    let program = r#"
+def f():
+    with r:
+        fail("oops")
+f()
+"#;
+    let (err, entered, exited) = run(program);
+    assert!(err.unwrap().to_string().contains("oops"));
+    assert_eq!((1, 1), (entered, exited));
+}
+
+#[test]
+fn test_with_error_in_callee() {
+    let program = r#"
+def g():
+    with r:
+        return 1 // 0
+def f():
+    with r:
+        g()
+f()
+"#;
+    let (err, entered, exited) = run(program);
+    assert!(err.is_some());
+    assert_eq!((2, 2), (entered, exited));
+}
+
+#[test]
+fn test_with_return() {
+    let program = r#"
+def f():
+    with r as x:
+        with r as y:
+            return x + y
+if f() != 3:
+    fail(f)
+"#;
+    assert_eq!((2, 2), run_pass(program));
+}
+
+#[test]
+fn test_with_break_continue() {
+    let program = r#"
+def f():
+    with r:
+        for i in range(10):
+            with r:
+                if i == 1:
+                    continue
+                if i == 2:
+                    break
+f()
+"#;
+    assert_eq!((4, 4), run_pass(program));
+}
+
+#[test]
+fn test_with_no_enter() {
+    let (err, entered, exited) = run("with 1:\n    pass\n");
+    assert!(err.unwrap().to_string().contains("__enter__"));
+    assert_eq!((0, 0), (entered, exited));
+}
@@ -35,6 +35,8 @@ pub(crate) mod error;
 pub(crate) mod fill_types_for_lint;
 pub(crate) mod function;
 pub(crate) mod interface;
+pub(crate) mod literal;
+pub(crate) mod members;
 pub(crate) mod mode;
 pub(crate) mod oracle;
 pub(crate) mod small_arc_vec;
@@ -57,6 +59,8 @@ pub use callable_param::ParamIsRequired;
 pub use callable_param::ParamSpec;
 pub use function::TyFunction;
 pub use interface::Interface;
+pub use members::members_of;
+pub use members::UnionMembers;
 pub use oracle::ctx::TypingOracleCtx;
 pub use oracle::traits::TypingBinOp;
 pub use oracle::traits::TypingUnOp;
@@ -15,6 +15,7 @@
  * limitations under the License.
  */
 
+use std::cmp::Ordering;
 use std::fmt;
 use std::fmt::Display;
 use std::fmt::Formatter;
@@ -27,7 +28,7 @@ use dupe::Dupe;
 use crate::typing::ty::TypeRenderConfig;
 use crate::typing::Ty;
 
-#[derive(Dupe, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Allocative)]
+#[derive(Dupe, Clone, Eq, PartialEq, Hash, Debug, Allocative)]
 enum ArcTyInner {
     // These are shortcuts to avoid allocations for common cases.
     Any,
@@ -60,8 +61,6 @@ impl Display for ArcTyInner {
     Clone,
     Eq,
     PartialEq,
-    Ord,
-    PartialOrd,
     Hash,
     derive_more::Display,
     Debug,
@@ -69,6 +68,19 @@ impl Display for ArcTyInner {
 )]
 pub struct ArcTy(ArcTyInner);
 
+impl PartialOrd for ArcTy {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ArcTy {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Same order as `Ty`, not the order of shortcuts.
+        Ty::cmp(self, other)
+    }
+}
+
 impl ArcTy {
     pub(crate) fn any() -> ArcTy {
         ArcTy(ArcTyInner::Any)
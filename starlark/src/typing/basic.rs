@@ -36,6 +36,11 @@ use crate::values::typing::any::TypingAny;
 use crate::values::StarlarkValue;
 
 /// Type that is not a union.
+///
+/// Types are ordered by variant in declaration order: `Any`, then simple types
+/// (ordered by type name, e.g. `bool` before `int`), then `Iter`, `Callable` and `type`,
+/// then containers, then custom types (ordered by implementation, then by value).
+/// Unions are sorted with this order, so it is also the order in which they are printed.
 #[derive(Eq, PartialEq, Hash, Clone, Dupe, Debug, Ord, PartialOrd, Allocative)]
 pub enum TyBasic {
     /// Type that contain anything
@@ -55,10 +60,10 @@ pub enum TyBasic {
     Tuple(TyTuple),
     /// A dictionary, with key and value types
     Dict(ArcTy, ArcTy),
-    /// Custom type.
-    Custom(TyCustom),
     /// A set.
     Set(ArcTy),
+    /// Custom type.
+    Custom(TyCustom),
 }
 
 impl TyBasic {
@@ -22,6 +22,8 @@ use starlark_map::small_map::SmallMap;
 use starlark_syntax::syntax::ast::AssignOp;
 use starlark_syntax::syntax::ast::AssignP;
 use starlark_syntax::syntax::ast::AssignTargetP;
+use starlark_syntax::syntax::ast::AstLiteral;
+use starlark_syntax::syntax::ast::BinOp;
 use starlark_syntax::syntax::ast::ClauseP;
 use starlark_syntax::syntax::ast::DefP;
 use starlark_syntax::syntax::ast::ExprP;
@@ -29,6 +31,7 @@ use starlark_syntax::syntax::ast::ForClauseP;
 use starlark_syntax::syntax::ast::ForP;
 use starlark_syntax::syntax::ast::IdentP;
 use starlark_syntax::syntax::ast::StmtP;
+use starlark_syntax::syntax::ast::WithP;
 use starlark_syntax::syntax::def::DefParamKind;
 use starlark_syntax::syntax::def::DefParams;
 use starlark_syntax::syntax::def::DefRegularParamMode;
@@ -62,6 +65,8 @@ pub(crate) enum BindExpr<'a> {
     /// Get this position from the expression
     GetIndex(usize, Box<BindExpr<'a>>),
     Iter(Box<BindExpr<'a>>),
+    /// The result of calling `__enter__` on the expression.
+    Enter(&'a CstExpr),
     AssignModify(&'a CstAssignTarget, AssignOp, &'a CstExpr),
     /// Set this index in the variable
     SetIndex(BindingId, &'a CstExpr, Box<BindExpr<'a>>),
@@ -75,6 +80,7 @@ impl<'a> BindExpr<'a> {
             BindExpr::Expr(x) => x.span,
             BindExpr::GetIndex(_, x) => x.span(),
             BindExpr::Iter(x) => x.span(),
+            BindExpr::Enter(x) => x.span,
             BindExpr::AssignModify(x, _, _) => x.span,
             BindExpr::SetIndex(_, x, _) => x.span,
             BindExpr::ListAppend(_, x) => x.span,
@@ -98,6 +104,19 @@ pub(crate) struct Bindings<'a> {
     /// ```
     pub(crate) check: Vec<&'a CstExpr>,
     pub(crate) check_type: Vec<(Span, Option<&'a CstExpr>, Ty)>,
+    /// Narrowings of variable types by `if x == "lit"` conditions.
+    pub(crate) narrowings: Vec<Narrowing>,
+}
+
+/// Within `span`, the variable `binding` is known to be equal to `value` if `equal`,
+/// or known to be not equal to it otherwise.
+///
+/// Created for the branches of `if x == "lit"` or `if x != "lit"` statements.
+pub(crate) struct Narrowing {
+    pub(crate) span: Span,
+    pub(crate) binding: BindingId,
+    pub(crate) value: String,
+    pub(crate) equal: bool,
 }
 
 pub(crate) struct BindingsCollect<'a, 'b> {
@@ -175,6 +194,35 @@ impl<'a, 'b> BindingsCollect<'a, 'b> {
         Ok(())
     }
 
+    /// Record narrowings of the `if` branch `then` and the `else` branch `els`
+    /// if `cond` compares a variable to a string literal.
+    fn narrow(&mut self, cond: &CstExpr, then: Span, els: Option<Span>) {
+        let ExprP::Op(lhs, op @ (BinOp::Equal | BinOp::NotEqual), rhs) = &**cond else {
+            return;
+        };
+        let (ident, value) = match (&lhs.node, &rhs.node) {
+            (ExprP::Identifier(ident), ExprP::Literal(AstLiteral::String(value)))
+            | (ExprP::Literal(AstLiteral::String(value)), ExprP::Identifier(ident)) => {
+                (ident, value)
+            }
+            _ => return,
+        };
+        let Some(ResolvedIdent::Slot(_, binding)) = ident.node.payload else {
+            return;
+        };
+        let equal = *op == BinOp::Equal;
+        for (span, equal) in [(Some(then), equal), (els, !equal)] {
+            if let Some(span) = span {
+                self.bindings.narrowings.push(Narrowing {
+                    span,
+                    binding,
+                    value: value.node.clone(),
+                    equal,
+                });
+            }
+        }
+    }
+
     /// Type must be populated earlier.
     fn resolved_ty(
         expr: &CstTypeExpr,
@@ -318,6 +366,14 @@ impl<'a, 'b> BindingsCollect<'a, 'b> {
                 StmtP::For(ForP { var, over, body: _ }) => {
                     self.assign(var, BindExpr::Iter(Box::new(BindExpr::Expr(over))), codemap)?
                 }
+                StmtP::With(WithP {
+                    expr,
+                    target,
+                    body: _,
+                }) => match target {
+                    Some(target) => self.assign(target, BindExpr::Enter(expr), codemap)?,
+                    None => self.bindings.check.push(expr),
+                },
                 StmtP::Def(def) => {
                     self.visit_def(def, typecheck_mode, codemap)?;
                     // We do our own visit_children, with a different return type
@@ -360,8 +416,14 @@ impl<'a, 'b> BindingsCollect<'a, 'b> {
 
                     self.bindings.check.push(x)
                 }
-                StmtP::If(x, _) => self.bindings.check.push(x),
-                StmtP::IfElse(x, _) => self.bindings.check.push(x),
+                StmtP::If(x, body) => {
+                    self.narrow(x, body.span, None);
+                    self.bindings.check.push(x)
+                }
+                StmtP::IfElse(x, bodies) => {
+                    self.narrow(x, bodies.0.span, Some(bodies.1.span));
+                    self.bindings.check.push(x)
+                }
                 _ => {}
             },
             Visit::Expr(x) => match &**x {
@@ -19,6 +19,7 @@ use std::cell::RefCell;
 use std::fmt::Debug;
 
 use starlark_map::unordered_map::UnorderedMap;
+use starlark_syntax::lexer::TokenInt;
 use starlark_syntax::slice_vec_ext::SliceExt;
 use starlark_syntax::syntax::ast::AssignOp;
 use starlark_syntax::syntax::ast::AssignTargetP;
@@ -41,14 +42,17 @@ use crate::eval::compiler::scope::ResolvedIdent;
 use crate::eval::compiler::scope::Slot;
 use crate::typing::basic::TyBasic;
 use crate::typing::bindings::BindExpr;
+use crate::typing::bindings::Narrowing;
 use crate::typing::call_args::TyCallArgs;
 use crate::typing::error::InternalError;
 use crate::typing::error::TypingError;
 use crate::typing::error::TypingOrInternalError;
 use crate::typing::fill_types_for_lint::ModuleVarTypes;
+use crate::typing::literal::TyLiteral;
 use crate::typing::oracle::ctx::TypingOracleCtx;
 use crate::typing::oracle::traits::TypingBinOp;
 use crate::typing::oracle::traits::TypingUnOp;
+use crate::typing::tuple::TyTuple;
 use crate::typing::ty::Approximation;
 use crate::typing::ty::Ty;
 
@@ -60,6 +64,7 @@ pub(crate) struct TypingContext<'a> {
     pub(crate) approximoations: RefCell<Vec<Approximation>>,
     pub(crate) types: UnorderedMap<BindingId, Ty>,
     pub(crate) module_var_types: &'a ModuleVarTypes,
+    pub(crate) narrowings: Vec<Narrowing>,
 }
 
 impl TypingContext<'_> {
@@ -145,10 +150,37 @@ impl TypingContext<'_> {
             }
         }
 
+        // `t[0]` where `t: tuple[int, str]` is `int`, not `int | str`.
+        if let ExprP::Literal(AstLiteral::Int(i)) = &index.node {
+            if let TokenInt::I32(i) = i.node {
+                if let Some(ty) = Self::tuple_elem(&array_ty, i) {
+                    return Ok(ty);
+                }
+            }
+        }
+
         let index = self.expression_type_spanned(index)?;
         self.result_to_ty_with_internal_error(self.oracle.expr_index(span, array_ty, index))
     }
 
+    /// Type of the element `i` of a fixed size tuple, if `ty` is only such tuples
+    /// and `i` is in range for all of them.
+    fn tuple_elem(ty: &Ty, i: i32) -> Option<Ty> {
+        let mut res = Vec::new();
+        for x in ty.iter_union() {
+            let TyBasic::Tuple(TyTuple::Elems(elems)) = x else {
+                return None;
+            };
+            let i = if i < 0 {
+                elems.len().checked_sub(i.unsigned_abs() as usize)?
+            } else {
+                i as usize
+            };
+            res.push(elems.get(i)?.clone());
+        }
+        Some(Ty::unions(res))
+    }
+
     fn expression_un_op(
         &self,
         span: Span,
@@ -164,6 +196,16 @@ impl TypingContext<'_> {
             BindExpr::Expr(x) => self.expression_type(x),
             BindExpr::GetIndex(i, x) => Ok(self.oracle.indexed(&self.expression_bind_type(x)?, *i)),
             BindExpr::Iter(x) => Ok(self.from_iterated(&self.expression_bind_type(x)?, x.span())),
+            BindExpr::Enter(x) => {
+                let enter = self.expr_dot(&self.expression_type(x)?, "__enter__", x.span);
+                let args = TyCallArgs {
+                    pos: Vec::new(),
+                    named: Vec::new(),
+                    args: None,
+                    kwargs: None,
+                };
+                self.validate_call(&enter, &args, x.span)
+            }
             BindExpr::AssignModify(lhs, op, rhs) => {
                 let span = lhs.span;
                 let rhs = self.expression_type_spanned(rhs)?;
@@ -314,11 +356,14 @@ impl TypingContext<'_> {
             star_star,
         } = args;
 
+        let f_ty = self.expression_type(f)?;
+        let literal_params = Self::has_literal_params(&f_ty);
+
         let mut pos_ty: Vec<Spanned<Ty>> = Vec::new();
         for pos in pos {
             pos_ty.push(Spanned {
                 span: pos.span,
-                node: self.expression_type(&pos.node.expr())?,
+                node: self.call_arg_type(&pos.node.expr(), literal_params)?,
             });
         }
 
@@ -333,7 +378,10 @@ impl TypingContext<'_> {
             };
             named_ty.push(Spanned {
                 span: named.span,
-                node: (name, self.expression_type(&named.node.expr())?),
+                node: (
+                    name,
+                    self.call_arg_type(&named.node.expr(), literal_params)?,
+                ),
             });
         }
 
@@ -360,12 +408,41 @@ impl TypingContext<'_> {
             kwargs: kwargs_ty,
         };
 
-        let f_ty = self.expression_type(f)?;
         // If we can't resolve the types of the arguments, we can't validate the call,
         // but we still know the type of the result since the args don't impact that
         self.validate_call(&f_ty, &args_ty, span)
     }
 
+    /// Does the function have a parameter which accepts `typing.Literal[...]`?
+    fn has_literal_params(f_ty: &Ty) -> bool {
+        f_ty.iter_union().iter().any(|f| {
+            let callable = match f {
+                TyBasic::Callable(c) => c,
+                f => match f.as_function() {
+                    Some(f) => &f.callable,
+                    None => return false,
+                },
+            };
+            callable.params().params().iter().any(|p| {
+                p.ty.iter_union()
+                    .iter()
+                    .any(|t| matches!(t, TyBasic::Custom(c) if c.0.as_any().is::<TyLiteral>()))
+            })
+        })
+    }
+
+    /// Type of call argument.
+    /// String literals are typed by their value when passed to `typing.Literal` parameters.
+    fn call_arg_type(&self, expr: &CstExpr, literal_params: bool) -> Result<Ty, InternalError> {
+        let ty = self.expression_type(expr)?;
+        match &expr.node {
+            ExprP::Literal(AstLiteral::String(s)) if literal_params => {
+                Ok(Ty::custom(TyLiteral::new([s.node.clone()])))
+            }
+            _ => Ok(ty),
+        }
+    }
+
     fn expr_slice(
         &self,
         span: Span,
@@ -390,7 +467,7 @@ impl TypingContext<'_> {
                 .unwrap_or_else(Ty::any),
             Some(ResolvedIdent::Slot(_, i)) => {
                 if let Some(ty) = self.types.get(i) {
-                    ty.clone()
+                    self.narrow(*i, x.span, ty.clone())
                 } else {
                     // All types must be resolved to this point,
                     // this code is unreachable.
@@ -407,6 +484,41 @@ impl TypingContext<'_> {
         }
     }
 
+    /// Apply narrowings of the `if` branches enclosing `span` to the type of the variable.
+    ///
+    /// For example, in `if mode == "fast": ...` the type `typing.Literal["fast", "slow"]`
+    /// of `mode` is `typing.Literal["fast"]`, and `typing.Literal["slow"]` in `else`.
+    fn narrow(&self, binding: BindingId, span: Span, mut ty: Ty) -> Ty {
+        for n in &self.narrowings {
+            if n.binding != binding || !n.span.contains(span.begin()) {
+                continue;
+            }
+            ty = Ty::unions(
+                ty.iter_union()
+                    .iter()
+                    .filter_map(|t| {
+                        let literal = match t {
+                            TyBasic::Custom(c) => c.0.as_any().downcast_ref::<TyLiteral>(),
+                            _ => None,
+                        };
+                        let Some(literal) = literal else {
+                            return Some(Ty::basic(t.clone()));
+                        };
+                        let literal = TyLiteral::new(
+                            literal
+                                .values
+                                .iter()
+                                .filter(|v| (**v == n.value) == n.equal)
+                                .cloned(),
+                        );
+                        (!literal.values.is_empty()).then(|| Ty::custom(literal))
+                    })
+                    .collect(),
+            );
+        }
+        ty
+    }
+
     pub(crate) fn expression_type(&self, x: &CstExpr) -> Result<Ty, InternalError> {
         let span = x.span;
         match &**x {
@@ -421,6 +533,14 @@ impl TypingContext<'_> {
                 self.expression_type(i1)?;
                 Ok(Ty::any())
             }
+            ExprP::IndexN(a_is) => {
+                let (a, is) = &**a_is;
+                self.expression_type(a)?;
+                for i in is {
+                    self.expression_type(i)?;
+                }
+                Ok(Ty::any())
+            }
             ExprP::Slice(x, start, stop, stride) => self.expr_slice(
                 span,
                 x,
@@ -471,6 +591,10 @@ impl TypingContext<'_> {
                     .unzip();
                 Ok(Ty::dict(Ty::unions(ks), Ty::unions(vs)))
             }
+            ExprP::Set(xs) => {
+                let ts = xs.try_map(|x| self.expression_type(x))?;
+                Ok(Ty::set(Ty::unions(ts)))
+            }
             ExprP::ListComprehension(a, b, c) => {
                 self.check_comprehension(b, c)?;
                 Ok(Ty::list(self.expression_type(a)?))
@@ -33,6 +33,7 @@ use starlark_syntax::syntax::ast::ForP;
 use starlark_syntax::syntax::ast::LoadArgP;
 use starlark_syntax::syntax::ast::LoadP;
 use starlark_syntax::syntax::ast::StmtP;
+use starlark_syntax::syntax::ast::WithP;
 use starlark_syntax::syntax::def::DefParamKind;
 use starlark_syntax::syntax::def::DefParams;
 use starlark_syntax::syntax::def::DefRegularParamMode;
@@ -56,6 +57,7 @@ use crate::eval::compiler::scope::Slot;
 use crate::typing::callable_param::ParamIsRequired;
 use crate::typing::error::InternalError;
 use crate::typing::error::TypingError;
+use crate::typing::literal::TyLiteral;
 use crate::typing::Approximation;
 use crate::typing::ParamSpec;
 use crate::typing::Ty;
@@ -267,6 +269,8 @@ impl<'a, 'v> GlobalTypesBuilder<'a, 'v> {
                 let (a, i0, i1) = &**a_i0_i1;
                 self.index2(span, a, i0, i1)
             }
+            // Only allowed in type expressions.
+            ExprP::IndexN(..) => Ok(GlobalValue::any()),
             ExprP::Identifier(ident) => self.expr_ident(ident),
             ExprP::Literal(lit) => self.expr_literal(lit),
             ExprP::Op(lhs, op, rhs) => self.bin_op(span, lhs, *op, rhs),
@@ -280,6 +284,7 @@ impl<'a, 'v> GlobalTypesBuilder<'a, 'v> {
             | ExprP::If(..)
             | ExprP::List(_)
             | ExprP::Dict(_)
+            | ExprP::Set(_)
             | ExprP::ListComprehension(_, _, _)
             | ExprP::DictComprehension(_, _, _)
             | ExprP::FString(_) => Ok(GlobalValue::any()),
@@ -410,6 +415,18 @@ impl<'a, 'v> GlobalTypesBuilder<'a, 'v> {
         self.eval_stmt_unset(body)
     }
 
+    fn with_stmt_unset(&mut self, with_stmt: &WithP<CstPayload>) -> Result<(), InternalError> {
+        let WithP {
+            expr: _,
+            target,
+            body,
+        } = with_stmt;
+        if let Some(target) = target {
+            self.assign_unset(target)?;
+        }
+        self.eval_stmt_unset(body)
+    }
+
     /// When we are not sure if code is executed exactly once (like in a for loop body),
     /// we just reset all the variables.
     fn eval_stmt_unset(&mut self, stmt: &CstStmt) -> Result<(), InternalError> {
@@ -435,6 +452,7 @@ impl<'a, 'v> GlobalTypesBuilder<'a, 'v> {
                 Ok(())
             }
             StmtP::For(for_stmt) => self.for_stmt_unset(for_stmt),
+            StmtP::With(with_stmt) => self.with_stmt_unset(with_stmt),
             StmtP::Def(def) => self.assign_unset_ident(&def.name),
             StmtP::Load(_) => Err(self.internal_error(stmt.span, "load")),
         }
@@ -506,6 +524,7 @@ impl<'a, 'v> GlobalTypesBuilder<'a, 'v> {
                 Ok(())
             }
             StmtP::For(for_stmt) => self.for_stmt_unset(for_stmt),
+            StmtP::With(with_stmt) => self.with_stmt_unset(with_stmt),
             StmtP::Def(def) => self.top_level_def(def),
             StmtP::Load(load) => self.load(load),
         }
@@ -566,6 +585,26 @@ impl<'a, 'v> GlobalTypesBuilder<'a, 'v> {
         Ok(self.unknown_ty(span))
     }
 
+    /// Type of `typing.Literal[...]` with the given arguments.
+    fn literal_ty(
+        &mut self,
+        items: &[&Spanned<TypeExprUnpackP<CstPayload>>],
+        x: &Spanned<TypeExprUnpackP<CstPayload>>,
+    ) -> Ty {
+        let mut values = Vec::with_capacity(items.len());
+        for item in items {
+            let TypeExprUnpackP::Literal(s) = item.node else {
+                self.approximations.push(Approximation::new(
+                    "Expecting string literal in typing.Literal[...]",
+                    x,
+                ));
+                return Ty::any();
+            };
+            values.push(s.to_owned());
+        }
+        Ty::custom(TyLiteral::new(values))
+    }
+
     fn from_type_expr_impl(
         &mut self,
         x: &Spanned<TypeExprUnpackP<CstPayload>>,
@@ -583,6 +622,13 @@ impl<'a, 'v> GlobalTypesBuilder<'a, 'v> {
                 ));
                 Ok(Ty::any())
             }
+            TypeExprUnpackP::Literal(..) => {
+                self.approximations.push(Approximation::new(
+                    "String literal cannot be used as type",
+                    x,
+                ));
+                Ok(Ty::any())
+            }
             TypeExprUnpackP::Tuple(xs) => {
                 Ok(Ty::tuple(xs.try_map(|x| self.from_type_expr_impl(x))?))
             }
@@ -591,7 +637,10 @@ impl<'a, 'v> GlobalTypesBuilder<'a, 'v> {
             }
             TypeExprUnpackP::Path(path) => self.path_ty(path),
             TypeExprUnpackP::Index(a, i) => {
-                if let Some(a) = self.expr_ident(a)?.value {
+                if let Some(a) = self.eval_path(a)? {
+                    if a.ptr_eq(Constants::get().typing_literal.0.to_value()) {
+                        return Ok(self.literal_ty(&[i], x));
+                    }
                     if !a.ptr_eq(Constants::get().fn_list.0.to_value()) {
                         self.approximations.push(Approximation::new("Not list", x));
                         return Ok(Ty::any());
@@ -622,7 +671,9 @@ impl<'a, 'v> GlobalTypesBuilder<'a, 'v> {
             }
             TypeExprUnpackP::Index2(a, i0, i1) => {
                 if let Some(a) = self.eval_path(a)? {
-                    if a.ptr_eq(Constants::get().fn_dict.0.to_value()) {
+                    if a.ptr_eq(Constants::get().typing_literal.0.to_value()) {
+                        Ok(self.literal_ty(&[i0, i1], x))
+                    } else if a.ptr_eq(Constants::get().fn_dict.0.to_value()) {
                         let i0 = self.from_type_expr_impl(i0)?;
                         let i1 = self.from_type_expr_impl(i1)?;
                         let i0 = TypeCompiled::from_ty(&i0, self.heap);
@@ -644,17 +695,15 @@ impl<'a, 'v> GlobalTypesBuilder<'a, 'v> {
                         }
                     } else if a.ptr_eq(Constants::get().fn_tuple.0.to_value()) {
                         let i0 = self.from_type_expr_impl(i0)?;
-                        let TypeExprUnpackP::Ellipsis = i1.node else {
-                            self.approximations
-                                .push(Approximation::new("Expecting ellipsis in tuple[x, ...]", x));
-                            return Ok(Ty::any());
-                        };
                         let r0 = TypeCompiled::from_ty(&i0, self.heap);
-                        match a.get_ref().at2(
-                            r0.to_inner(),
-                            Ellipsis::new_value().to_value(),
-                            self.heap,
-                        ) {
+                        let r1 = match i1.node {
+                            TypeExprUnpackP::Ellipsis => Ellipsis::new_value().to_value(),
+                            _ => {
+                                let i1 = self.from_type_expr_impl(i1)?;
+                                TypeCompiled::from_ty(&i1, self.heap).to_inner()
+                            }
+                        };
+                        match a.get_ref().at2(r0.to_inner(), r1, self.heap) {
                             Ok(t) => match TypeCompiled::new(t, self.heap) {
                                 Ok(ty) => Ok(ty.as_ty().clone()),
                                 Err(_) => {
@@ -712,6 +761,23 @@ impl<'a, 'v> GlobalTypesBuilder<'a, 'v> {
                     Ok(Ty::any())
                 }
             }
+            TypeExprUnpackP::IndexN(a, is) => {
+                if let Some(a) = self.eval_path(a)? {
+                    if a.ptr_eq(Constants::get().fn_tuple.0.to_value()) {
+                        Ok(Ty::tuple(is.try_map(|i| self.from_type_expr_impl(i))?))
+                    } else if a.ptr_eq(Constants::get().typing_literal.0.to_value()) {
+                        Ok(self.literal_ty(&is.iter().collect::<Vec<_>>(), x))
+                    } else {
+                        self.approximations
+                            .push(Approximation::new("Not tuple or typing.Literal", x));
+                        Ok(Ty::any())
+                    }
+                } else {
+                    self.approximations
+                        .push(Approximation::new("Not global", x));
+                    Ok(Ty::any())
+                }
+            }
         }
     }
 
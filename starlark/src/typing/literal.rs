@@ -0,0 +1,135 @@
+/*
+ * Copyright 2019 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use allocative::Allocative;
+use starlark_map::sorted_set::SortedSet;
+
+use crate::typing::custom::TyCustomImpl;
+use crate::typing::error::TypingNoContextError;
+use crate::typing::error::TypingNoContextOrInternalError;
+use crate::typing::Ty;
+use crate::typing::TyBasic;
+use crate::typing::TyStarlarkValue;
+use crate::typing::TypingBinOp;
+use crate::typing::TypingOracleCtx;
+use crate::values::string::repr::string_repr;
+use crate::values::string::StarlarkStr;
+use crate::values::typing::type_compiled::alloc::TypeMatcherAlloc;
+use crate::values::typing::type_compiled::matcher::TypeMatcher;
+use crate::values::Value;
+
+/// `typing.Literal["a", "b"]`: a string equal to one of the given values.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Allocative)]
+pub(crate) struct TyLiteral {
+    pub(crate) values: SortedSet<String>,
+}
+
+impl TyLiteral {
+    pub(crate) fn new(values: impl IntoIterator<Item = String>) -> TyLiteral {
+        TyLiteral {
+            values: values.into_iter().collect(),
+        }
+    }
+
+    fn str() -> TyStarlarkValue {
+        TyStarlarkValue::new::<StarlarkStr>()
+    }
+}
+
+impl TyCustomImpl for TyLiteral {
+    fn as_name(&self) -> Option<&str> {
+        Some(Self::str().as_name())
+    }
+
+    fn bin_op(
+        &self,
+        bin_op: TypingBinOp,
+        rhs: &TyBasic,
+        _ctx: &TypingOracleCtx,
+    ) -> Result<Ty, TypingNoContextOrInternalError> {
+        Ok(Self::str().bin_op(bin_op, rhs)?)
+    }
+
+    fn iter_item(&self) -> Result<Ty, TypingNoContextError> {
+        Self::str().iter_item()
+    }
+
+    fn index(
+        &self,
+        item: &TyBasic,
+        _ctx: &TypingOracleCtx,
+    ) -> Result<Ty, TypingNoContextOrInternalError> {
+        Ok(Self::str().index(item)?)
+    }
+
+    fn attribute(&self, attr: &str) -> Result<Ty, TypingNoContextError> {
+        Self::str().attr(attr)
+    }
+
+    fn union2(a: Arc<Self>, b: Arc<Self>) -> Result<Arc<Self>, (Arc<Self>, Arc<Self>)> {
+        if a == b {
+            // Fast path.
+            Ok(a)
+        } else {
+            Ok(Arc::new(TyLiteral {
+                values: a.values.union(&b.values).cloned().collect(),
+            }))
+        }
+    }
+
+    fn intersects(x: &Self, y: &Self) -> bool {
+        x.values.iter().any(|v| y.values.contains(v))
+    }
+
+    fn intersects_with(&self, other: &TyBasic) -> bool {
+        *other == TyBasic::string()
+    }
+
+    fn matcher<T: TypeMatcherAlloc>(&self, factory: T) -> T::Result {
+        #[derive(Allocative, Debug, Clone)]
+        struct LiteralMatcher(SortedSet<String>);
+
+        impl TypeMatcher for LiteralMatcher {
+            fn matches(&self, value: Value) -> bool {
+                match value.unpack_str() {
+                    Some(s) => self.0.contains(s),
+                    None => false,
+                }
+            }
+        }
+
+        factory.alloc(LiteralMatcher(self.values.clone()))
+    }
+}
+
+impl Display for TyLiteral {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut values = String::new();
+        for (i, v) in self.values.iter().enumerate() {
+            if i != 0 {
+                values.push_str(", ");
+            }
+            string_repr(v, &mut values);
+        }
+        write!(f, "typing.Literal[{}]", values)
+    }
+}
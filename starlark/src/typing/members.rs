@@ -0,0 +1,112 @@
+/*
+ * Copyright 2019 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::BTreeSet;
+
+use crate::codemap::CodeMap;
+use crate::codemap::Span;
+use crate::typing::starlark_value::TyStarlarkValue;
+use crate::typing::Ty;
+use crate::typing::TyBasic;
+use crate::typing::TypingOracleCtx;
+use crate::values::dict::value::MutableDict;
+use crate::values::list::value::List;
+use crate::values::set::value::MutableSet;
+
+/// Which members of a union [`members_of`] returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnionMembers {
+    /// Members available in every alternative, which are safe to use.
+    Common,
+    /// Members available in at least one alternative.
+    All,
+}
+
+/// Names of the attributes of a type, or `None` if they are not known statically.
+fn member_names(ty: &TyBasic) -> Option<Vec<String>> {
+    match ty {
+        TyBasic::StarlarkValue(s) => Some(s.member_names()),
+        TyBasic::List(_) => Some(TyStarlarkValue::new::<List>().member_names()),
+        TyBasic::Dict(..) => Some(TyStarlarkValue::new::<MutableDict>().member_names()),
+        TyBasic::Set(_) => Some(TyStarlarkValue::new::<MutableSet>().member_names()),
+        TyBasic::Tuple(_) => Some(Vec::new()),
+        TyBasic::Any
+        | TyBasic::Callable(_)
+        | TyBasic::Iter(_)
+        | TyBasic::Type
+        | TyBasic::Custom(_) => None,
+    }
+}
+
+/// Attributes available on values of a type, with their types, sorted by name.
+///
+/// For a union, the type of an attribute is the union of its types in the alternatives.
+/// Alternatives whose attributes are not known statically, like `typing.Any`, contribute nothing.
+pub fn members_of(ty: &Ty, union: UnionMembers) -> Vec<(String, Ty)> {
+    let mut names: Option<BTreeSet<String>> = None;
+    for basic in ty.iter_union() {
+        let Some(xs) = member_names(basic) else {
+            continue;
+        };
+        names = Some(match (names, union) {
+            (None, _) => xs.into_iter().collect(),
+            (Some(mut names), UnionMembers::All) => {
+                names.extend(xs);
+                names
+            }
+            (Some(names), UnionMembers::Common) => {
+                let xs: BTreeSet<String> = xs.into_iter().collect();
+                names.intersection(&xs).cloned().collect()
+            }
+        });
+    }
+    let oracle = TypingOracleCtx {
+        codemap: CodeMap::empty_static(),
+    };
+    names
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|name| {
+            let ty = oracle.expr_dot(Span::default(), ty, &name).ok()?;
+            Some((name, ty))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::typing::members::members_of;
+    use crate::typing::members::UnionMembers;
+    use crate::typing::Ty;
+
+    #[test]
+    fn test_members_of_union() {
+        let int_or_str = Ty::union2(Ty::int(), Ty::string());
+        // `int` has no methods, so nothing is safe to complete.
+        assert!(members_of(&int_or_str, UnionMembers::Common).is_empty());
+        let all = members_of(&int_or_str, UnionMembers::All);
+        assert_eq!(all, members_of(&Ty::string(), UnionMembers::Common));
+        assert!(all.iter().any(|(name, _)| name == "startswith"));
+
+        let str_or_list = Ty::union2(Ty::string(), Ty::list(Ty::int()));
+        let common = members_of(&str_or_list, UnionMembers::Common);
+        assert_eq!(
+            common.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>(),
+            &["index"]
+        );
+    }
+}
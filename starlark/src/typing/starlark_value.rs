@@ -265,6 +265,14 @@ impl TyStarlarkValue {
         Err(TypingNoContextError)
     }
 
+    /// Names of the members registered in the methods of this type.
+    pub(crate) fn member_names(self) -> Vec<String> {
+        match (self.vtable.vtable.get_methods)() {
+            Some(methods) => methods.names(),
+            None => Vec::new(),
+        }
+    }
+
     pub(crate) fn attr(self, name: &str) -> Result<Ty, TypingNoContextError> {
         if let Ok(ty) = self.attr_from_methods(name) {
             return Ok(ty);
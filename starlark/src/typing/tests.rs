@@ -49,6 +49,7 @@ use crate::values::ValueOfUnchecked;
 mod call;
 mod callable;
 mod list;
+mod literal;
 mod special_function;
 mod tuple;
 mod types;
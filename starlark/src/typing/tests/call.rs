@@ -142,3 +142,18 @@ def test():
 "#,
     );
 }
+
+#[test]
+fn test_callable_call_arg_type() {
+    TypeCheck::new().check(
+        "callable_call_arg_type",
+        r#"
+def apply(f: typing.Callable[[int, str], bool]) -> bool:
+    # Good.
+    f(1, "x")
+
+    # Bad.
+    return f("x", 1)
+"#,
+    );
+}
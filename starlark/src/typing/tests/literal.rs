@@ -0,0 +1,97 @@
+/*
+ * Copyright 2019 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::typing::tests::TypeCheck;
+
+#[test]
+fn test_literal() {
+    TypeCheck::new().check(
+        "literal",
+        r#"
+def f(mode: typing.Literal["fast", "slow"]) -> str:
+    return mode
+
+def g():
+    # Good.
+    f("fast")
+    f(mode = "slow")
+
+    # Bad.
+    f("x")
+"#,
+    );
+}
+
+#[test]
+fn test_literal_many() {
+    TypeCheck::new().check(
+        "literal_many",
+        r#"
+def f(mode: typing.Literal["fast", "slow", "default"]) -> str:
+    return mode
+
+def g():
+    # Good.
+    f("default")
+
+    # Bad.
+    f("x")
+"#,
+    );
+}
+
+#[test]
+fn test_literal_narrowing() {
+    TypeCheck::new().check(
+        "literal_narrowing",
+        r#"
+def fast_only(mode: typing.Literal["fast"]):
+    pass
+
+def slow_only(mode: typing.Literal["slow"]):
+    pass
+
+def f(mode: typing.Literal["fast", "slow"]):
+    if mode == "fast":
+        # Good.
+        fast_only(mode)
+    else:
+        slow_only(mode)
+        # Bad: `mode` is `"slow"` here.
+        fast_only(mode)
+
+def g(mode: typing.Literal["fast", "slow"]):
+    if "fast" != mode:
+        # Bad: `mode` is `"slow"` here.
+        fast_only(mode)
+"#,
+    );
+}
+
+#[test]
+fn test_literal_as_str() {
+    TypeCheck::new().check(
+        "literal_as_str",
+        r#"
+def f(mode: typing.Literal["fast", "slow", "default"]) -> str:
+    return "mode_" + mode.upper() + "_" + mode[0]
+
+def g(mode: typing.Literal["fast", "slow"]) -> int:
+    return mode
+"#,
+    );
+}
@@ -45,3 +45,43 @@ def g():
 "#,
     );
 }
+
+#[test]
+fn test_tuple_elems() {
+    TypeCheck::new().check(
+        "tuple_elems",
+        r#"
+def f(t: tuple[int, str]) -> str:
+    return t[1]
+
+def g(t: tuple[int, str]) -> str:
+    # Bad: element 0 is `int`.
+    return t[0]
+
+def h():
+    # Good.
+    f((1, "x"))
+
+    # Bad.
+    f(("x", 1))
+"#,
+    );
+}
+
+#[test]
+fn test_tuple_three_elems() {
+    TypeCheck::new().check(
+        "tuple_three_elems",
+        r#"
+def f(t: tuple[int, str, bool]) -> bool:
+    return t[2]
+
+def g():
+    # Good.
+    f((1, "x", True))
+
+    # Bad.
+    f((1, "x", 2))
+"#,
+    );
+}
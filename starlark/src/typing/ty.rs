@@ -49,8 +49,13 @@ use crate::typing::tuple::TyTuple;
 use crate::typing::ParamSpec;
 use crate::typing::TypingOracleCtx;
 use crate::values::bool::StarlarkBool;
+use crate::values::dict::DictRef;
+use crate::values::list::ListRef;
+use crate::values::set::refs::SetRef;
+use crate::values::tuple::TupleRef;
 use crate::values::typing::never::TypingNever;
 use crate::values::StarlarkValue;
+use crate::values::UnpackValue;
 use crate::values::Value;
 
 /// A typing operation wasn't able to produce a precise result,
@@ -80,6 +85,8 @@ impl Display for Approximation {
 }
 
 /// A Starlark type.
+///
+/// Types are ordered by their alternatives, which are [ordered](TyBasic) and sorted.
 #[derive(
     Debug, Clone, Dupe, PartialEq, Eq, Hash, PartialOrd, Ord, Allocative, Trace
 )]
@@ -433,6 +440,71 @@ impl Ty {
         }
     }
 
+    /// Typechecker type of value, with element types of lists, tuples, dicts and sets
+    /// computed from their contents, e.g. `list[int | str]` for `[1, "a"]`.
+    ///
+    /// Element types of empty containers are [`Ty::any`].
+    /// Other values are typed with [`Ty::of_value`].
+    ///
+    /// Element types of large lists, dicts and sets are computed from a sample of
+    /// their first elements. Containers which contain themselves, or which are nested
+    /// beyond a total budget of examined values, are typed as [`Ty::any`] at that point.
+    pub fn from_value(value: Value) -> Ty {
+        /// Number of elements examined in each list, dict or set.
+        const SAMPLE: usize = 100;
+
+        struct FromValue<'v> {
+            /// Containers being typed, to detect cycles.
+            stack: Vec<Value<'v>>,
+            /// Number of values which can still be examined.
+            budget: usize,
+        }
+
+        impl<'v> FromValue<'v> {
+            fn elems(&mut self, values: impl IntoIterator<Item = Value<'v>>) -> Ty {
+                let tys: Vec<Ty> = values
+                    .into_iter()
+                    .take(SAMPLE)
+                    .map(|x| self.ty(x))
+                    .collect();
+                if tys.is_empty() {
+                    Ty::any()
+                } else {
+                    Ty::unions(tys)
+                }
+            }
+
+            fn ty(&mut self, value: Value<'v>) -> Ty {
+                if self.budget == 0 || self.stack.iter().any(|x| x.ptr_eq(value)) {
+                    return Ty::any();
+                }
+                self.budget -= 1;
+                self.stack.push(value);
+                let ty = if let Some(list) = ListRef::from_value(value) {
+                    Ty::list(self.elems(list.iter()))
+                } else if let Some(tuple) = TupleRef::from_value(value) {
+                    Ty::tuple(tuple.iter().map(|x| self.ty(x)).collect())
+                } else if let Some(dict) = DictRef::from_value(value) {
+                    let k = self.elems(dict.keys());
+                    let v = self.elems(dict.values());
+                    Ty::dict(k, v)
+                } else if let Some(set) = SetRef::unpack_value_opt(value) {
+                    Ty::set(self.elems(set.aref.content.iter().copied()))
+                } else {
+                    Ty::of_value(value)
+                };
+                self.stack.pop();
+                ty
+            }
+        }
+
+        FromValue {
+            stack: Vec::new(),
+            budget: 10_000,
+        }
+        .ty(value)
+    }
+
     /// Check if the value of this type can be called with given arguments and expected return type.
     #[must_use]
     pub(crate) fn check_call<'a>(
@@ -557,3 +629,87 @@ impl Display for Ty {
         self.fmt_with_config(f, &TypeRenderConfig::Default)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::assert;
+    use crate::typing::Ty;
+    use crate::typing::TyStruct;
+
+    fn from_value(program: &str) -> String {
+        Ty::from_value(assert::pass(program).value()).to_string()
+    }
+
+    #[test]
+    fn test_from_value() {
+        assert_eq!("list[int | str]", from_value("[1, 'a']"));
+        assert_eq!("list", from_value("[]"));
+        assert_eq!("(int, list[bool])", from_value("(1, [True])"));
+        assert_eq!("dict[str, None | list[int]]", from_value("{'a': [1], 'b': None}"));
+        assert_eq!("set[int]", from_value("set([1, 2])"));
+        assert_eq!("int", from_value("1"));
+    }
+
+    #[test]
+    fn test_from_value_cycle() {
+        assert_eq!("list", from_value("l = []; l.append(l); l"));
+        assert_eq!("dict[str, list]", from_value("d = {}; d['a'] = [1, d]; d"));
+    }
+
+    #[test]
+    fn test_from_value_large() {
+        // Only the first elements are examined.
+        assert_eq!("list[int]", from_value("list(range(1000)) + ['a']"));
+        // Repeated references to the same list do not cause exponential work.
+        assert_eq!(
+            "list",
+            from_value("l = [1]\nfor _ in range(20):\n    l = [l, l]\nl")
+        );
+    }
+
+    #[test]
+    fn test_ord() {
+        let mut tys = vec![
+            Ty::custom(TyStruct::any()),
+            Ty::set(Ty::int()),
+            Ty::dict(Ty::string(), Ty::int()),
+            Ty::list(Ty::string()),
+            Ty::list(Ty::int()),
+            Ty::tuple(vec![Ty::int()]),
+            Ty::union2(Ty::string(), Ty::int()),
+            Ty::string(),
+            Ty::none(),
+            Ty::bool(),
+            Ty::int(),
+            Ty::any(),
+        ];
+        tys.sort();
+        assert_eq!(
+            vec![
+                "typing.Any",
+                "None",
+                "bool",
+                "int",
+                "int | str",
+                "str",
+                "list[int]",
+                "list[str]",
+                "(int,)",
+                "dict[str, int]",
+                "set[int]",
+                "struct(..)",
+            ],
+            tys.iter().map(|t| t.to_string()).collect::<Vec<_>>()
+        );
+
+        // Unions do not depend on the order of alternatives.
+        let mut alternatives = tys.split_off(1);
+        let union = Ty::unions(alternatives.clone());
+        alternatives.reverse();
+        assert_eq!(union, Ty::unions(alternatives));
+        assert_eq!(
+            "None | bool | int | str | list[int | str] | (int,) | dict[str, int] | set[int] | struct(..)",
+            union.to_string()
+        );
+    }
+}
@@ -77,6 +77,12 @@ pub(crate) fn solve_bindings(
         approximoations: RefCell::new(Vec::new()),
         types,
         module_var_types,
+        // Narrowing is not sound if the variable is assigned, so only narrow parameters.
+        narrowings: bindings
+            .narrowings
+            .into_iter()
+            .filter(|n| !bindings.expressions.contains_key(&n.binding))
+            .collect(),
     };
     const ITERATIONS: usize = 100;
     for _iteration in 0..ITERATIONS {
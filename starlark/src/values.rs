@@ -41,6 +41,7 @@ pub use starlark_derive::Freeze;
 pub use starlark_derive::NoSerialize;
 pub use starlark_derive::StarlarkAttrs;
 pub use starlark_derive::Trace;
+pub use starlark_derive::UnpackEnum;
 pub use starlark_derive::UnpackValue;
 
 pub use crate::any::AnyLifetime;
@@ -71,6 +72,9 @@ pub use crate::values::layout::value::FrozenValue;
 pub use crate::values::layout::value::Value;
 pub use crate::values::layout::value::ValueLike;
 pub use crate::values::layout::value_lifetimeless::ValueLifetimeless;
+pub use crate::values::members::members;
+pub use crate::values::members::MemberInfo;
+pub use crate::values::members::MemberKind;
 pub use crate::values::owned::OwnedFrozenValue;
 pub use crate::values::owned::OwnedFrozenValueTyped;
 pub use crate::values::trace::Trace;
@@ -108,6 +112,7 @@ pub use crate::values::value_of_unchecked::ValueOfUncheckedGeneric;
 
 mod alloc_value;
 mod comparison;
+mod deep_freeze;
 pub(crate) mod demand;
 pub(crate) mod error;
 mod freeze;
@@ -115,9 +120,11 @@ pub(crate) mod frozen_ref;
 mod index;
 pub(crate) mod iter;
 pub(crate) mod layout;
+mod members;
 mod owned;
 pub(crate) mod owned_frozen_ref;
 pub(crate) mod recursive_repr_or_json_guard;
+mod repr_truncated;
 mod stack_guard;
 pub(crate) mod starlark_type_id;
 mod trace;
@@ -125,7 +132,7 @@ pub(crate) mod traits;
 pub mod type_repr;
 pub(crate) mod types;
 pub mod typing;
-mod unpack;
+pub(crate) mod unpack;
 mod unpack_and_discard;
 pub(crate) mod value_of;
 pub(crate) mod value_of_unchecked;
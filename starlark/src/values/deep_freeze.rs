@@ -0,0 +1,309 @@
+/*
+ * Copyright 2018 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Copy a mutable value tree into a frozen heap without freezing the source heap.
+
+use std::mem::MaybeUninit;
+
+use starlark_map::small_map::SmallMap;
+use starlark_map::small_set::SmallSet;
+
+use crate::collections::Hashed;
+use crate::values::dict::DictRef;
+use crate::values::float::StarlarkFloat;
+use crate::values::layout::avalue::AValue;
+use crate::values::layout::avalue::AValueFrozenList;
+use crate::values::layout::avalue::AValueFrozenTuple;
+use crate::values::layout::avalue::AValueSimple;
+use crate::values::layout::heap::arena::Reservation;
+use crate::values::layout::identity::ValueIdentity;
+use crate::values::list::ListRef;
+use crate::values::set::refs::SetRef;
+use crate::values::structs::value::FrozenStruct;
+use crate::values::structs::StructRef;
+use crate::values::tuple::TupleRef;
+use crate::values::types::dict::value::DictGen;
+use crate::values::types::dict::value::FrozenDict;
+use crate::values::types::dict::value::FrozenDictData;
+use crate::values::types::int::int_or_big::StarlarkIntRef;
+use crate::values::types::list::value::FrozenListData;
+use crate::values::types::list::value::ListGen;
+use crate::values::types::set::value::FrozenSet;
+use crate::values::types::set::value::FrozenSetData;
+use crate::values::types::set::value::SetGen;
+use crate::values::types::tuple::value::FrozenTuple;
+use crate::values::Freezer;
+use crate::values::FrozenHeap;
+use crate::values::FrozenValue;
+use crate::values::UnpackValue;
+use crate::values::Value;
+use crate::values::ValueLike;
+
+#[derive(Debug, thiserror::Error)]
+enum DeepFreezeError {
+    #[error("Cannot deep-freeze value of type `{0}`")]
+    Unsupported(&'static str),
+}
+
+struct DeepFreezer<'v, 'h> {
+    heap: &'h FrozenHeap,
+    /// Copies of the values visited so far, keyed by the source value pointer.
+    /// Containers are recorded before their elements are copied,
+    /// so shared and cyclic references resolve to the same copy.
+    copies: SmallMap<ValueIdentity<'v>, FrozenValue>,
+}
+
+impl<'v, 'h> DeepFreezer<'v, 'h> {
+    fn copy(&mut self, value: Value<'v>) -> anyhow::Result<FrozenValue> {
+        // Frozen values are copied too, because they may live in a heap which
+        // is not kept alive by the copy, like the frozen heap of a module.
+        // Only `None` and booleans are static and can be shared.
+        if let Some(frozen) = value.unpack_frozen() {
+            if value.is_none() || value.unpack_bool().is_some() {
+                return Ok(frozen);
+            }
+        }
+        if let Some(copy) = self.copies.get(&value.identity()) {
+            return Ok(*copy);
+        }
+        let copy = self.copy_impl(value)?;
+        self.copies.insert(value.identity(), copy);
+        Ok(copy)
+    }
+
+    /// Reserve the copy of `value`, so it can be referenced before it is filled.
+    fn reserve<T: AValue<'static>>(
+        &mut self,
+        value: Value<'v>,
+        extra_len: usize,
+    ) -> (
+        FrozenValue,
+        Reservation<'static, T>,
+        &'h mut [MaybeUninit<T::ExtraElem>],
+    ) {
+        let (copy, r, extra) = self.heap.reserve_with_extra::<T>(extra_len);
+        self.copies.insert(value.identity(), copy);
+        (copy, r, unsafe { &mut *extra })
+    }
+
+    /// Copy `items` into the extra elements of a list or a tuple,
+    /// which have been initialized, so the container is valid while the elements are copied.
+    fn copy_items(
+        &mut self,
+        extra: &mut [MaybeUninit<FrozenValue>],
+        items: &[Value<'v>],
+    ) -> anyhow::Result<()> {
+        for place in extra.iter_mut() {
+            place.write(FrozenValue::new_none());
+        }
+        for (place, item) in extra.iter_mut().zip(items) {
+            place.write(self.copy(*item)?);
+        }
+        Ok(())
+    }
+
+    fn copy_impl(&mut self, value: Value<'v>) -> anyhow::Result<FrozenValue> {
+        if let Some(s) = value.unpack_str() {
+            Ok(self.heap.alloc_str(s).to_frozen_value())
+        } else if let Some(i) = StarlarkIntRef::unpack(value) {
+            Ok(self.heap.alloc(i.to_owned()))
+        } else if let Some(f) = value.downcast_ref::<StarlarkFloat>() {
+            Ok(self.heap.alloc(f.0))
+        } else if let Some(list) = ListRef::from_value(value) {
+            if list.is_empty() {
+                return Ok(FrozenValue::new_empty_list());
+            }
+            let (copy, r, extra) = self.reserve::<AValueFrozenList>(value, list.len());
+            r.fill(ListGen(unsafe { FrozenListData::new(list.len()) }));
+            self.copy_items(extra, list.content())?;
+            Ok(copy)
+        } else if let Some(tuple) = TupleRef::from_value(value) {
+            if tuple.len() == 0 {
+                return Ok(FrozenValue::new_empty_tuple());
+            }
+            let (copy, r, extra) = self.reserve::<AValueFrozenTuple>(value, tuple.len());
+            r.fill(unsafe { FrozenTuple::new(tuple.len()) });
+            self.copy_items(extra, tuple.content())?;
+            Ok(copy)
+        } else if let Some(dict) = DictRef::from_value(value) {
+            let (copy, r, _) = self.reserve::<AValueSimple<FrozenDict>>(value, 0);
+            let mut content = SmallMap::with_capacity(dict.len());
+            for (k, v) in dict.iter_hashed() {
+                // Frozen values hash the same as their mutable originals.
+                let k = Hashed::new_unchecked(k.hash(), self.copy(*k.key())?);
+                content.insert_hashed_unique_unchecked(k, self.copy(v)?);
+            }
+            r.fill(DictGen(FrozenDictData { content }));
+            Ok(copy)
+        } else if let Some(set) = SetRef::unpack_value_opt(value) {
+            let set = &*set.aref;
+            let (copy, r, _) = self.reserve::<AValueSimple<FrozenSet>>(value, 0);
+            let mut content = SmallSet::with_capacity(set.iter().len());
+            for x in set.iter_hashed() {
+                let x = Hashed::new_unchecked(x.hash(), self.copy(*x.key())?);
+                content.insert_hashed_unique_unchecked(x);
+            }
+            r.fill(SetGen(FrozenSetData { content }));
+            Ok(copy)
+        } else if let Some(s) = StructRef::from_value(value) {
+            let (copy, r, _) = self.reserve::<AValueSimple<FrozenStruct>>(value, 0);
+            let mut fields = SmallMap::with_capacity(s.iter().len());
+            for (k, v) in s.iter() {
+                fields.insert(self.heap.alloc_str(k.as_str()), self.copy(v)?);
+            }
+            r.fill(FrozenStruct::new(fields));
+            Ok(copy)
+        } else {
+            Err(DeepFreezeError::Unsupported(value.get_type()).into())
+        }
+    }
+}
+
+impl<'v> Value<'v> {
+    /// Copy this value into the heap of `freezer`,
+    /// producing a frozen value independent of the original.
+    ///
+    /// Unlike [`Freezer::freeze`], the source value is not modified,
+    /// so it can be mutated afterwards without affecting the copy.
+    /// Values reachable more than once are copied once, and the copies are shared,
+    /// so the copy has the same structure as the original, including cycles.
+    /// Already frozen values are copied too, so the copy only references values
+    /// allocated by `freezer`.
+    ///
+    /// Supports `None`, booleans, strings, numbers, lists, tuples, dicts, sets and structs.
+    /// Returns an error for other types.
+    pub fn deep_freeze_clone(self, freezer: &Freezer) -> anyhow::Result<FrozenValue> {
+        self.deep_freeze_clone_to_heap(freezer.frozen_heap())
+    }
+
+    /// Like [`deep_freeze_clone`](Value::deep_freeze_clone), but copy into `heap`.
+    pub(crate) fn deep_freeze_clone_to_heap(
+        self,
+        heap: &FrozenHeap,
+    ) -> anyhow::Result<FrozenValue> {
+        DeepFreezer {
+            heap,
+            copies: SmallMap::new(),
+        }
+        .copy(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::environment::Globals;
+    use crate::environment::Module;
+    use crate::eval::Evaluator;
+    use crate::syntax::AstModule;
+    use crate::syntax::Dialect;
+    use crate::values::list::AllocList;
+    use crate::values::list::ListRef;
+    use crate::values::types::list::value::ListData;
+    use crate::values::Freezer;
+    use crate::values::FrozenHeap;
+    use crate::values::Heap;
+    use crate::values::Value;
+
+    #[test]
+    fn test_deep_freeze_clone_independent() {
+        let heap = Heap::new();
+        let inner = heap.alloc(AllocList([1, 2]));
+        let outer = heap.alloc(AllocList([inner, heap.alloc("x"), inner]));
+
+        let freezer = Freezer::new(FrozenHeap::new());
+        let frozen = outer.deep_freeze_clone(&freezer).unwrap();
+
+        ListData::from_value_mut(inner)
+            .unwrap()
+            .push(Value::testing_new_int(3), &heap);
+
+        assert_eq!("[[1, 2, 3], \"x\", [1, 2, 3]]", outer.to_repr());
+        assert_eq!("[[1, 2], \"x\", [1, 2]]", frozen.to_value().to_repr());
+        let items = ListRef::from_frozen_value(frozen).unwrap();
+        assert!(items[0].ptr_eq(items[2]));
+    }
+
+    #[test]
+    fn test_deep_freeze_clone_cycle() {
+        let heap = Heap::new();
+        let list = heap.alloc(AllocList::EMPTY);
+        ListData::from_value_mut(list).unwrap().push(list, &heap);
+
+        let freezer = Freezer::new(FrozenHeap::new());
+        let frozen = list.deep_freeze_clone(&freezer).unwrap();
+        let items = ListRef::from_frozen_value(frozen).unwrap();
+        assert_eq!(1, items.len());
+        assert!(items[0].ptr_eq(frozen.to_value()));
+        assert_eq!("[[...]]", frozen.to_value().to_repr());
+    }
+
+    #[test]
+    fn test_deep_freeze_clone_types() {
+        let module = Module::new();
+        let mut eval = Evaluator::new(&module);
+        let ast = AstModule::parse(
+            "x.star",
+            r#"
+l = []
+d = {"l": l, "s": set([1, "x"]), "t": (l, 2.5), "st": struct(l = l, n = None)}
+l.append(d)
+"#
+            .to_owned(),
+            &Dialect::Extended,
+        )
+        .unwrap();
+        eval.eval_module(ast, &Globals::extended_internal()).unwrap();
+        let heap = module.heap();
+        let d = module.get("d").unwrap();
+
+        let freezer = Freezer::new(FrozenHeap::new());
+        let frozen = d.deep_freeze_clone(&freezer).unwrap().to_value();
+        assert_eq!(d.to_repr(), frozen.to_repr());
+        let l = frozen.at(heap.alloc("l"), heap).unwrap();
+        let t = frozen.at(heap.alloc("t"), heap).unwrap();
+        assert!(l.ptr_eq(t.at(heap.alloc(0), heap).unwrap()));
+        assert!(frozen.ptr_eq(l.at(heap.alloc(0), heap).unwrap()));
+    }
+
+    #[test]
+    fn test_deep_freeze_clone_outlives_module() {
+        let freezer = Freezer::new(FrozenHeap::new());
+        let frozen = {
+            let module = Module::new();
+            let mut eval = Evaluator::new(&module);
+            let ast = AstModule::parse(
+                "x.star",
+                "xs = ['literal', 1.5, (2, 'x')]".to_owned(),
+                &Dialect::Standard,
+            )
+            .unwrap();
+            eval.eval_module(ast, &Globals::standard()).unwrap();
+            // The string literals are allocated in the frozen heap of the module.
+            module
+                .get("xs")
+                .unwrap()
+                .deep_freeze_clone(&freezer)
+                .unwrap()
+        };
+        let heap = freezer.into_ref();
+        assert_eq!(
+            "[\"literal\", 1.5, (2, \"x\")]",
+            frozen.to_value().to_repr()
+        );
+        drop(heap);
+    }
+}
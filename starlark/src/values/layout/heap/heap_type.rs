@@ -31,6 +31,7 @@ use std::marker::PhantomData;
 use std::mem;
 use std::mem::MaybeUninit;
 use std::ops::Deref;
+use std::path::Path;
 use std::ptr;
 use std::slice;
 use std::sync::Arc;
@@ -83,6 +84,7 @@ use crate::values::list::value::VALUE_EMPTY_FROZEN_LIST;
 use crate::values::string::intern::interner::FrozenStringValueInterner;
 use crate::values::string::intern::interner::StringValueInterner;
 use crate::values::string::str_type::StarlarkStr;
+use crate::values::types::path::PathNotUtf8;
 use crate::values::AllocFrozenValue;
 use crate::values::AllocValue;
 use crate::values::ComplexValue;
@@ -98,6 +100,10 @@ use crate::values::ValueOf;
 use crate::values::ValueOfUnchecked;
 use crate::values::ValueTyped;
 
+#[derive(Debug, thiserror::Error)]
+#[error("Evaluation exceeded the heap budget of {0} bytes (allocated {1} bytes)")]
+struct HeapBudgetExceeded(usize, usize);
+
 #[derive(Copy, Clone, Dupe)]
 pub(crate) enum HeapKind {
     Unfrozen,
@@ -111,6 +117,10 @@ pub struct Heap {
     peak_allocated: Cell<usize>,
     arena: FastCell<Arena<Bump>>,
     str_interner: RefCell<StringValueInterner<'static>>,
+    /// Budget set with [`Evaluator::set_max_heap_bytes`](crate::eval::Evaluator::set_max_heap_bytes).
+    max_allocated_bytes: Cell<Option<usize>>,
+    /// Allocated bytes when an allocation first exceeded `max_allocated_bytes`.
+    budget_exceeded: Cell<Option<usize>>,
 }
 
 impl Debug for Heap {
@@ -333,6 +343,21 @@ impl FrozenHeap {
         self.alloc_str_impl(x.key(), x.hash())
     }
 
+    /// Reserve space for a value which is filled later,
+    /// so it can be referenced before its contents are known.
+    pub(crate) fn reserve_with_extra<'v, 'v2, T: AValue<'v2>>(
+        &'v self,
+        extra_len: usize,
+    ) -> (
+        FrozenValue,
+        Reservation<'v2, T>,
+        *mut [MaybeUninit<T::ExtraElem>],
+    ) {
+        let (r, extra) = self.arena.reserve_with_extra::<T>(extra_len);
+        let fv = FrozenValue::new_ptr(unsafe { cast::ptr_lifetime(r.ptr()) }, false);
+        (fv, r, extra)
+    }
+
     /// Allocate a tuple with the given elements on this heap.
     pub(crate) fn alloc_tuple<'v>(&'v self, elems: &[FrozenValue]) -> FrozenValue {
         if elems.is_empty() {
@@ -556,9 +581,7 @@ impl Freezer {
         Reservation<'v2, T>,
         *mut [MaybeUninit<T::ExtraElem>],
     ) {
-        let (r, extra) = self.heap.arena.reserve_with_extra::<T>(extra_len);
-        let fv = FrozenValue::new_ptr(unsafe { cast::ptr_lifetime(r.ptr()) }, false);
-        (fv, r, extra)
+        self.heap.reserve_with_extra::<T>(extra_len)
     }
 
     /// Freeze a nested value while freezing yourself.
@@ -609,12 +632,65 @@ impl Heap {
         self.arena.borrow().available_bytes()
     }
 
+    pub(crate) fn set_max_allocated_bytes(&self, max_allocated_bytes: Option<usize>) {
+        self.max_allocated_bytes.set(max_allocated_bytes);
+        self.budget_exceeded.set(None);
+    }
+
+    /// Called after each allocation to record that the heap grew past its budget.
+    /// The error is reported by [`check_budget`](Heap::check_budget),
+    /// because allocations cannot fail.
+    #[inline(always)]
+    fn after_alloc(&self) {
+        // Only a flag check on the allocation path when no budget is set.
+        if let Some(max_allocated_bytes) = self.max_allocated_bytes.get() {
+            self.record_budget_exceeded(max_allocated_bytes);
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn record_budget_exceeded(&self, max_allocated_bytes: usize) {
+        let allocated = self.allocated_bytes();
+        if allocated > max_allocated_bytes && self.budget_exceeded.get().is_none() {
+            self.budget_exceeded.set(Some(allocated));
+        }
+    }
+
+    /// Fail if an allocation exceeded the heap budget.
+    #[inline(always)]
+    pub(crate) fn check_budget(&self) -> crate::Result<()> {
+        match (self.max_allocated_bytes.get(), self.budget_exceeded.get()) {
+            (Some(max_allocated_bytes), Some(allocated)) => Err(crate::Error::new_other(
+                HeapBudgetExceeded(max_allocated_bytes, allocated),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fail if allocating `bytes` more would exceed the heap budget.
+    /// Used before large allocations, so they fail without allocating memory.
+    pub(crate) fn check_budget_for(&self, bytes: usize) -> crate::Result<()> {
+        self.check_budget()?;
+        if let Some(max_allocated_bytes) = self.max_allocated_bytes.get() {
+            let allocated = self.allocated_bytes().saturating_add(bytes);
+            if allocated > max_allocated_bytes {
+                return Err(crate::Error::new_other(HeapBudgetExceeded(
+                    max_allocated_bytes,
+                    allocated,
+                )));
+            }
+        }
+        Ok(())
+    }
+
     fn alloc_raw<'v, 'v2: 'v2>(
         &'v self,
         x: AValueImpl<'v2, impl AValue<'v2, ExtraElem = ()>>,
     ) -> Value<'v> {
         let arena = self.arena.borrow();
         let v: &AValueRepr<_> = arena.alloc(x);
+        self.after_alloc();
 
         // We have an arena inside a RefCell which stores ValueMem<'v>
         // However, we promise not to clear the RefCell other than for GC
@@ -640,6 +716,7 @@ impl Heap {
     ) -> StringValue<'v> {
         let arena = self.arena.borrow();
         let v = arena.alloc_str_init(len, hash, init);
+        self.after_alloc();
 
         // We have an arena inside a RefCell which stores ValueMem<'v>
         // However, we promise not to clear the RefCell other than for GC
@@ -671,6 +748,15 @@ impl Heap {
         }
     }
 
+    /// Allocate a path as a string on the heap.
+    /// Fails if the path is not valid UTF-8.
+    pub fn alloc_path<'v>(&'v self, x: &Path) -> crate::Result<StringValue<'v>> {
+        match x.to_str() {
+            Some(x) => Ok(self.alloc_str(x)),
+            None => Err(crate::Error::new_other(PathNotUtf8(x.to_owned()))),
+        }
+    }
+
     /// Intern string.
     pub fn alloc_str_intern<'v>(&'v self, x: &str) -> StringValue<'v> {
         if let Some(x) = constant_string(x) {
@@ -732,6 +818,7 @@ impl Heap {
         unsafe {
             let arena = self.arena.borrow();
             let (avalue, extra) = arena.alloc_extra(tuple_avalue(elems.len()));
+            self.after_alloc();
             let extra = &mut *extra;
             maybe_uninit_write_slice(extra, elems);
             Value::new_repr(&*avalue)
@@ -752,6 +839,7 @@ impl Heap {
             unsafe {
                 let arena = self.arena.borrow();
                 let (avalue, extra) = arena.alloc_extra(tuple_avalue(lower));
+                self.after_alloc();
                 let extra = &mut *extra;
                 maybe_uninit_write_from_exact_size_iter(extra, elems, Value::new_none());
                 Value::new_repr(&*avalue)
@@ -770,6 +858,7 @@ impl Heap {
 
         unsafe {
             let (avalue, _) = self.arena.borrow().alloc_extra(array_avalue(cap));
+            self.after_alloc();
             ValueTyped::new_repr(&*avalue)
         }
     }
@@ -1017,12 +1106,16 @@ impl<'v> Tracer<'v> {
 mod tests {
     use starlark_derive::starlark_module;
 
+    use super::Freezer;
+    use super::FrozenHeap;
     use super::FrozenHeapRef;
     use super::Heap;
     use crate as starlark;
     use crate::assert::Assert;
     use crate::environment::GlobalsBuilder;
+    use crate::values::list::AllocList;
     use crate::values::StringValue;
+    use crate::values::Value;
 
     #[test]
     fn test_send_sync()
@@ -1031,6 +1124,24 @@ mod tests {
     {
     }
 
+    #[test]
+    fn test_allocated_summary_by_bytes() {
+        let heap = Heap::new();
+        for i in 0..10 {
+            heap.alloc_str(&format!("string number {i}"));
+        }
+        for _ in 0..3 {
+            heap.alloc(AllocList::EMPTY);
+        }
+        let summary = heap.allocated_summary().summary_by_bytes();
+        assert_eq!(
+            vec!["string", "list"],
+            summary.iter().map(|(t, _, _)| *t).collect::<Vec<_>>()
+        );
+        assert_eq!((10, 3), (summary[0].1, summary[1].1));
+        assert!(summary[0].2 > summary[1].2);
+    }
+
     #[test]
     fn test_string_reallocated_on_heap() {
         let heap = Heap::new();
@@ -1053,6 +1164,26 @@ mod tests {
         );
     }
 
+    /// Freeze the value produced by `alloc` in two unrelated heaps and check if results are the same.
+    fn frozen_shared_across_heaps(alloc: impl for<'v> Fn(&'v Heap) -> Value<'v>) -> bool {
+        let freeze = || {
+            let heap = Heap::new();
+            let freezer = Freezer::new(FrozenHeap::new());
+            freezer.freeze(alloc(&heap)).unwrap()
+        };
+        freeze().to_value().ptr_eq(freeze().to_value())
+    }
+
+    #[test]
+    fn test_freeze_small_values_shared_across_heaps() {
+        // Small ints are encoded in the pointer, and empty and single ASCII character strings
+        // are static, so freezing them in unrelated heaps yields the same frozen values.
+        assert!(frozen_shared_across_heaps(|h| h.alloc(1)));
+        assert!(frozen_shared_across_heaps(|h| h.alloc(-256)));
+        assert!(frozen_shared_across_heaps(|h| h.alloc_str("").to_value()));
+        assert!(frozen_shared_across_heaps(|h| h.alloc_str("a").to_value()));
+    }
+
     #[starlark_module]
     fn validate_str_interning(globals: &mut GlobalsBuilder) {
         fn append_x<'v>(str: StringValue<'v>, heap: &'v Heap) -> anyhow::Result<StringValue<'v>> {
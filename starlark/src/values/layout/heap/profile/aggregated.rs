@@ -35,6 +35,7 @@ use crate::eval::runtime::profile::instant::ProfilerInstant;
 use crate::eval::runtime::small_duration::SmallDuration;
 use crate::eval::ProfileData;
 use crate::util::arc_str::ArcStr;
+use crate::values::function::NativeFunction;
 use crate::values::layout::heap::arena::ArenaVisitor;
 use crate::values::layout::heap::heap_type::HeapKind;
 use crate::values::layout::heap::profile::alloc_counts::AllocCounts;
@@ -60,7 +61,9 @@ impl FunctionIds {
         match self.values.entry(x.ptr_value()) {
             hash_map::Entry::Occupied(v) => *v.get(),
             hash_map::Entry::Vacant(outer) => {
-                let function_id = self.strings.index(&x.to_str());
+                let name =
+                    NativeFunction::profile_name(x).map_or_else(|| x.to_str(), str::to_owned);
+                let function_id = self.strings.index(&name);
                 outer.insert(function_id);
                 function_id
             }
@@ -42,6 +42,17 @@ impl HeapSummary {
             .collect()
     }
 
+    /// (Type name, count, total size) by type, sorted by total size descending.
+    pub fn summary_by_bytes(&self) -> Vec<(&'static str, usize, usize)> {
+        let mut summary: Vec<_> = self
+            .summary
+            .iter()
+            .map(|(k, v)| (*k, v.count, v.bytes))
+            .collect();
+        summary.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.0.cmp(b.0)));
+        summary
+    }
+
     pub(crate) fn total(&self) -> AllocCounts {
         self.summary.values().sum()
     }
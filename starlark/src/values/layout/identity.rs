@@ -25,6 +25,8 @@ use crate::values::Value;
 
 /// An opaque value representing the identity of a given Value. Two values have the same identity
 /// if and only if [`Value::ptr_eq`] would return [`true`] on them.
+///
+/// Implements [`Hash`] and [`Eq`], so it can be used as a map key to build identity maps.
 #[derive(Eq, PartialEq, Copy, Clone, Dupe, Hash, Debug, Allocative)]
 pub struct ValueIdentity<'v> {
     identity: RawPointer,
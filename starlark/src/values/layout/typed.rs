@@ -476,6 +476,16 @@ mod tests {
         assert_eq!(17, v.as_ref().get().to_i32());
     }
 
+    #[test]
+    fn test_new_err_wrong_type() {
+        let err =
+            FrozenValueTyped::<NoneType>::new_err(FrozenValue::testing_new_int(17)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Expected value of type `NoneType` but got `int (repr: 17)`"
+        );
+    }
+
     #[test]
     fn test_unpack_value_for_frozen_value_typed() {
         #[starlark_module]
@@ -535,6 +535,14 @@ impl<'v> Value<'v> {
         ValueIdentity::new(self)
     }
 
+    /// Hash of this value's identity, derived from its pointer.
+    /// Values which are [`Value::ptr_eq`] have equal identity hashes.
+    /// Valid until the next GC, like [`Value::identity`].
+    #[inline]
+    pub fn identity_hash(self) -> StarlarkHashValue {
+        StarlarkHashValue::hash_64(self.ptr_value().ptr_value() as u64)
+    }
+
     /// Get the underlying pointer.
     /// Should be done sparingly as it slightly breaks the abstraction.
     /// Most useful as a hash key based on pointer.
@@ -832,10 +840,14 @@ impl<'v> Value<'v> {
     }
 
     /// Implement the `str()` function - converts a string value to itself,
-    /// otherwise uses `repr()`.
+    /// otherwise uses [`StarlarkValue::collect_str`], which defaults to `repr()`.
     pub fn to_str(self) -> String {
         match self.unpack_str() {
-            None => self.to_repr(),
+            None => {
+                let mut s = String::new();
+                self.collect_str(&mut s);
+                s
+            }
             Some(s) => s.to_owned(),
         }
     }
@@ -863,6 +875,19 @@ impl<'v> Value<'v> {
         serde_json::to_value(self).map_err(|e| anyhow::anyhow!(e))
     }
 
+    /// Parse JSON and allocate the result on the heap.
+    ///
+    /// Objects become dicts and arrays become lists.
+    pub fn from_json(json: &str, heap: &'v Heap) -> anyhow::Result<Value<'v>> {
+        let json = serde_json::from_str::<serde_json::Value>(json)?;
+        Ok(Self::from_json_value(json, heap))
+    }
+
+    /// Allocate a JSON value on the heap, the inverse of [`to_json_value`](Value::to_json_value).
+    pub fn from_json_value(json: serde_json::Value, heap: &'v Heap) -> Value<'v> {
+        heap.alloc(json)
+    }
+
     /// Forwards to [`StarlarkValue::set_attr`].
     pub fn set_attr(self, attribute: &str, alloc_value: Value<'v>) -> crate::Result<()> {
         self.get_ref().set_attr(attribute, alloc_value)
@@ -976,6 +1001,27 @@ impl<'v> Value<'v> {
         aref.has_attr(attribute, heap)
     }
 
+    /// Get all attributes of a struct-like value with their values,
+    /// or [`None`] if the value is not struct-like.
+    ///
+    /// Includes attributes defined with `#[starlark(attribute)]` in the type's methods,
+    /// followed by attributes returned by [`StarlarkValue::get_attrs`].
+    pub fn get_attrs(self, heap: &'v Heap) -> crate::Result<Option<Vec<(String, Value<'v>)>>> {
+        let aref = self.get_ref();
+        let mut result = None;
+        if let Some(methods) = aref.vtable().methods() {
+            for (name, attr) in methods.attributes() {
+                result
+                    .get_or_insert_with(Vec::new)
+                    .push((name.to_owned(), attr.bind(self, heap)?));
+            }
+        }
+        if let Some(attrs) = aref.get_attrs(heap) {
+            result.get_or_insert_with(Vec::new).extend(attrs);
+        }
+        Ok(result)
+    }
+
     /// Get a list of all the attributes this function supports, used to implement the
     /// `dir()` function.
     pub fn dir_attr(self) -> Vec<String> {
@@ -1320,10 +1366,18 @@ pub trait ValueLike<'v>:
 
     /// `str(x)`.
     fn collect_str(self, collector: &mut String) {
-        if let Some(s) = self.to_value().unpack_str() {
+        let value = self.to_value();
+        if let Some(s) = value.unpack_str() {
             collector.push_str(s);
         } else {
-            self.collect_repr(collector);
+            match repr_stack_push(value) {
+                Ok(_guard) => {
+                    value.get_ref().collect_str(collector);
+                }
+                Err(..) => {
+                    value.get_ref().collect_repr_cycle(collector);
+                }
+            }
         }
     }
 
@@ -1474,6 +1528,7 @@ mod tests {
     use num_bigint::BigInt;
 
     use crate::assert;
+    use crate::collections::SmallMap;
     use crate::environment::Globals;
     use crate::typing::Ty;
     use crate::values::int::pointer_i32::PointerI32;
@@ -1508,6 +1563,27 @@ mod tests {
         assert!(none.downcast_ref::<PointerI32>().is_none());
     }
 
+    #[test]
+    fn test_ptr_eq_and_identity() {
+        let heap = Heap::new();
+        let list = heap.alloc(AllocList([1, 2]));
+        let same = list;
+        let fresh = heap.alloc(AllocList([1, 2]));
+
+        assert!(list.ptr_eq(same));
+        assert!(!list.ptr_eq(fresh));
+        assert!(list.equals(fresh).unwrap());
+        assert_eq!(list.identity_hash(), same.identity_hash());
+        assert_ne!(list.identity(), fresh.identity());
+
+        let mut seen = SmallMap::new();
+        seen.insert(list.identity(), "list");
+        seen.insert(same.identity(), "same");
+        seen.insert(fresh.identity(), "fresh");
+        assert_eq!(2, seen.len());
+        assert_eq!(Some(&"same"), seen.get(&list.identity()));
+    }
+
     #[test]
     fn test_unpack_i32() {
         let heap = Heap::new();
@@ -1541,6 +1617,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_json_round_trip() {
+        let json = serde_json::json!({
+            "a": [1, 2.5, "s", null, true],
+            "b": {"c": [], "d": {"e": -7}},
+        });
+        let heap = Heap::new();
+        let value = Value::from_json_value(json.clone(), &heap);
+        assert_eq!(json, value.to_json_value().unwrap());
+        let value = Value::from_json(&json.to_string(), &heap).unwrap();
+        assert_eq!(json.to_string(), value.to_json().unwrap());
+        assert!(Value::from_json("{", &heap).is_err());
+    }
+
+    #[test]
+    fn test_to_json_function_fails() {
+        let value = assert::pass("len");
+        assert!(value.value().to_json_value().is_err());
+        let value = assert::pass("[{'f': lambda: 1}]");
+        assert!(value.value().to_json().is_err());
+    }
+
     #[test]
     fn test_display_for_type_error() {
         assert_eq!(
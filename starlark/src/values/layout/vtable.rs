@@ -492,6 +492,16 @@ impl<'v> AValueDyn<'v> {
         (self.vtable.starlark_value.collect_repr)(self.value, collector)
     }
 
+    #[inline]
+    pub(crate) fn get_attrs(self, heap: &'v Heap) -> Option<Vec<(String, Value<'v>)>> {
+        (self.vtable.starlark_value.get_attrs)(self.value, heap)
+    }
+
+    #[inline]
+    pub(crate) fn collect_str(self, collector: &mut String) {
+        (self.vtable.starlark_value.collect_str)(self.value, collector)
+    }
+
     #[inline]
     pub(crate) fn collect_repr_cycle(self, collector: &mut String) {
         (self.vtable.starlark_value.collect_repr_cycle)(self.value, collector)
@@ -0,0 +1,132 @@
+/*
+ * Copyright 2018 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Reflection over the members of a value.
+
+use std::collections::HashSet;
+
+use dupe::Dupe;
+
+use crate::typing::Ty;
+use crate::values::Value;
+
+/// Whether a member is an attribute or a method, see [`MemberInfo`].
+#[derive(Debug, Clone, Copy, Dupe, PartialEq, Eq)]
+pub enum MemberKind {
+    /// An attribute, e.g. defined with `#[starlark(attribute)]`.
+    Attr,
+    /// A method, which must be called.
+    Method,
+}
+
+/// Description of a member of a value, returned by [`members`].
+#[derive(Debug, Clone)]
+pub struct MemberInfo {
+    /// The name of the member.
+    pub name: String,
+    /// Is the member an attribute or a method.
+    pub kind: MemberKind,
+    /// For an attribute, the type it evaluates to, and for a method, the type of the callable.
+    /// Attributes which are not registered in the methods of the type are of type `typing.Any`.
+    pub ty: Ty,
+}
+
+/// List the members of a value, like `dir()` does, but with their kind and type.
+///
+/// The result is sorted by name.
+pub fn members(value: Value) -> Vec<MemberInfo> {
+    let aref = value.get_ref();
+    let mut res = Vec::new();
+    if let Some(methods) = aref.vtable().methods() {
+        let attributes: HashSet<&str> = methods.attributes().map(|(name, _)| name).collect();
+        for name in methods.names() {
+            let kind = if attributes.contains(name.as_str()) {
+                MemberKind::Attr
+            } else {
+                MemberKind::Method
+            };
+            let ty = methods.get_ty(&name).unwrap_or_else(Ty::any);
+            res.push(MemberInfo { name, kind, ty });
+        }
+    }
+    res.extend(aref.dir_attr().into_iter().map(|name| MemberInfo {
+        name,
+        kind: MemberKind::Attr,
+        ty: Ty::any(),
+    }));
+    res.sort_by(|a, b| a.name.cmp(&b.name));
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use allocative::Allocative;
+    use derive_more::Display;
+    use starlark_derive::starlark_module;
+    use starlark_derive::starlark_value;
+    use starlark_derive::NoSerialize;
+
+    use crate as starlark;
+    use crate::any::ProvidesStaticType;
+    use crate::environment::Methods;
+    use crate::environment::MethodsBuilder;
+    use crate::environment::MethodsStatic;
+    use crate::starlark_simple_value;
+    use crate::typing::Ty;
+    use crate::values::members::members;
+    use crate::values::members::MemberKind;
+    use crate::values::Heap;
+    use crate::values::StarlarkValue;
+
+    #[derive(Debug, Display, ProvidesStaticType, NoSerialize, Allocative)]
+    #[display("Counter")]
+    struct Counter(i32);
+    starlark_simple_value!(Counter);
+
+    #[starlark_module]
+    fn counter_methods(builder: &mut MethodsBuilder) {
+        #[starlark(attribute)]
+        fn value(this: &Counter) -> anyhow::Result<i32> {
+            Ok(this.0)
+        }
+
+        fn add(this: &Counter, x: i32) -> anyhow::Result<i32> {
+            Ok(this.0 + x)
+        }
+    }
+
+    #[starlark_value(type = "counter")]
+    impl<'v> StarlarkValue<'v> for Counter {
+        fn get_methods() -> Option<&'static Methods> {
+            static RES: MethodsStatic = MethodsStatic::new();
+            RES.methods(counter_methods)
+        }
+    }
+
+    #[test]
+    fn test_members() {
+        let heap = Heap::new();
+        let members = members(heap.alloc(Counter(1)));
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[0].name, "add");
+        assert_eq!(members[0].kind, MemberKind::Method);
+        assert_eq!(members[0].ty.to_string(), "def(x: int) -> int");
+        assert_eq!(members[1].name, "value");
+        assert_eq!(members[1].kind, MemberKind::Attr);
+        assert_eq!(members[1].ty, Ty::int());
+    }
+}
@@ -0,0 +1,302 @@
+/*
+ * Copyright 2018 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Render the `repr` of a value within a length budget, for diagnostics.
+
+use crate::values::dict::DictRef;
+use crate::values::list::ListRef;
+use crate::values::recursive_repr_or_json_guard::repr_stack_push;
+use crate::values::set::refs::SetRef;
+use crate::values::tuple::TupleRef;
+use crate::values::types::string::repr::string_repr;
+use crate::values::UnpackValue;
+use crate::values::Value;
+
+/// Item of a container: a value, or a key and a value in a dict.
+type Item<'v> = (Option<Value<'v>>, Value<'v>);
+
+/// Opening and closing brackets and items of a non-empty list, tuple, dict or set.
+fn container<'v>(value: Value<'v>) -> Option<(&'static str, &'static str, Vec<Item<'v>>)> {
+    let (open, close, items): (_, _, Vec<_>) = if let Some(list) = ListRef::from_value(value) {
+        ("[", "]", list.iter().map(|x| (None, x)).collect())
+    } else if let Some(tuple) = TupleRef::from_value(value) {
+        let close = if tuple.len() == 1 { ",)" } else { ")" };
+        ("(", close, tuple.iter().map(|x| (None, x)).collect())
+    } else if let Some(dict) = DictRef::from_value(value) {
+        ("{", "}", dict.iter().map(|(k, v)| (Some(k), v)).collect())
+    } else if let Some(set) = SetRef::unpack_value_opt(value) {
+        ("set([", "])", set.aref.iter().map(|x| (None, x)).collect())
+    } else {
+        return None;
+    };
+    if items.is_empty() {
+        None
+    } else {
+        Some((open, close, items))
+    }
+}
+
+fn escape(c: char) -> String {
+    let mut escaped = String::new();
+    string_repr(c.encode_utf8(&mut [0; 4]), &mut escaped);
+    // Remove the quotes.
+    escaped.pop();
+    escaped.remove(0);
+    escaped
+}
+
+fn omitted(count: usize, what: &str) -> String {
+    format!("...<{count} {what} omitted>...")
+}
+
+/// Cut `s` to at most `max_len` chars.
+fn clamp(mut s: String, max_len: usize) -> String {
+    if let Some((end, _)) = s.char_indices().nth(max_len) {
+        s.truncate(end);
+    }
+    s
+}
+
+/// Length in chars of `repr` of `value` if it is at most `max_len`,
+/// without rendering more than needed to find out.
+fn repr_len(value: Value, max_len: usize) -> Option<usize> {
+    let len = if let Some(s) = value.unpack_str() {
+        let mut len = 2;
+        for c in s.chars() {
+            len += escape(c).chars().count();
+            if len > max_len {
+                return None;
+            }
+        }
+        len
+    } else if let Some((open, close, items)) = container(value) {
+        match repr_stack_push(value) {
+            Ok(_guard) => {
+                let mut len = open.len() + close.len();
+                for (i, item) in items.iter().enumerate() {
+                    let sep = if i == 0 { 0 } else { 2 };
+                    len += sep + item_len(*item, max_len.checked_sub(len + sep)?)?;
+                }
+                len
+            }
+            Err(..) => repr_cycle(value).chars().count(),
+        }
+    } else {
+        value.to_repr().chars().count()
+    };
+    if len <= max_len { Some(len) } else { None }
+}
+
+fn item_len((key, value): Item, max_len: usize) -> Option<usize> {
+    match key {
+        None => repr_len(value, max_len),
+        Some(key) => {
+            let key_len = repr_len(key, max_len)? + 2;
+            Some(key_len + repr_len(value, max_len.checked_sub(key_len)?)?)
+        }
+    }
+}
+
+fn item_repr((key, value): Item) -> String {
+    match key {
+        None => value.to_repr(),
+        Some(key) => format!("{}: {}", key.to_repr(), value.to_repr()),
+    }
+}
+
+fn repr_cycle(value: Value) -> String {
+    let mut s = String::new();
+    value.get_ref().collect_repr_cycle(&mut s);
+    s
+}
+
+/// Render `value` in at most `max_len` chars.
+fn repr_within(value: Value, max_len: usize) -> String {
+    if repr_len(value, max_len).is_some() {
+        value.to_repr()
+    } else if let Some(s) = value.unpack_str() {
+        str_within(s, max_len)
+    } else if let Some((open, close, items)) = container(value) {
+        match repr_stack_push(value) {
+            Ok(_guard) => items_within(open, close, &items, max_len),
+            Err(..) => clamp(repr_cycle(value), max_len),
+        }
+    } else {
+        clamp(value.to_repr(), max_len)
+    }
+}
+
+/// Render a container which does not fit in `max_len` chars.
+fn items_within(open: &str, close: &str, items: &[Item], max_len: usize) -> String {
+    let inner = max_len.saturating_sub(open.len() + close.len());
+    // Reserve space for the marker and the separators around it.
+    let avail = inner.saturating_sub(omitted(items.len(), "items").len() + 4);
+    // Keep 2/3 of the space for the front, because the front is usually more interesting.
+    let front_max = avail - avail / 3;
+
+    let mut front = Vec::new();
+    let mut len = 0;
+    for item in items {
+        let sep = if front.is_empty() { 0 } else { 2 };
+        match front_max
+            .checked_sub(len + sep)
+            .and_then(|max_len| item_len(*item, max_len))
+        {
+            Some(item_len) => {
+                len += sep + item_len;
+                front.push(item_repr(*item));
+            }
+            None => {
+                if front.is_empty() && avail != 0 {
+                    // Show the start of the first item rather than nothing,
+                    // taking space from the back if needed.
+                    let item = match *item {
+                        (None, value) => repr_within(value, avail),
+                        (Some(key), value) => {
+                            let key = repr_within(key, avail);
+                            let value_max = avail.saturating_sub(key.chars().count() + 2);
+                            let value = repr_within(value, value_max);
+                            clamp(format!("{key}: {value}"), avail)
+                        }
+                    };
+                    len = item.chars().count();
+                    front.push(item);
+                }
+                break;
+            }
+        }
+    }
+
+    let mut back = Vec::new();
+    for item in items[front.len()..].iter().rev() {
+        let sep = if back.is_empty() { 0 } else { 2 };
+        match avail
+            .checked_sub(len + sep)
+            .and_then(|max_len| item_len(*item, max_len))
+        {
+            Some(item_len) => {
+                len += sep + item_len;
+                back.push(item_repr(*item));
+            }
+            None => break,
+        }
+    }
+    back.reverse();
+
+    let omitted_count = items.len() - front.len() - back.len();
+    let mut parts = front;
+    if omitted_count != 0 {
+        parts.push(omitted(omitted_count, "items"));
+    }
+    parts.extend(back);
+    clamp(format!("{open}{}{close}", parts.join(", ")), max_len)
+}
+
+/// Render the string `s` whose `repr` does not fit in `max_len` chars.
+fn str_within(s: &str, max_len: usize) -> String {
+    let chars = s.chars().count();
+    let avail = max_len.saturating_sub(omitted(chars, "chars").len() + 2);
+    let front_max = avail - avail / 3;
+
+    let mut front = String::new();
+    let mut front_chars = 0;
+    let mut len = 0;
+    for c in s.chars() {
+        let c = escape(c);
+        let c_len = c.chars().count();
+        if len + c_len > front_max {
+            break;
+        }
+        len += c_len;
+        front_chars += 1;
+        front.push_str(&c);
+    }
+    let mut back = Vec::new();
+    for c in s.chars().rev().take(chars - front_chars) {
+        let c = escape(c);
+        let c_len = c.chars().count();
+        if len + c_len > avail {
+            break;
+        }
+        len += c_len;
+        back.push(c);
+    }
+    back.reverse();
+    let marker = omitted(chars - front_chars - back.len(), "chars");
+    clamp(format!("\"{front}{marker}{}\"", back.concat()), max_len)
+}
+
+impl<'v> Value<'v> {
+    /// Like [`to_repr`](Value::to_repr), but the result is at most `max_len` characters.
+    ///
+    /// The middle of long strings, lists, tuples, dicts and sets is replaced with a marker
+    /// saying how many characters or items were omitted,
+    /// and the omitted parts are not rendered at all.
+    /// If even the marker does not fit, the result is cut at `max_len` characters.
+    ///
+    /// This is meant for diagnostics: the result is not a valid Starlark expression.
+    pub fn repr_truncated(self, max_len: usize) -> String {
+        repr_within(self, max_len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::values::list::AllocList;
+    use crate::values::types::list::value::ListData;
+    use crate::values::Heap;
+
+    #[test]
+    fn test_repr_truncated() {
+        let heap = Heap::new();
+        assert_eq!("[1, 2]", heap.alloc(AllocList([1, 2])).repr_truncated(6));
+
+        let list = heap.alloc(AllocList(0..10000));
+        let repr = list.repr_truncated(80);
+        assert_eq!(
+            "[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, ...<9987 items omitted>..., 9998, 9999]",
+            repr
+        );
+        assert!(repr.len() <= 80);
+
+        let s = heap.alloc("abcdefghijklmnopqrstuvwxyz\n");
+        assert_eq!("\"abcdefghijklmnopqrstuvwxyz\\n\"", s.repr_truncated(30));
+        assert_eq!("\"ab...<25 chars omitted>...\"", s.repr_truncated(29));
+
+        let nested = heap.alloc(AllocList([heap.alloc(AllocList(0..1000)), heap.alloc("x")]));
+        assert_eq!(
+            "[[...<1000 items omitted>...], \"x\"]",
+            nested.repr_truncated(60)
+        );
+
+        let cycle = heap.alloc(AllocList::EMPTY);
+        ListData::from_value_mut(cycle).unwrap().push(cycle, &heap);
+        assert_eq!("[[...]]", cycle.repr_truncated(60));
+    }
+
+    #[test]
+    fn test_repr_truncated_smaller_than_marker() {
+        let heap = Heap::new();
+        let list = heap.alloc(AllocList(0..10000));
+        let s = heap.alloc("x".repeat(10000));
+        for max_len in 0..40 {
+            assert!(list.repr_truncated(max_len).chars().count() <= max_len);
+            assert!(s.repr_truncated(max_len).chars().count() <= max_len);
+        }
+        assert_eq!("[...<1", list.repr_truncated(6));
+    }
+}
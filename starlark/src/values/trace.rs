@@ -19,6 +19,9 @@ use std::cell::Cell;
 use std::cell::OnceCell;
 use std::cell::RefCell;
 use std::cell::UnsafeCell;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::marker;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicI16;
@@ -81,6 +84,26 @@ unsafe impl<'v, T: Trace<'v>> Trace<'v> for [T] {
     }
 }
 
+unsafe impl<'v, T: Trace<'v>> Trace<'v> for VecDeque<T> {
+    fn trace(&mut self, tracer: &Tracer<'v>) {
+        self.iter_mut().for_each(|x| x.trace(tracer));
+    }
+}
+
+/// Keys cannot be modified in place, so they must not contain values.
+unsafe impl<'v, K: 'static, V: Trace<'v>, S> Trace<'v> for HashMap<K, V, S> {
+    fn trace(&mut self, tracer: &Tracer<'v>) {
+        self.values_mut().for_each(|x| x.trace(tracer));
+    }
+}
+
+/// Keys cannot be modified in place, so they must not contain values.
+unsafe impl<'v, K: 'static, V: Trace<'v>> Trace<'v> for BTreeMap<K, V> {
+    fn trace(&mut self, tracer: &Tracer<'v>) {
+        self.values_mut().for_each(|x| x.trace(tracer));
+    }
+}
+
 unsafe impl<'v, T: Trace<'v>> Trace<'v> for RawTable<T> {
     fn trace(&mut self, tracer: &Tracer<'v>) {
         unsafe {
@@ -306,3 +329,44 @@ unsafe impl<'v, A, B, R> Trace<'v> for fn(A, B) -> R {
 unsafe impl<'v, A, B, C, R> Trace<'v> for fn(A, B, C) -> R {
     fn trace(&mut self, _tracer: &Tracer<'v>) {}
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::collections::HashMap;
+    use std::collections::VecDeque;
+
+    use crate::values::Heap;
+    use crate::values::Trace;
+    use crate::values::Value;
+
+    #[test]
+    fn test_trace_std_collections() {
+        let heap = Heap::new();
+        let mut map: HashMap<String, Value> = HashMap::new();
+        let mut tree: BTreeMap<u32, Option<Value>> = BTreeMap::new();
+        let mut deque: VecDeque<Value> = VecDeque::new();
+        for i in 0..100 {
+            map.insert(i.to_string(), heap.alloc(format!("map{i}")));
+            tree.insert(i, Some(heap.alloc(format!("tree{i}"))));
+            deque.push_back(heap.alloc(format!("deque{i}")));
+            heap.alloc(format!("garbage{i}"));
+        }
+        let before = heap.allocated_bytes();
+        // Safe: the collections hold all the values we use after the GC.
+        unsafe {
+            heap.garbage_collect(|tracer| {
+                map.trace(tracer);
+                tree.trace(tracer);
+                deque.trace(tracer);
+            })
+        };
+        // Garbage was collected, and every value in the collections was moved.
+        assert!(heap.allocated_bytes() < before);
+        for i in 0..100 {
+            assert_eq!(map[&i.to_string()].unpack_str(), Some(&*format!("map{i}")));
+            assert_eq!(tree[&i].unwrap().unpack_str(), Some(&*format!("tree{i}")));
+            assert_eq!(deque[i as usize].unpack_str(), Some(&*format!("deque{i}")));
+        }
+    }
+}
@@ -361,6 +361,14 @@ pub trait StarlarkValue<'v>:
         write!(collector, "{}", self).unwrap()
     }
 
+    /// Return a string representation of self, as returned by the `str()` function.
+    /// Defaults to [`collect_repr`](StarlarkValue::collect_repr).
+    ///
+    /// Only used for the top-level value: elements of containers are always printed with `repr`.
+    fn collect_str(&self, collector: &mut String) {
+        self.collect_repr(collector)
+    }
+
     /// Invoked to print `repr` when a cycle is the object stack is detected.
     fn collect_repr_cycle(&self, collector: &mut String) {
         write!(collector, "<{}...>", Self::TYPE).unwrap()
@@ -625,6 +633,16 @@ pub trait StarlarkValue<'v>:
         Vec::new()
     }
 
+    /// Return all attributes of the current value with their values, in declaration order,
+    /// or [`None`] if the value is not struct-like.
+    ///
+    /// Used to destructure values from Rust, see [`Value::get_attrs`].
+    /// Must be consistent with [`get_attr`](StarlarkValue::get_attr)
+    /// and [`dir_attr`](StarlarkValue::dir_attr).
+    fn get_attrs(&self, _heap: &'v Heap) -> Option<Vec<(String, Value<'v>)>> {
+        None
+    }
+
     /// Tell whether `other` is in the current value, if it is a container.
     ///
     /// # Examples
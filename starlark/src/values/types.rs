@@ -22,6 +22,7 @@ pub mod array;
 pub mod bigint;
 pub mod bool;
 pub mod dict;
+pub(crate) mod duration;
 pub(crate) mod ellipsis;
 pub mod enumeration;
 pub mod exported_name;
@@ -34,6 +35,7 @@ pub mod list_or_tuple;
 pub mod namespace;
 pub mod none;
 pub(crate) mod num;
+pub(crate) mod path;
 pub mod range;
 pub mod record;
 pub mod set;
@@ -21,6 +21,7 @@ mod alloc;
 
 mod dict_type;
 pub(crate) mod globals;
+mod index_map;
 pub(crate) mod methods;
 mod refs;
 mod traits;
@@ -32,5 +33,6 @@ pub use crate::values::dict::dict_type::DictType;
 pub use crate::values::dict::refs::DictMut;
 pub use crate::values::dict::refs::DictRef;
 pub use crate::values::dict::refs::FrozenDictRef;
+pub use crate::values::dict::unpack::DictOf;
 pub use crate::values::dict::unpack::UnpackDictEntries;
 pub use crate::values::dict::value::Dict;
@@ -89,7 +89,7 @@ pub(crate) fn register_dict(globals: &mut GlobalsBuilder) {
         // It might have named/kwargs arguments, which we copy over (afterwards).
 
         let pos = args.optional1(heap)?;
-        let kwargs = args.names()?;
+        let kwargs = args.names_dict()?;
 
         match pos {
             None => Ok(kwargs),
@@ -0,0 +1,113 @@
+/*
+ * Copyright 2018 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Conversions between [`IndexMap`] and Starlark dicts, preserving insertion order.
+
+#![cfg(feature = "indexmap")]
+
+use std::hash::Hash;
+
+use either::Either;
+use indexmap::IndexMap;
+
+use crate::typing::Ty;
+use crate::values::dict::AllocDict;
+use crate::values::dict::DictRef;
+use crate::values::type_repr::StarlarkTypeRepr;
+use crate::values::types::dict::dict_type::DictType;
+use crate::values::AllocFrozenValue;
+use crate::values::AllocValue;
+use crate::values::FrozenHeap;
+use crate::values::FrozenValue;
+use crate::values::Heap;
+use crate::values::UnpackValue;
+use crate::values::Value;
+
+impl<'v, K: AllocValue<'v>, V: AllocValue<'v>> AllocValue<'v> for IndexMap<K, V> {
+    fn alloc_value(self, heap: &'v Heap) -> Value<'v> {
+        AllocDict(self).alloc_value(heap)
+    }
+}
+
+impl<K: AllocFrozenValue, V: AllocFrozenValue> AllocFrozenValue for IndexMap<K, V> {
+    fn alloc_frozen_value(self, heap: &FrozenHeap) -> FrozenValue {
+        AllocDict(self).alloc_frozen_value(heap)
+    }
+}
+
+impl<K: StarlarkTypeRepr, V: StarlarkTypeRepr> StarlarkTypeRepr for IndexMap<K, V> {
+    type Canonical = <DictType<K, V> as StarlarkTypeRepr>::Canonical;
+
+    fn starlark_type_repr() -> Ty {
+        DictType::<K, V>::starlark_type_repr()
+    }
+}
+
+impl<'v, K: UnpackValue<'v> + Hash + Eq, V: UnpackValue<'v>> UnpackValue<'v> for IndexMap<K, V> {
+    type Error = Either<K::Error, V::Error>;
+
+    fn unpack_value_impl(value: Value<'v>) -> Result<Option<Self>, Self::Error> {
+        let Some(dict) = DictRef::from_value(value) else {
+            return Ok(None);
+        };
+        let it = dict.iter();
+        let mut r = IndexMap::with_capacity(it.len());
+        for (k, v) in it {
+            let Some(k) = K::unpack_value_impl(k).map_err(Either::Left)? else {
+                return Ok(None);
+            };
+            let Some(v) = V::unpack_value_impl(v).map_err(Either::Right)? else {
+                return Ok(None);
+            };
+            r.insert(k, v);
+        }
+        Ok(Some(r))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+    use starlark_derive::starlark_module;
+
+    use crate as starlark;
+    use crate::assert::Assert;
+    use crate::environment::GlobalsBuilder;
+
+    #[starlark_module]
+    fn index_map_functions(globals: &mut GlobalsBuilder) {
+        fn index_map_round_trip(d: IndexMap<String, i32>) -> anyhow::Result<IndexMap<String, i32>> {
+            Ok(d)
+        }
+
+        fn index_map_keys(d: IndexMap<String, i32>) -> anyhow::Result<Vec<String>> {
+            Ok(d.into_keys().collect())
+        }
+    }
+
+    #[test]
+    fn test_index_map_preserves_order() {
+        let mut a = Assert::new();
+        a.globals_add(index_map_functions);
+        a.eq("['b', 'a']", "index_map_keys({'b': 1, 'a': 2})");
+        a.eq(
+            "['b', 'a']",
+            "list(index_map_round_trip({'b': 1, 'a': 2}).keys())",
+        );
+        a.eq("{'b': 1, 'a': 2}", "index_map_round_trip({'b': 1, 'a': 2})");
+    }
+}
@@ -17,9 +17,12 @@
 
 use either::Either;
 
+use crate::typing::Ty;
 use crate::values::dict::DictRef;
 use crate::values::type_repr::StarlarkTypeRepr;
 use crate::values::types::dict::dict_type::DictType;
+use crate::values::AllocValue;
+use crate::values::Heap;
 use crate::values::UnpackValue;
 use crate::values::Value;
 
@@ -68,3 +71,139 @@ impl<'v, K: UnpackValue<'v>, V: UnpackValue<'v>> UnpackValue<'v> for UnpackDictE
         Ok(Some(UnpackDictEntries { entries }))
     }
 }
+
+/// Unpack `dict[K, V]`, validating every entry, and keep the original dict value.
+///
+/// A dict with a key or value of the wrong type does not match,
+/// like any other value which is not `dict[K, V]`,
+/// but parameter type errors name the offending entry.
+#[derive(Debug)]
+pub struct DictOf<'v, K, V> {
+    /// The original dict value.
+    pub value: Value<'v>,
+    /// Unpacked entries, in dict order.
+    pub entries: Vec<(K, V)>,
+}
+
+impl<'v, K: StarlarkTypeRepr, V: StarlarkTypeRepr> StarlarkTypeRepr for DictOf<'v, K, V> {
+    type Canonical = <DictType<K, V> as StarlarkTypeRepr>::Canonical;
+
+    fn starlark_type_repr() -> Ty {
+        DictType::<K, V>::starlark_type_repr()
+    }
+}
+
+impl<'v, K: UnpackValue<'v>, V: UnpackValue<'v>> UnpackValue<'v> for DictOf<'v, K, V> {
+    type Error = Either<K::Error, V::Error>;
+
+    fn unpack_value_impl(value: Value<'v>) -> Result<Option<Self>, Self::Error> {
+        Ok(UnpackDictEntries::unpack_value_impl(value)?
+            .map(|UnpackDictEntries { entries }| DictOf { value, entries }))
+    }
+
+    fn unpack_mismatch_reason(value: Value<'v>) -> Option<String> {
+        let dict = DictRef::unpack_value_opt(value)?;
+        for (k, v) in dict.iter() {
+            if let Ok(None) = K::unpack_value_impl(k) {
+                return Some(format!(
+                    "key `{}` has type `{}`, expected `{}`",
+                    k.to_repr(),
+                    k.get_type(),
+                    K::starlark_type_repr()
+                ));
+            }
+            if let Ok(None) = V::unpack_value_impl(v) {
+                return Some(format!(
+                    "value for key `{}` has type `{}`, expected `{}`",
+                    k.to_repr(),
+                    v.get_type(),
+                    V::starlark_type_repr()
+                ));
+            }
+        }
+        None
+    }
+}
+
+impl<'v, K, V> AllocValue<'v> for DictOf<'v, K, V>
+where
+    Self: StarlarkTypeRepr,
+{
+    fn alloc_value(self, _heap: &'v Heap) -> Value<'v> {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use either::Either;
+    use starlark_derive::starlark_module;
+
+    use crate as starlark;
+    use crate::assert::Assert;
+    use crate::environment::GlobalsBuilder;
+    use crate::values::dict::DictOf;
+
+    #[starlark_module]
+    fn dict_of_functions(globals: &mut GlobalsBuilder) {
+        fn sum_values<'v>(m: DictOf<'v, String, i32>) -> anyhow::Result<i32> {
+            Ok(m.entries.iter().map(|(_, v)| *v).sum())
+        }
+
+        fn keys_joined<'v>(m: DictOf<'v, String, i32>) -> anyhow::Result<String> {
+            Ok(m.entries
+                .iter()
+                .map(|(k, _)| k.as_str())
+                .collect::<Vec<_>>()
+                .join(","))
+        }
+
+        fn describe<'v>(
+            m: Either<DictOf<'v, String, i32>, DictOf<'v, String, String>>,
+        ) -> anyhow::Result<String> {
+            Ok(match m {
+                Either::Left(m) => format!("ints: {}", m.entries.len()),
+                Either::Right(m) => format!("strings: {}", m.entries.len()),
+            })
+        }
+    }
+
+    #[test]
+    fn test_dict_of() {
+        let mut a = Assert::new();
+        a.globals_add(dict_of_functions);
+        a.eq("6", "sum_values({'a': 1, 'b': 2, 'c': 3})");
+        a.eq("'b,a'", "keys_joined({'b': 1, 'a': 2})");
+        a.eq("0", "sum_values({})");
+    }
+
+    #[test]
+    fn test_dict_of_union() {
+        let mut a = Assert::new();
+        a.globals_add(dict_of_functions);
+        a.eq("'ints: 2'", "describe({'a': 1, 'b': 2})");
+        a.eq("'strings: 1'", "describe({'a': 'x'})");
+        a.fail(
+            "noop(describe)({'a': 1, 'b': 'x'})",
+            "Type of parameter `m` doesn't match",
+        );
+    }
+
+    #[test]
+    fn test_dict_of_wrong_type() {
+        let mut a = Assert::new();
+        a.globals_add(dict_of_functions);
+        a.fail(
+            "noop(sum_values)({'a': 1, 'b': 'x'})",
+            "Type of parameter `m` doesn't match, expected `dict[str, int]`, actual `dict (repr: {\"a\": 1, \"b\": \"x\"})`: value for key `\"b\"` has type `string`, expected `int`",
+        );
+        a.fail(
+            "noop(sum_values)({'a': 1, 2: 3})",
+            "Type of parameter `m` doesn't match, expected `dict[str, int]`, actual `dict (repr: {\"a\": 1, 2: 3})`: key `2` has type `int`, expected `str`",
+        );
+        a.fail(
+            "noop(sum_values)([1])",
+            "Type of parameter `m` doesn't match, expected `dict[str, int]`",
+        );
+    }
+}
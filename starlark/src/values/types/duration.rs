@@ -0,0 +1,130 @@
+/*
+ * Copyright 2019 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Unpacking of [`Duration`] from Starlark values.
+
+use std::time::Duration;
+
+use either::Either;
+
+use crate::typing::Ty;
+use crate::values::type_repr::StarlarkTypeRepr;
+use crate::values::UnpackValue;
+use crate::values::Value;
+
+#[derive(Debug, thiserror::Error)]
+enum DurationError {
+    #[error(
+        "Invalid duration `{0}`, expected a non-negative integer followed by one of `ms`, `s`, `m`, `h`"
+    )]
+    Invalid(String),
+    #[error("Duration `{0}` is too large")]
+    Overflow(String),
+}
+
+fn parse_duration(s: &str) -> Result<Duration, DurationError> {
+    // `ms` must be checked before `m` and `s`.
+    let (number, millis_per_unit) = if let Some(n) = s.strip_suffix("ms") {
+        (n, 1)
+    } else if let Some(n) = s.strip_suffix('s') {
+        (n, 1000)
+    } else if let Some(n) = s.strip_suffix('m') {
+        (n, 60 * 1000)
+    } else if let Some(n) = s.strip_suffix('h') {
+        (n, 60 * 60 * 1000)
+    } else {
+        return Err(DurationError::Invalid(s.to_owned()));
+    };
+    if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(DurationError::Invalid(s.to_owned()));
+    }
+    let millis = number
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(millis_per_unit))
+        .ok_or_else(|| DurationError::Overflow(s.to_owned()))?;
+    Ok(Duration::from_millis(millis))
+}
+
+impl StarlarkTypeRepr for Duration {
+    type Canonical = <Either<u64, String> as StarlarkTypeRepr>::Canonical;
+
+    fn starlark_type_repr() -> Ty {
+        Either::<u64, String>::starlark_type_repr()
+    }
+}
+
+/// Unpack either an integer number of seconds,
+/// or a string with a unit suffix (`ms`, `s`, `m` or `h`), e.g. `"500ms"`.
+impl<'v> UnpackValue<'v> for Duration {
+    type Error = crate::Error;
+
+    fn unpack_value_impl(value: Value<'v>) -> crate::Result<Option<Self>> {
+        if let Some(s) = value.unpack_str() {
+            parse_duration(s).map(Some).map_err(crate::Error::new_value)
+        } else {
+            Ok(u64::unpack_value(value)?.map(Duration::from_secs))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use starlark_derive::starlark_module;
+
+    use crate as starlark;
+    use crate::assert::Assert;
+    use crate::environment::GlobalsBuilder;
+
+    #[starlark_module]
+    fn duration_functions(globals: &mut GlobalsBuilder) {
+        fn timeout_millis(timeout: Duration) -> anyhow::Result<u64> {
+            Ok(timeout.as_millis() as u64)
+        }
+    }
+
+    #[test]
+    fn test_unpack_duration() {
+        let mut a = Assert::new();
+        a.globals_add(duration_functions);
+        a.eq("5000", "timeout_millis(5)");
+        a.eq("500", "timeout_millis('500ms')");
+        a.eq("5000", "timeout_millis('5s')");
+        a.eq("120000", "timeout_millis('2m')");
+        a.eq("3600000", "timeout_millis('1h')");
+    }
+
+    #[test]
+    fn test_unpack_duration_error() {
+        let mut a = Assert::new();
+        a.globals_add(duration_functions);
+        a.fail("timeout_millis('5x')", "Invalid duration `5x`");
+        a.fail("timeout_millis('s')", "Invalid duration `s`");
+        a.fail("timeout_millis('-5s')", "Invalid duration `-5s`");
+        a.fail(
+            "timeout_millis(-5)",
+            "Error unpacking value for parameter `timeout`",
+        );
+        a.fail("noop(timeout_millis)([])", "expected `int | str`");
+        a.fail(
+            "timeout_millis('99999999999999999999h')",
+            "Duration `99999999999999999999h` is too large",
+        );
+    }
+}
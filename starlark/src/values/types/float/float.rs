@@ -147,8 +147,12 @@ pub(crate) fn write_compact<W: fmt::Write>(
         };
 
         if exponent.abs() >= WRITE_PRECISION as i32 {
-            // use scientific notation if exponent is outside of our precision (but strip 0s)
-            write_scientific(output, f, exponent_char, true)
+            // use scientific notation if exponent is outside of our precision,
+            // with the shortest mantissa which round-trips
+            let s = format!("{:e}", f);
+            let (mantissa, exponent) = s.split_once('e').unwrap();
+            let exponent: i32 = exponent.parse().unwrap();
+            write!(output, "{}{}{:+03}", mantissa, exponent_char, exponent)
         } else if f.fract() == 0.0 {
             // make sure there's a fractional part even if the number doesn't have it
             output.write_fmt(format_args!("{:.1}", f))
@@ -404,6 +408,26 @@ mod tests {
         assert_eq!(compact(1.23e45), "1.23e+45");
         assert_eq!(compact(-3.14e-145), "-3.14e-145");
         assert_eq!(compact(1e300), "1e+300");
+        assert_eq!(compact(123456789.0), "1.23456789e+08");
+        assert_eq!(compact(1.0000001e-7), "1.0000001e-07");
+        assert_eq!(compact(0.1 + 0.2), "0.30000000000000004");
+    }
+
+    #[test]
+    fn test_repr_round_trips() {
+        for x in [
+            0.1 + 0.2,
+            123456789.0,
+            1.0 / 3.0,
+            -2.5e-300,
+            f64::MAX,
+            f64::MIN_POSITIVE,
+            5e-324,
+        ] {
+            assert_eq!(x, compact(x).parse::<f64>().unwrap(), "{}", compact(x));
+        }
+        assert::eq("'0.30000000000000004'", "repr(0.1 + 0.2)");
+        assert::eq("'1.23456789e+08'", "repr(123456789.0)");
     }
 
     #[test]
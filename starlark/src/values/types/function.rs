@@ -42,6 +42,7 @@ use crate::typing::tuple::TyTuple;
 use crate::typing::Ty;
 use crate::typing::TyBasic;
 use crate::values::type_repr::StarlarkTypeRepr;
+use crate::values::tuple::TupleRef;
 use crate::values::types::ellipsis::Ellipsis;
 use crate::values::typing::type_compiled::compiled::TypeCompiled;
 use crate::values::AllocFrozenValue;
@@ -59,12 +60,6 @@ use crate::values::ValueError;
 use crate::values::ValueLifetimeless;
 use crate::values::ValueLike;
 
-#[derive(Debug, thiserror::Error)]
-enum FunctionError {
-    #[error("`tuple[]` is implemented only for `tuple[T, ...]`")]
-    TupleOnlyEllipsis,
-}
-
 /// Return value of `type(any function)`.
 pub const FUNCTION_TYPE: &str = "function";
 
@@ -135,16 +130,39 @@ pub(crate) struct NativeFunction {
     #[allocative(skip)]
     pub(crate) function: Box<dyn NativeFunc>,
     pub(crate) name: String,
+    /// Name in call stacks and profiles, `name` if not set.
+    pub(crate) profile_name: Option<String>,
     /// `.type` attribute and a type when this function is used in type expression.
     pub(crate) as_type: Option<Ty>,
     pub(crate) ty: Ty,
     /// Safe to evaluate speculatively.
     pub(crate) speculative_exec_safe: bool,
+    /// Fails when determinism is enforced.
+    pub(crate) nondeterministic: bool,
     #[derivative(Debug = "ignore")]
     pub(crate) docs: DocItem,
     pub(crate) special_builtin_function: Option<SpecialBuiltinFunction>,
 }
 
+impl NativeFunction {
+    /// Custom name for profiles if the value is a native function declared with `profile_name`.
+    pub(crate) fn profile_name<'v>(value: Value<'v>) -> Option<&'v str> {
+        value.downcast_ref::<NativeFunction>()?.profile_name.as_deref()
+    }
+
+    /// Name of the function if the value is a native function or method
+    /// declared with `nondeterministic`.
+    pub(crate) fn nondeterministic_name<'v>(value: Value<'v>) -> Option<&'v str> {
+        if let Some(f) = value.downcast_ref::<NativeFunction>() {
+            f.nondeterministic.then_some(f.name.as_str())
+        } else if let Some(m) = value.downcast_ref::<NativeMethod>() {
+            m.nondeterministic.then_some(m.name.as_str())
+        } else {
+            None
+        }
+    }
+}
+
 impl AllocFrozenValue for NativeFunction {
     fn alloc_frozen_value(self, heap: &FrozenHeap) -> FrozenValue {
         heap.alloc_simple(self)
@@ -162,11 +180,15 @@ impl<'v> AllocValue<'v> for NativeFunction {
 impl<'v> StarlarkValue<'v> for NativeFunction {
     fn invoke(
         &self,
-        _me: Value<'v>,
+        me: Value<'v>,
         args: &Arguments<'v, '_>,
         eval: &mut Evaluator<'v, '_, '_>,
     ) -> crate::Result<Value<'v>> {
-        self.function.invoke(eval, args).map_err(Into::into)
+        eval.with_native_frame(me, |eval| self.function.invoke(eval, args))
+    }
+
+    fn name_for_call_stack(&self, _me: Value<'v>) -> String {
+        self.profile_name.as_ref().unwrap_or(&self.name).clone()
     }
 
     fn get_attr(&self, attribute: &str, heap: &'v Heap) -> Option<Value<'v>> {
@@ -213,6 +235,17 @@ impl<'v> StarlarkValue<'v> for NativeFunction {
                 let index = TypeCompiled::new(index, heap)?;
                 Ok(TypeCompiled::type_set_of(index, heap).to_inner())
             }
+            Some(SpecialBuiltinFunction::Tuple) => match TupleRef::from_value(index) {
+                // `tuple[(T0, T1, T2)]`, also the value of `tuple[T0, T1, T2]` in types.
+                Some(items) => {
+                    let items = items
+                        .iter()
+                        .map(|x| Ok(TypeCompiled::new(x, heap)?.as_ty().clone()))
+                        .collect::<crate::Result<Vec<_>>>()?;
+                    Ok(TypeCompiled::from_ty(&Ty::tuple(items), heap).to_inner())
+                }
+                None => ValueError::unsupported(self, "[]"),
+            },
             _ => ValueError::unsupported(self, "[]"),
         }
     }
@@ -231,18 +264,18 @@ impl<'v> StarlarkValue<'v> for NativeFunction {
                 Ok(TypeCompiled::type_dict_of(index0, index1, heap).to_inner())
             }
             Some(SpecialBuiltinFunction::Tuple) => {
-                let item = TypeCompiled::new(index0, heap)?;
-                if index1.downcast_ref::<Ellipsis>().is_some() {
-                    Ok(TypeCompiled::from_ty(
-                        &Ty::basic(TyBasic::Tuple(TyTuple::Of(ArcTy::new(
-                            item.as_ty().clone(),
-                        )))),
-                        heap,
-                    )
-                    .to_inner())
+                let item0 = TypeCompiled::new(index0, heap)?;
+                let ty = if index1.downcast_ref::<Ellipsis>().is_some() {
+                    // `tuple[T, ...]`.
+                    Ty::basic(TyBasic::Tuple(TyTuple::Of(ArcTy::new(
+                        item0.as_ty().clone(),
+                    ))))
                 } else {
-                    Err(crate::Error::new_other(FunctionError::TupleOnlyEllipsis))
-                }
+                    // `tuple[T0, T1]`.
+                    let item1 = TypeCompiled::new(index1, heap)?;
+                    Ty::tuple2(item0.as_ty().clone(), item1.as_ty().clone())
+                };
+                Ok(TypeCompiled::from_ty(&ty, heap).to_inner())
             }
             _ => ValueError::unsupported(self, "[,]"),
         }
@@ -260,6 +293,8 @@ pub(crate) struct NativeMethod {
     pub(crate) ty: Ty,
     /// Safe to evaluate speculatively.
     pub(crate) speculative_exec_safe: bool,
+    /// Fails when determinism is enforced.
+    pub(crate) nondeterministic: bool,
     #[derivative(Debug = "ignore")]
     pub(crate) docs: DocItem,
 }
@@ -356,9 +391,11 @@ where
         args: &Arguments<'v, '_>,
         eval: &mut Evaluator<'v, '_, '_>,
     ) -> crate::Result<Value<'v>> {
-        self.method
-            .function
-            .invoke(eval, self.this.to_value(), args)
+        eval.with_native_frame(self.method.to_value(), |eval| {
+            self.method
+                .function
+                .invoke(eval, self.this.to_value(), args)
+        })
     }
 
     fn documentation(&self) -> DocItem {
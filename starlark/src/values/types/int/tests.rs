@@ -48,6 +48,28 @@ fn test_minus() {
     assert::eq("2147483648", "-(-2147483647 - 1)")
 }
 
+#[test]
+fn test_overflow_to_big() {
+    // Starlark has no `**`, so build `2 ** 100` with shifts and multiplication.
+    assert::pass(
+        r#"
+big = 1 << 100
+assert_eq(big, 1024 * 1024 * 1024 * 1024 * 1024 * 1024 * 1024 * 1024 * 1024 * 1024)
+assert_eq(repr(big), "1267650600228229401496703205376")
+assert_eq(str(-big), "-1267650600228229401496703205376")
+assert_eq(2147483647 + 1, 2147483648)
+assert_eq(-2147483648 - 1, -2147483649)
+assert_eq(65536 * 65536, 4294967296)
+assert_eq(big + 1 - big, 1)
+assert_eq((big + 5) % 7, (big % 7 + 5) % 7)
+assert_eq(big // (1 << 98), 4)
+assert_true(big > 2147483647)
+assert_true(-big < -2147483648)
+assert_true(big != big + 1)
+"#,
+    );
+}
+
 #[test]
 fn test_int_tag() {
     fn check(x: InlineInt) {
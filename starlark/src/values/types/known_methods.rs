@@ -56,7 +56,7 @@ impl KnownMethod {
         args: &Arguments<'v, '_>,
         eval: &mut Evaluator<'v, '_, '_>,
     ) -> crate::Result<Value<'v>> {
-        self.imp.invoke(eval, this, args)
+        eval.with_native_frame(self.to_value(), |eval| self.imp.invoke(eval, this, args))
     }
 }
 
@@ -24,6 +24,7 @@ use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Formatter;
+use std::mem;
 use std::slice;
 
 use allocative::Allocative;
@@ -540,7 +541,11 @@ where
             Ok(None) => return None,
             Err(e) => return Some(Err(e)),
         };
-        let mut result = Vec::with_capacity(self.0.content().len() * cmp::max(0, l) as usize);
+        let len = self.0.content().len() * cmp::max(0, l) as usize;
+        if let Err(e) = heap.check_budget_for(len.saturating_mul(mem::size_of::<Value>())) {
+            return Some(Err(e));
+        }
+        let mut result = Vec::with_capacity(len);
         for _ in 0..l {
             result.extend(self.0.content().iter());
         }
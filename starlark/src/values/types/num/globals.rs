@@ -15,13 +15,68 @@
  * limitations under the License.
  */
 
+use std::cmp::Ordering;
+
+use num_bigint::BigInt;
 use starlark_derive::starlark_module;
 
 use crate as starlark;
 use crate::environment::GlobalsBuilder;
+use crate::values::none::NoneOr;
+use crate::values::types::int::int_or_big::StarlarkInt;
+use crate::values::types::int::int_or_big::StarlarkIntRef;
 use crate::values::types::num::value::Num;
 use crate::values::types::num::value::NumRef;
 
+/// Round an int to a multiple of `10 ** -ndigits`, ties to even.
+fn round_int(x: StarlarkIntRef, ndigits: i32) -> anyhow::Result<StarlarkInt> {
+    if ndigits >= 0 {
+        return Ok(x.to_owned());
+    }
+    let digits = x.to_string().trim_start_matches('-').len();
+    if ndigits.unsigned_abs() as usize > digits {
+        // `|x| < 10 ** -ndigits / 10`, so it rounds to zero,
+        // and we don't compute a huge power of ten.
+        return Ok(StarlarkInt::from(0));
+    }
+    let m = StarlarkInt::from(BigInt::from(10).pow(ndigits.unsigned_abs()));
+    let q = x.floor_div(m.as_ref())?;
+    // Non-negative, because `m` is positive.
+    let twice_r = x.percent(m.as_ref())?.as_ref() * 2;
+    let round_up = match twice_r.as_ref().cmp(&m.as_ref()) {
+        Ordering::Less => false,
+        Ordering::Equal => q.as_ref().percent(StarlarkInt::from(2).as_ref())?.as_ref() != 0,
+        Ordering::Greater => true,
+    };
+    let q = if round_up {
+        q.as_ref() + StarlarkInt::from(1).as_ref()
+    } else {
+        q
+    };
+    Ok(q.as_ref() * m.as_ref())
+}
+
+/// Round a float to `ndigits` decimal digits, ties to even.
+fn round_float(x: f64, ndigits: i32) -> f64 {
+    if !x.is_finite() {
+        x
+    } else if ndigits >= 0 {
+        // Formatting rounds the exact binary value, so `2.675` rounds down as in Python.
+        // Beyond 350 digits every finite float is already exact.
+        format!("{:.*}", ndigits.min(350) as usize, x)
+            .parse()
+            .unwrap_or(x)
+    } else {
+        let m = 10f64.powi(ndigits.saturating_neg());
+        if m.is_infinite() {
+            // `x / m` is zero, and `0 * inf` would be NaN.
+            0f64.copysign(x)
+        } else {
+            (x / m).round_ties_even() * m
+        }
+    }
+}
+
 #[starlark_module]
 pub(crate) fn register_num(globals: &mut GlobalsBuilder) {
     /// Take the absolute value of an int.
@@ -41,4 +96,45 @@ pub(crate) fn register_num(globals: &mut GlobalsBuilder) {
             NumRef::Float(a) => Ok(Num::Float(a.0.abs())),
         }
     }
+
+    /// [round](https://docs.python.org/3/library/functions.html#round):
+    /// round a number to `ndigits` decimal digits.
+    ///
+    /// Without `ndigits`, returns the nearest `int`.
+    /// Otherwise returns a number of the same type as `x`, and `ndigits` may be negative.
+    /// Ties are rounded to the even choice, as in Python.
+    ///
+    /// ```
+    /// # starlark::assert::all_true(r#"
+    /// round(2.5) == 2
+    /// round(3.5) == 4
+    /// round(-0.5) == 0
+    /// round(2.675, 2) == 2.67
+    /// round(1234.5, -1) == 1230.0
+    /// round(1.0, -400) == 0.0
+    /// repr(round(-1.0, -400)) == "-0.0"
+    /// round(1250, -2) == 1200
+    /// round(7) == 7
+    /// round((1 << 100) + 9, -1) == (1 << 100) + 4
+    /// round(1, -2000000000) == 0
+    /// round(-60, -2) == -100
+    /// # "#);
+    /// # starlark::assert::fail(r#"
+    /// round(float("nan"))  # error: cannot be represented as exact integer
+    /// # "#, "cannot be represented as exact integer");
+    /// ```
+    #[starlark(speculative_exec_safe)]
+    fn round(
+        #[starlark(require = pos)] x: NumRef,
+        #[starlark(default = NoneOr::None)] ndigits: NoneOr<i32>,
+    ) -> anyhow::Result<Num> {
+        match (x, ndigits) {
+            (NumRef::Int(a), NoneOr::None) => Ok(Num::Int(a.to_owned())),
+            (NumRef::Int(a), NoneOr::Other(n)) => Ok(Num::Int(round_int(a, n)?)),
+            (NumRef::Float(a), NoneOr::None) => Ok(Num::Int(StarlarkInt::from_f64_exact(
+                a.0.round_ties_even(),
+            )?)),
+            (NumRef::Float(a), NoneOr::Other(n)) => Ok(Num::Float(round_float(a.0, n))),
+        }
+    }
 }
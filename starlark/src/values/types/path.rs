@@ -0,0 +1,134 @@
+/*
+ * Copyright 2019 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Conversions between filesystem paths and Starlark strings.
+//!
+//! Starlark strings are UTF-8, so there is no `AllocValue` for paths:
+//! use [`Heap::alloc_path`](crate::values::Heap::alloc_path),
+//! which fails if the path is not valid UTF-8.
+
+use std::convert::Infallible;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::typing::Ty;
+use crate::values::type_repr::StarlarkTypeRepr;
+use crate::values::UnpackValue;
+use crate::values::Value;
+
+#[derive(Debug, thiserror::Error)]
+#[error("Path `{}` is not valid UTF-8", .0.display())]
+pub(crate) struct PathNotUtf8(pub(crate) PathBuf);
+
+impl StarlarkTypeRepr for PathBuf {
+    type Canonical = <String as StarlarkTypeRepr>::Canonical;
+
+    fn starlark_type_repr() -> Ty {
+        String::starlark_type_repr()
+    }
+}
+
+impl StarlarkTypeRepr for &Path {
+    type Canonical = <String as StarlarkTypeRepr>::Canonical;
+
+    fn starlark_type_repr() -> Ty {
+        String::starlark_type_repr()
+    }
+}
+
+impl<'v> UnpackValue<'v> for PathBuf {
+    type Error = Infallible;
+
+    fn unpack_value_impl(value: Value<'v>) -> Result<Option<Self>, Self::Error> {
+        Ok(value.unpack_str().map(PathBuf::from))
+    }
+}
+
+impl<'v> UnpackValue<'v> for &'v Path {
+    type Error = Infallible;
+
+    fn unpack_value_impl(value: Value<'v>) -> Result<Option<Self>, Self::Error> {
+        Ok(value.unpack_str().map(Path::new))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::path::PathBuf;
+
+    use starlark_derive::starlark_module;
+
+    use crate as starlark;
+    use crate::assert::Assert;
+    use crate::environment::GlobalsBuilder;
+    use crate::values::none::NoneOr;
+    use crate::values::Heap;
+    use crate::values::StringValue;
+
+    #[starlark_module]
+    fn path_functions(globals: &mut GlobalsBuilder) {
+        fn path_join<'v>(
+            dir: &Path,
+            name: PathBuf,
+            heap: &'v Heap,
+        ) -> starlark::Result<StringValue<'v>> {
+            heap.alloc_path(&dir.join(name))
+        }
+
+        fn path_parent<'v>(
+            path: &Path,
+            heap: &'v Heap,
+        ) -> starlark::Result<NoneOr<StringValue<'v>>> {
+            Ok(NoneOr::from_option(
+                path.parent().map(|p| heap.alloc_path(p)).transpose()?,
+            ))
+        }
+    }
+
+    #[test]
+    fn test_path_round_trip() {
+        let mut a = Assert::new();
+        a.globals_add(path_functions);
+        a.eq("'a/b/c.txt'", "path_join('a/b', 'c.txt')");
+        a.eq("'a/b'", "path_parent(path_join('a/b', 'c.txt'))");
+        a.eq("None", "path_parent('')");
+    }
+
+    #[test]
+    fn test_path_wrong_type() {
+        let mut a = Assert::new();
+        a.globals_add(path_functions);
+        a.fail("noop(path_join)(1, 'x')", "expected `str`, actual `int");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_path_not_utf8_is_error() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let heap = Heap::new();
+        let path = Path::new(OsStr::from_bytes(b"a/\xff.txt"));
+        let err = heap.alloc_path(path).unwrap_err();
+        assert!(
+            err.to_string().contains("is not valid UTF-8"),
+            "unexpected error: {err}"
+        );
+        assert_eq!("a/b", heap.alloc_path(Path::new("a/b")).unwrap().as_str());
+    }
+}
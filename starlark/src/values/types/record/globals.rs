@@ -239,6 +239,18 @@ assert_ne(r1(host="test"), diff(host="test"))
         );
     }
 
+    #[test]
+    fn test_record_repr() {
+        assert::pass(
+            r#"
+rec_type = record(host=str, port=field(int, 80))
+assert_eq(repr(rec_type(host="h")), 'record[rec_type](host="h", port=80)')
+assert_eq(str(rec_type(host="h", port=1)), 'record[rec_type](host="h", port=1)')
+assert_eq(repr(rec_type), "record(host=field(str), port=field(int, 80))")
+"#,
+        );
+    }
+
     #[test]
     fn test_field_invalid() {
         assert::fails(
@@ -105,7 +105,7 @@ impl<'v> SetData<'v> {
 #[repr(transparent)]
 pub(crate) struct FrozenSetData {
     /// The data stored by the set. The values must all be hashable values.
-    content: SmallSet<FrozenValue>,
+    pub(crate) content: SmallSet<FrozenValue>,
 }
 
 pub(crate) type MutableSet<'v> = SetGen<RefCell<SetData<'v>>>;
@@ -469,4 +469,28 @@ mod tests {
             "Operation `-` not supported for types `set` and `list`",
         );
     }
+
+    #[test]
+    fn test_literal() {
+        assert::eq("{1, 2, 3}", "set([1, 2, 3])");
+        assert::eq("{1, 2, 1,}", "set([1, 2])");
+        assert::eq("type({1})", "'set'");
+        assert::eq("[x for x in {3, 1, 2, 1}]", "[3, 1, 2]");
+        // Empty braces are still a dict.
+        assert::eq("type({})", "'dict'");
+    }
+
+    #[test]
+    fn test_literal_ops() {
+        assert::eq("{1, 2} | {2, 3}", "{1, 2, 3}");
+        assert::eq("{1, 2} & {2, 3}", "{2}");
+        assert::eq("{1, 2} - {2, 3}", "{1}");
+        assert::is_true("2 in {1, 2}");
+        assert::is_true("3 not in {1, 2}");
+    }
+
+    #[test]
+    fn test_literal_unhashable() {
+        assert::fail("def f(x): return {x, [x]}\nf(1)", "not hashable");
+    }
 }
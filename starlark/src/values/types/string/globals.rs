@@ -104,7 +104,7 @@ pub(crate) fn register_str(globals: &mut GlobalsBuilder) {
     /// repr("x\"y😿 \\'")      == "\"x\\\"y\\U0001f63f \\\\'\""
     /// # "#);
     /// ```
-    #[starlark(speculative_exec_safe)]
+    #[starlark(speculative_exec_safe(allow_eval))]
     fn repr<'v>(
         #[starlark(require = pos)] a: Value<'v>,
         eval: &mut Evaluator<'v, '_, '_>,
@@ -131,7 +131,7 @@ pub(crate) fn register_str(globals: &mut GlobalsBuilder) {
     /// str([1, "x"])                   == "[1, \"x\"]"
     /// # "#);
     /// ```
-    #[starlark(as_type = StarlarkStr, speculative_exec_safe)]
+    #[starlark(as_type = StarlarkStr, speculative_exec_safe(allow_eval))]
     fn str<'v>(
         #[starlark(require = pos)] a: Value<'v>,
         eval: &mut Evaluator<'v, '_, '_>,
@@ -141,7 +141,7 @@ pub(crate) fn register_str(globals: &mut GlobalsBuilder) {
             Ok(a)
         } else {
             let mut s = eval.string_pool.alloc();
-            a.collect_repr(&mut s);
+            a.collect_str(&mut s);
             let r = eval.heap().alloc_str(&s);
             eval.string_pool.release(s);
             Ok(r)
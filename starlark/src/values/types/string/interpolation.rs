@@ -217,13 +217,7 @@ pub(crate) fn percent(format: &str, value: Value) -> crate::Result<String> {
         res.push_str(item.literal);
         match item.format {
             None => {}
-            Some(PercentSFormat::Str) => {
-                let arg = next_value()?;
-                match arg.unpack_str() {
-                    None => arg.collect_repr(&mut res),
-                    Some(s) => res.push_str(s),
-                }
-            }
+            Some(PercentSFormat::Str) => next_value()?.collect_str(&mut res),
             Some(PercentSFormat::Repr) => next_value()?.collect_repr(&mut res),
             Some(PercentSFormat::Dec) => {
                 let value = next_value()?;
@@ -328,7 +328,7 @@ pub(crate) fn string_methods(builder: &mut MethodsBuilder) {
     /// "Is {0!r} {0!s}?".format("heterological") == "Is \"heterological\" heterological?"
     /// # "#);
     /// ```
-    #[starlark(speculative_exec_safe)]
+    #[starlark(speculative_exec_safe(allow_eval))]
     fn format<'v>(
         this: &str,
         args: &Arguments<'v, '_>,
@@ -338,7 +338,7 @@ pub(crate) fn string_methods(builder: &mut MethodsBuilder) {
         dot_format::format(
             this,
             iter,
-            args.names()?,
+            args.names_dict()?,
             &mut eval.string_pool,
             eval.module_env.heap(),
         )
@@ -1082,12 +1082,13 @@ pub(crate) fn string_methods(builder: &mut MethodsBuilder) {
     /// "one\n\ntwo".splitlines() == ["one", "", "two"]
     /// "one\n\ntwo".splitlines(True) == ["one\n", "\n", "two"]
     /// "a\nb".splitlines() == ["a", "b"]
+    /// "a\r\nb\rc".splitlines(keepends = True) == ["a\r\n", "b\r", "c"]
     /// # "#);
     /// ```
     #[starlark(speculative_exec_safe)]
     fn splitlines<'v>(
         this: &str,
-        #[starlark(require = pos, default = false)] keepends: bool,
+        #[starlark(default = false)] keepends: bool,
         heap: &'v Heap,
     ) -> anyhow::Result<Vec<StringValue<'v>>> {
         let mut s = this;
@@ -1256,9 +1257,9 @@ pub(crate) fn string_methods(builder: &mut MethodsBuilder) {
 
     /// [string.removesuffix](
     /// https://docs.python.org/3.9/library/stdtypes.html#str.removesuffix
-    /// ): remove a prefix from a string. _Not part of standard Starlark._
+    /// ): remove a suffix from a string. _Not part of standard Starlark._
     ///
-    /// If the string starts with the prefix string, return `string[len(prefix):]`.
+    /// If the string ends with the suffix string, return `string[:-len(suffix)]`.
     /// Otherwise, return a copy of the original string:
     ///
     /// ```
@@ -1304,6 +1305,29 @@ mod tests {
         assert::eq("'Троянская война окончена'.find('война')", "10");
     }
 
+    #[test]
+    fn test_removeprefix_removesuffix() {
+        assert::eq("'abc'.removeprefix('')", "'abc'");
+        assert::eq("''.removeprefix('a')", "''");
+        assert::eq("'abc'.removeprefix('abcd')", "'abc'");
+        assert::eq("'abc'.removesuffix('')", "'abc'");
+        assert::eq("''.removesuffix('a')", "''");
+        assert::eq("'abc'.removesuffix('zabc')", "'abc'");
+    }
+
+    #[test]
+    fn test_splitlines() {
+        assert::eq("''.splitlines()", "[]");
+        assert::eq("'abc'.splitlines()", "['abc']");
+        assert::eq("'\\n'.splitlines()", "['']");
+        assert::eq("'a\\r\\nb\\rc\\n\\nd'.splitlines()", "['a', 'b', 'c', '', 'd']");
+        assert::eq(
+            "'a\\r\\nb\\rc\\n'.splitlines(keepends = True)",
+            "['a\\r\\n', 'b\\r', 'c\\n']",
+        );
+        assert::eq("'\\r\\n'.splitlines(True)", "['\\r\\n']");
+    }
+
     #[test]
     fn test_opaque_iterator() {
         assert::is_true("type('foo'.elems()) != type([])");
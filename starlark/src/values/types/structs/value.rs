@@ -179,6 +179,15 @@ where
         self.fields.keys().map(|x| x.as_str().to_owned()).collect()
     }
 
+    fn get_attrs(&self, _heap: &'v Heap) -> Option<Vec<(String, Value<'v>)>> {
+        Some(
+            coerce(&self.fields)
+                .iter()
+                .map(|(k, v)| (k.as_str().to_owned(), *v))
+                .collect(),
+        )
+    }
+
     fn documentation(&self) -> DocItem {
         // This treats structs as being value-like, and intentionally generates bad docs in the case
         // of namespace-like usage. See
@@ -210,6 +219,7 @@ impl<'v, V: ValueLike<'v>> Serialize for StructGen<'v, V> {
 mod tests {
 
     use crate::assert;
+    use crate::values::Heap;
 
     #[test]
     fn test_repr() {
@@ -262,6 +272,25 @@ json.encode(struct(foo = [struct(bar = "some")])) == '{"foo":[{"bar":"some"}]}'
         );
     }
 
+    #[test]
+    fn test_get_attrs() {
+        let heap = Heap::new();
+        let s = assert::Assert::new().pass_module("s = struct(b = 1, a = [], c = 'x')");
+        let s = s.get("s").unwrap();
+        let attrs = s.value().get_attrs(&heap).unwrap().unwrap();
+        let attrs: Vec<(String, String)> =
+            attrs.into_iter().map(|(k, v)| (k, v.to_repr())).collect();
+        assert_eq!(
+            vec![
+                ("b".to_owned(), "1".to_owned()),
+                ("a".to_owned(), "[]".to_owned()),
+                ("c".to_owned(), "\"x\"".to_owned()),
+            ],
+            attrs
+        );
+        assert!(heap.alloc(1).get_attrs(&heap).unwrap().is_none());
+    }
+
     #[test]
     fn test_comparison_bug() {
         // TODO(nga): this should be false, because `a < b`,
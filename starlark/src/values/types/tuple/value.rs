@@ -315,4 +315,27 @@ str((1,)) == "(1,)"
         assert::is_true("isinstance((1, 2), tuple[int, ...])");
         assert::is_false("isinstance(('x', 2), tuple[int, ...])");
     }
+
+    #[test]
+    fn test_tuple_elems_runtime() {
+        assert::is_true("isinstance((1, 'x'), tuple[int, str])");
+        assert::is_false("isinstance(('x', 1), tuple[int, str])");
+        assert::is_false("isinstance((1, 'x', 2), tuple[int, str])");
+        assert::fail(
+            "def f(t: tuple[int, str]): pass\nf((1, 2))",
+            "Value `(1, 2)` of type `tuple` does not match the type annotation `(int, str)`",
+        );
+    }
+
+    #[test]
+    fn test_tuple_three_elems_runtime() {
+        assert::is_true("def f(t: tuple[int, str, bool]): pass\nf((1, 'x', True))\nTrue");
+        assert::is_true("isinstance((1, 'x', True), tuple[(int, str, bool)])");
+        assert::is_false("isinstance((1, 'x', 2), tuple[(int, str, bool)])");
+        assert::is_false("isinstance((1, 'x'), tuple[(int, str, bool)])");
+        assert::fail(
+            "def f(t: tuple[int, str, bool]): pass\nf((1, 'x', 2))",
+            "Value `(1, \"x\", 2)` of type `tuple` does not match the type annotation `(int, str, bool)`",
+        );
+    }
 }
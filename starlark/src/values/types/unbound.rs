@@ -89,7 +89,9 @@ impl UnboundValue {
             self.to_frozen_value().to_value(),
             Some(span),
             |eval| match self {
-                UnboundValue::Method(_, m) => m.invoke(eval, this, args),
+                UnboundValue::Method(m, imp) => {
+                    eval.with_native_frame(m.to_value(), |eval| imp.invoke(eval, this, args))
+                }
                 UnboundValue::Attr(_, a) => {
                     NativeAttribute::invoke_method_impl(&**a, this, args, eval)
                 }
@@ -21,6 +21,7 @@ pub(crate) mod any;
 pub(crate) mod callable;
 pub(crate) mod globals;
 pub(crate) mod iter;
+pub(crate) mod literal;
 pub mod macro_refs;
 pub(crate) mod never;
 pub(crate) mod ty;
@@ -19,6 +19,7 @@ use crate::environment::GlobalsBuilder;
 use crate::values::typing::any::TypingAny;
 use crate::values::typing::callable::TypingCallable;
 use crate::values::typing::iter::TypingIterable;
+use crate::values::typing::literal::TypingLiteral;
 use crate::values::typing::never::TypingNever;
 use crate::values::typing::type_compiled::globals::register_eval_type;
 
@@ -29,5 +30,6 @@ pub(crate) fn register_typing(globals: &mut GlobalsBuilder) {
         globals.set("Never", TypingNever);
         globals.set("Callable", TypingCallable);
         globals.set("Iterable", TypingIterable);
+        globals.set("Literal", TypingLiteral);
     });
 }
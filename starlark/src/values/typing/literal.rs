@@ -0,0 +1,156 @@
+/*
+ * Copyright 2019 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use allocative::Allocative;
+use starlark_derive::starlark_value;
+use starlark_derive::NoSerialize;
+use starlark_derive::ProvidesStaticType;
+
+use crate as starlark;
+use crate::private::Private;
+use crate::typing::literal::TyLiteral;
+use crate::typing::Ty;
+use crate::values::layout::avalue::alloc_static;
+use crate::values::layout::avalue::AValueBasic;
+use crate::values::layout::avalue::AValueImpl;
+use crate::values::layout::heap::repr::AValueRepr;
+use crate::values::tuple::TupleRef;
+use crate::values::typing::TypeCompiled;
+use crate::values::AllocFrozenValue;
+use crate::values::FrozenHeap;
+use crate::values::FrozenValue;
+use crate::values::Heap;
+use crate::values::StarlarkValue;
+use crate::values::Value;
+
+#[derive(Debug, thiserror::Error)]
+enum TypingLiteralError {
+    #[error("`typing.Literal` arguments must be strings, got `{0}`")]
+    NotString(String),
+}
+
+#[derive(
+    Debug,
+    derive_more::Display,
+    Allocative,
+    ProvidesStaticType,
+    NoSerialize
+)]
+#[display("{}", Self::TYPE)]
+pub(crate) struct TypingLiteral;
+
+impl TypingLiteral {
+    fn literal<'v>(values: &[Value<'v>], heap: &'v Heap) -> crate::Result<Value<'v>> {
+        let mut strings = Vec::with_capacity(values.len());
+        for v in values {
+            match v.unpack_str() {
+                Some(s) => strings.push(s.to_owned()),
+                None => {
+                    return Err(crate::Error::new_other(TypingLiteralError::NotString(
+                        v.to_repr(),
+                    )));
+                }
+            }
+        }
+        Ok(TypeCompiled::from_ty(&Ty::custom(TyLiteral::new(strings)), heap).to_inner())
+    }
+}
+
+#[starlark_value(type = "typing.Literal")]
+impl<'v> StarlarkValue<'v> for TypingLiteral {
+    fn at(&self, index: Value<'v>, heap: &'v Heap) -> crate::Result<Value<'v>> {
+        match TupleRef::from_value(index) {
+            // `typing.Literal[("a", "b", "c")]`, also the value of
+            // `typing.Literal["a", "b", "c"]` in types.
+            Some(items) => Self::literal(items.content(), heap),
+            None => Self::literal(&[index], heap),
+        }
+    }
+
+    fn at2(
+        &self,
+        index0: Value<'v>,
+        index1: Value<'v>,
+        heap: &'v Heap,
+        _private: Private,
+    ) -> crate::Result<Value<'v>> {
+        Self::literal(&[index0, index1], heap)
+    }
+}
+
+impl AllocFrozenValue for TypingLiteral {
+    fn alloc_frozen_value(self, _heap: &FrozenHeap) -> FrozenValue {
+        static LITERAL: AValueRepr<AValueImpl<'static, AValueBasic<TypingLiteral>>> =
+            alloc_static(TypingLiteral);
+
+        FrozenValue::new_repr(&LITERAL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::assert;
+
+    #[test]
+    fn test_literal_runtime() {
+        assert::is_true("isinstance('fast', typing.Literal['fast', 'slow'])");
+        assert::is_true("isinstance('a', typing.Literal['a'])");
+        assert::is_false("isinstance('x', typing.Literal['fast', 'slow'])");
+        assert::is_false("isinstance(1, typing.Literal['fast', 'slow'])");
+        assert::eq(
+            "'typing.Literal[\"fast\", \"slow\"]'",
+            "str(typing.Literal['slow', 'fast'])",
+        );
+        assert::fail("typing.Literal[1]", "must be strings, got `1`");
+    }
+
+    #[test]
+    fn test_literal_param_runtime() {
+        assert::fail(
+            r#"
+def f(mode: typing.Literal["fast", "slow"]):
+    pass
+
+f(str("x"))
+"#,
+            "Value `x` of type `string` does not match the type annotation",
+        );
+    }
+
+    #[test]
+    fn test_literal_many() {
+        assert::is_true("isinstance('c', typing.Literal[('a', 'b', 'c')])");
+        assert::is_false("isinstance('d', typing.Literal[('a', 'b', 'c')])");
+        assert::fail(
+            r#"
+def f(mode: typing.Literal["a", "b", "c"]):
+    pass
+
+f("c")
+f(str("d"))
+"#,
+            "Value `d` of type `string` does not match the type annotation",
+        );
+    }
+
+    #[test]
+    fn test_literal_union() {
+        assert::is_true(
+            "isinstance('c', typing.Literal['a', 'b'] | typing.Literal['c'])",
+        );
+    }
+}
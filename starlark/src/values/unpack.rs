@@ -18,7 +18,9 @@
 //! Parameter conversion utilities for `starlark_module` macros.
 
 use std::convert::Infallible;
+use std::fmt;
 use std::fmt::Debug;
+use std::fmt::Display;
 
 use anyhow::Context;
 use either::Either;
@@ -226,36 +228,106 @@ pub trait UnpackValue<'v>: Sized + StarlarkTypeRepr {
             .ok_or_else(|| error(value, Self::starlark_type_repr))
     }
 
+    /// Explain why `value` did not unpack, when it is more specific than a type mismatch,
+    /// for example which entry of a container has the wrong type.
+    ///
+    /// Only called after [`unpack_value_impl`](UnpackValue::unpack_value_impl) returned `None`,
+    /// and appended to parameter type errors.
+    fn unpack_mismatch_reason(_value: Value<'v>) -> Option<String> {
+        None
+    }
+
     /// Unpack value, but instead of `None` return error about incorrect named argument type.
     #[inline]
     fn unpack_named_param(value: Value<'v>, param_name: &str) -> anyhow::Result<Self> {
-        #[cold]
-        fn error<'v>(value: Value<'v>, param_name: &str, ty: fn() -> Ty) -> anyhow::Error {
-            #[derive(thiserror::Error, Debug)]
-            #[error("Type of parameter `{0}` doesn't match, expected `{1}`, actual `{2}`")]
-            struct IncorrectParameterTypeNamedWithExpected(String, Ty, String);
+        unpack_named_param_at(value, param_name, None)
+    }
+}
 
-            crate::Error::new_value(IncorrectParameterTypeNamedWithExpected(
-                param_name.to_owned(),
-                ty(),
-                value.to_string_for_type_error(),
-            ))
-            .into_anyhow()
+/// Argument position suffix for parameter type errors.
+#[derive(Debug)]
+struct ArgumentIndex(Option<usize>);
+
+impl Display for ArgumentIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            // Report 1-based, as users count arguments.
+            Some(index) => write!(f, " (argument #{})", index + 1),
+            None => Ok(()),
         }
+    }
+}
 
-        Self::unpack_value(value)
-            .into_anyhow_result()
-            .with_context(|| {
-                format!(
-                    "Error unpacking value for parameter `{}` of type `{}",
-                    param_name,
-                    Self::starlark_type_repr()
-                )
-            })?
-            .ok_or_else(|| error(value, param_name, Self::starlark_type_repr))
+/// Suffix for parameter type errors from [`UnpackValue::unpack_mismatch_reason`].
+#[derive(Debug)]
+struct MismatchReason(Option<String>);
+
+impl Display for MismatchReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Some(reason) => write!(f, ": {reason}"),
+            None => Ok(()),
+        }
     }
 }
 
+/// Implementation of [`UnpackValue::unpack_named_param`]
+/// which also reports the 0-based positional `index` of the parameter if given.
+#[inline]
+pub(crate) fn unpack_named_param_at<'v, T: UnpackValue<'v>>(
+    value: Value<'v>,
+    param_name: &str,
+    index: Option<usize>,
+) -> anyhow::Result<T> {
+    #[cold]
+    fn error<'v>(
+        value: Value<'v>,
+        param_name: &str,
+        index: Option<usize>,
+        ty: fn() -> Ty,
+        reason: fn(Value<'v>) -> Option<String>,
+    ) -> anyhow::Error {
+        #[derive(thiserror::Error, Debug)]
+        #[error("Type of parameter `{0}` doesn't match, expected `{1}`, actual `{2}`{3}{4}")]
+        struct IncorrectParameterTypeNamedWithExpected(
+            String,
+            Ty,
+            String,
+            ArgumentIndex,
+            MismatchReason,
+        );
+
+        crate::Error::new_value(IncorrectParameterTypeNamedWithExpected(
+            param_name.to_owned(),
+            ty(),
+            value.to_string_for_type_error(),
+            ArgumentIndex(index),
+            MismatchReason(reason(value)),
+        ))
+        .into_anyhow()
+    }
+
+    T::unpack_value(value)
+        .into_anyhow_result()
+        .with_context(|| {
+            format!(
+                "Error unpacking value for parameter `{}` of type `{}{}",
+                param_name,
+                T::starlark_type_repr(),
+                ArgumentIndex(index),
+            )
+        })?
+        .ok_or_else(|| {
+            error(
+                value,
+                param_name,
+                index,
+                T::starlark_type_repr,
+                T::unpack_mismatch_reason,
+            )
+        })
+}
+
 impl<'v> UnpackValue<'v> for Value<'v> {
     type Error = Infallible;
 
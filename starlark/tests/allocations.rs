@@ -0,0 +1,110 @@
+/*
+ * Copyright 2018 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tests which count allocations with a global allocator.
+//!
+//! This is a separate test binary, so the allocator does not affect other tests.
+
+use std::alloc::GlobalAlloc;
+use std::alloc::Layout;
+use std::alloc::System;
+use std::cell::Cell;
+
+use starlark::assert::Assert;
+use starlark::environment::GlobalsBuilder;
+use starlark::starlark_module;
+
+/// Global allocator which counts the allocations made by each thread,
+/// so tests can check that a code path does not allocate.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATION_COUNT: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATION_COUNT.try_with(|c| c.set(c.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCATION_COUNT.try_with(|c| c.set(c.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Number of allocations made by the current thread so far.
+fn allocation_count() -> usize {
+    ALLOCATION_COUNT.with(|c| c.get())
+}
+
+#[test]
+fn test_positional_call_does_not_allocate() {
+    #[starlark_module]
+    fn helpers(builder: &mut GlobalsBuilder) {
+        fn add2(
+            #[starlark(require = pos)] x: i32,
+            #[starlark(require = pos)] y: i32,
+        ) -> anyhow::Result<i32> {
+            Ok(x + y)
+        }
+
+        fn allocation_count() -> anyhow::Result<i32> {
+            Ok(crate::allocation_count() as i32)
+        }
+    }
+
+    let mut a = Assert::new();
+    a.disable_gc();
+    a.globals_add(helpers);
+    a.pass(
+        r#"
+def loop(n):
+    x = 0
+    for i in range(n):
+        x = add2(x, 1)
+    return x
+
+def loop_star_args(n):
+    x = 0
+    args = (1, 1)
+    for i in range(n):
+        x = add2(*args)
+    return x
+
+def allocations(f, n):
+    before = allocation_count()
+    f(n)
+    return allocation_count() - before
+
+# Warm up: the first call may allocate.
+loop(10)
+loop_star_args(10)
+# The number of allocations does not grow with the number of calls.
+assert_eq(allocations(loop, 100), allocations(loop, 10000))
+assert_eq(allocations(loop_star_args, 100), allocations(loop_star_args, 10000))
+"#,
+    );
+}
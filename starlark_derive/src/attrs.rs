@@ -198,6 +198,17 @@ pub fn starlark_attrs() -> TokenStream {
         fn dir_attr(&self) -> Vec<String> {
             self.attrs_dir_attr()
         }
+        fn get_attrs(&self, heap: &'v starlark::values::Heap) -> Option<Vec<(String, starlark::values::Value<'v>)>> {
+            Some(
+                self.attrs_dir_attr()
+                    .into_iter()
+                    .filter_map(|attr| {
+                        let value = self.attrs_get_attr(&attr, heap)?;
+                        Some((attr, value))
+                    })
+                    .collect(),
+            )
+        }
     };
     expanded.into()
 }
@@ -33,6 +33,7 @@ mod serde;
 mod starlark_type_repr;
 mod starlark_value;
 mod trace;
+mod unpack_enum;
 mod unpack_value;
 mod util;
 mod v_lifetime;
@@ -76,6 +77,12 @@ mod vtable;
 ///   is considered safe to execute speculatively: the function should have
 ///   no global side effects, should not panic, and should finish in reasonable time.
 ///   The evaluator may invoke such functions early to generate more efficient code.
+///   Such functions cannot take an `&mut Evaluator` parameter,
+///   unless written as `#[starlark(speculative_exec_safe(allow_eval))]`, which asserts that
+///   the evaluator is only used for scratch state (e.g. its string pool) or to invoke values.
+/// * `#[starlark(nondeterministic)]` - the function result may differ between runs
+///   (e.g. it reads the time or the environment), so it fails when called by an evaluator
+///   with `Evaluator::enforce_determinism` set.
 /// * `#[starlark(attribute)]` to turn the name into
 ///   an attribute on the value. Such a function must take exactly one argument, namely a value
 ///   of the type you have attached it to.
@@ -83,10 +90,19 @@ mod vtable;
 /// Multiple attributes can be specified either separately `#[starlark(require = named)] #[starlark(default = "")]` or
 /// separated with a comman `#[starlark(require = named, default = "")]`.
 ///
-/// There are two special arguments, distinguished by their type, which provides access to interpreter state:
+/// There are three special arguments, distinguished by their type, which provides access to interpreter state:
 ///
 /// * `heap: &'v Heap` gives access to the Starlark heap, for allocating things.
 /// * `eval: &mut Evaluator<'v, '_, '_>` gives access to the Starlark evaluator, which can be used to look at interpreter state.
+/// * `call_site: Option<FileSpan>` is the location of the call, useful for diagnostics.
+///   It is [`None`] if the function was called from a native function rather than from Starlark code.
+///
+/// Functions return `anyhow::Result<T>`, `starlark::Result<T>` or `Result<T, E>`,
+/// where `E` implements `starlark::errors::NativeCallableError`,
+/// which lets custom error types choose the `starlark::ErrorKind` reported to callers.
+///
+/// A function declared as `async fn` is resolved by blocking on the handle
+/// set with `Evaluator::set_blocking_handle`. Such functions cannot take `&mut Evaluator`.
 ///
 /// A module can be used to define globals (with `GlobalsBuilder`) or methods on an object (with `MethodsBuilder`).
 /// In the case of methods, the first argument to each function will be the object itself, typically named `this`.
@@ -95,6 +111,10 @@ mod vtable;
 ///
 /// If a desired function name is also a Rust keyword, use the `r#` prefix, e.g. `r#type`.
 ///
+/// A `const NAME: T = expr;` item defines a global value. `expr` need not be a Rust constant
+/// expression: it is evaluated once, when the globals are built, and the result is allocated
+/// on the globals heap, so `const X: i32 = compute();` calls an ordinary function.
+///
 /// As a more complex example:
 ///
 /// ```ignore
@@ -140,6 +160,9 @@ pub fn derive_visit_span_mut(input: proc_macro::TokenStream) -> proc_macro::Toke
 }
 
 /// Derive the `Trace` trait.
+///
+/// Fields are skipped with `#[trace(unsafe_ignore)]` or `#[trace(static)]` only:
+/// other attributes such as `#[allocative(skip)]` do not affect tracing.
 #[proc_macro_derive(Trace, attributes(trace))]
 pub fn derive_trace(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     trace::derive_trace(input)
@@ -169,6 +192,16 @@ pub fn derive_unpack_value(input: proc_macro::TokenStream) -> proc_macro::TokenS
     unpack_value::derive_unpack_value(input)
 }
 
+/// Derive `UnpackValue`, `AllocValue` and `StarlarkTypeRepr` for an enum with unit variants,
+/// represented in Starlark as strings.
+///
+/// Variant names are converted to snake case (`FastMode` becomes `"fast_mode"`),
+/// or can be given explicitly with `#[starlark(name = "...")]`.
+#[proc_macro_derive(UnpackEnum, attributes(starlark))]
+pub fn derive_unpack_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    unpack_enum::derive_unpack_enum(input)
+}
+
 /// Derive the `AllocValue` trait.
 #[proc_macro_derive(AllocValue)]
 pub fn derive_alloc_value(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -200,6 +233,19 @@ pub fn starlark_attrs(_: proc_macro::TokenStream) -> proc_macro::TokenStream {
 
 /// Generate missing elements of `StarlarkValue` trait when this attribute
 /// is applied to an impl block of `StarlarkValue`.
+///
+/// * `#[starlark_value(type = "foo", repr = path::to_fn)]` - implement `collect_repr`
+///   by calling `fn(&Self, &mut String)`, so `repr()` can differ from `Display`-based `str()`.
+///   Unless `collect_str` is implemented explicitly, `str()` keeps using `Display`.
+/// * `#[starlark_value(type = "foo", eq = path::to_fn)]` - implement `equals`
+///   by calling `fn(&Self, &Self) -> bool` when the other value has the same type.
+///   Values of other types are never equal. If `write_hash` is implemented, it must agree with this function.
+/// * `#[starlark_value(type = "foo", iterate = path::to_fn)]` - implement `iterate_collect`
+///   by calling `fn(&Self, &'v Heap) -> impl IntoIterator<Item = Value<'v>>`, so `for x in foo:` works.
+/// * `#[starlark_value(type = "foo", add = path::to_fn)]` - implement `+` by calling
+///   `fn(&Self, &Self, &'v Heap) -> starlark::Result<Value<'v>>` when both operands are of this type.
+///   Likewise `sub`, `mul`, `div`, `floor_div`, `percent`, `bit_and`, `bit_or` and `bit_xor`.
+///   Other operand types are reported as unsupported.
 #[proc_macro_attribute]
 pub fn starlark_value(
     attr: proc_macro::TokenStream,
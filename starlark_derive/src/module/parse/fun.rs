@@ -49,6 +49,8 @@ use crate::module::typ::StarFun;
 use crate::module::typ::StarFunSource;
 use crate::module::typ::StarStmt;
 use crate::module::typ::ThisParam;
+use crate::module::util::is_type_name;
+use crate::module::util::unpack_option;
 use crate::util::GenericsUtil;
 
 #[derive(Default)]
@@ -58,6 +60,10 @@ struct FnAttrs {
     starlark_ty_custom_function: Option<Expr>,
     special_builtin_function: Option<Expr>,
     speculative_exec_safe: bool,
+    /// `speculative_exec_safe(allow_eval)`.
+    speculative_exec_safe_allow_eval: bool,
+    nondeterministic: bool,
+    profile_name: Option<syn::LitStr>,
     docstring: Option<String>,
     /// Rest attributes
     attrs: Vec<Attribute>,
@@ -198,6 +204,21 @@ fn parse_starlark_fn_attr(tokens: &Attribute, attrs: &mut FnAttrs) -> syn::Resul
                 continue;
             } else if ident == "speculative_exec_safe" {
                 attrs.speculative_exec_safe = true;
+                if parser.peek(syn::token::Paren) {
+                    let content;
+                    syn::parenthesized!(content in parser);
+                    let arg = content.parse::<Ident>()?;
+                    if arg != "allow_eval" || !content.is_empty() {
+                        return Err(syn::Error::new(
+                            arg.span(),
+                            "Expecting `#[starlark(speculative_exec_safe(allow_eval))]`",
+                        ));
+                    }
+                    attrs.speculative_exec_safe_allow_eval = true;
+                }
+                continue;
+            } else if ident == "nondeterministic" {
+                attrs.nondeterministic = true;
                 continue;
             } else if ident == "ty_custom_function" {
                 parser.parse::<Token![=]>()?;
@@ -207,6 +228,10 @@ fn parse_starlark_fn_attr(tokens: &Attribute, attrs: &mut FnAttrs) -> syn::Resul
                 parser.parse::<Token![=]>()?;
                 attrs.special_builtin_function = Some(parser.parse::<Expr>()?);
                 continue;
+            } else if ident == "profile_name" {
+                parser.parse::<Token![=]>()?;
+                attrs.profile_name = Some(parser.parse::<syn::LitStr>()?);
+                continue;
             }
             return Err(syn::Error::new(
                 ident.span(),
@@ -214,7 +239,10 @@ fn parse_starlark_fn_attr(tokens: &Attribute, attrs: &mut FnAttrs) -> syn::Resul
                     `#[starlark(as_type = ImplStarlarkValue)]`, \
                     `#[starlark(ty_custom_function = MyTy)]`, \
                     `#[starlark(attribute)]`, \
-                    `#[starlark(speculative_exec_safe)]` attribute",
+                    `#[starlark(speculative_exec_safe)]`, \
+                    `#[starlark(speculative_exec_safe(allow_eval))]`, \
+                    `#[starlark(nondeterministic)]`, \
+                    `#[starlark(profile_name = \"name\")]` attribute",
             ));
         }
 
@@ -247,11 +275,18 @@ fn parse_fn_attrs(span: Span, xs: Vec<Attribute>) -> syn::Result<FnAttrs> {
     if res.is_attribute && res.as_type.is_some() {
         return Err(syn::Error::new(span, "Can't be an attribute with a .type"));
     }
+    if res.speculative_exec_safe && res.nondeterministic {
+        return Err(syn::Error::new(
+            span,
+            "Can't be both `speculative_exec_safe` and `nondeterministic`",
+        ));
+    }
     Ok(res)
 }
 
-/// Check if given type is `anyhow::Result<T>` or `starlark::Result<T>`
-fn is_anyhow_or_starlark_result(t: &Type) -> bool {
+/// Is the type `anyhow::Result<T>`, `starlark::Result<T>`, or `Result<T, E>`
+/// (possibly written as `std::result::Result<T, E>`).
+fn is_supported_result(t: &Type) -> bool {
     let path = match t {
         Type::Path(p) => p,
         _ => return false,
@@ -259,30 +294,25 @@ fn is_anyhow_or_starlark_result(t: &Type) -> bool {
     if path.qself.is_some() {
         return false;
     }
-    let mut segments = path.path.segments.iter();
-    match segments.next() {
-        None => return false,
-        Some(s) if s.ident != "anyhow" && s.ident != "starlark" => return false,
-        _ => {}
-    };
-    let result = match segments.next() {
-        None => return false,
-        Some(s) if s.ident != "Result" => return false,
-        Some(result) => result,
+    let segments: Vec<_> = path.path.segments.iter().collect();
+    let (result, type_args) = match segments.as_slice() {
+        [m, r] if m.ident == "anyhow" || m.ident == "starlark" => (r, 1),
+        [r] => (r, 2),
+        [s, m, r] if (s.ident == "std" || s.ident == "core") && m.ident == "result" => (r, 2),
+        _ => return false,
     };
-    if segments.next().is_some() {
+    if result.ident != "Result" {
         return false;
     }
     let result_arguments = match &result.arguments {
         PathArguments::AngleBracketed(args) => args,
         _ => return false,
     };
-    let mut result_arguments = result_arguments.args.iter();
-    let t = match result_arguments.next() {
-        None => return false,
-        Some(t) => t,
-    };
-    matches!(t, GenericArgument::Type(_))
+    result_arguments.args.len() == type_args
+        && result_arguments
+            .args
+            .iter()
+            .all(|t| matches!(t, GenericArgument::Type(_)))
 }
 
 // Add a function to the `GlobalsModule` named `globals_builder`.
@@ -295,9 +325,12 @@ pub(crate) fn parse_fun(func: ItemFn, module_kind: ModuleKind) -> syn::Result<St
         is_attribute,
         as_type,
         speculative_exec_safe,
+        speculative_exec_safe_allow_eval,
+        nondeterministic,
         docstring,
         starlark_ty_custom_function,
         special_builtin_function,
+        profile_name,
         attrs,
     } = parse_fn_attrs(func.span(), func.attrs)?;
 
@@ -305,9 +338,12 @@ pub(crate) fn parse_fun(func: ItemFn, module_kind: ModuleKind) -> syn::Result<St
 
     let return_type = parse_fn_output(&func.sig.output, func.sig.span(), has_v)?;
 
+    let is_async = func.sig.asyncness.is_some();
+
     let mut this = None;
     let mut eval = None;
     let mut heap = None;
+    let mut call_site = None;
 
     // Seen an equivalent of `*` or `*args`. Meaning default parameters are named-only after this.
     let mut seen_star = false;
@@ -328,6 +364,15 @@ pub(crate) fn parse_fun(func: ItemFn, module_kind: ModuleKind) -> syn::Result<St
                 }
                 eval = Some(special);
             }
+            StarArgOrSpecial::CallSite(special) => {
+                if call_site.is_some() {
+                    return Err(syn::Error::new(
+                        span,
+                        "Repeated `Option<FileSpan>` parameter",
+                    ));
+                }
+                call_site = Some(special);
+            }
             StarArgOrSpecial::StarArg(arg) => {
                 if arg.pass_style == StarArgPassStyle::Args
                     || arg.pass_style == StarArgPassStyle::NamedOnly
@@ -380,13 +425,29 @@ pub(crate) fn parse_fun(func: ItemFn, module_kind: ModuleKind) -> syn::Result<St
         ));
     }
 
+    if is_async && eval.is_some() {
+        return Err(syn::Error::new(
+            sig_span,
+            "Async functions cannot have `&mut Evaluator` parameter",
+        ));
+    }
+
     if is_attribute {
+        if is_async {
+            return Err(syn::Error::new(sig_span, "Attributes cannot be async"));
+        }
         if eval.is_some() {
             return Err(syn::Error::new(
                 sig_span,
                 "Attributes cannot have `&mut Evaluator` parameter",
             ));
         }
+        if call_site.is_some() {
+            return Err(syn::Error::new(
+                sig_span,
+                "Attributes cannot have `Option<FileSpan>` parameter",
+            ));
+        }
 
         if args.is_some() {
             return Err(syn::Error::new(
@@ -406,6 +467,18 @@ pub(crate) fn parse_fun(func: ItemFn, module_kind: ModuleKind) -> syn::Result<St
                 "Attribute function cannot types are not implemented",
             ));
         }
+        if profile_name.is_some() {
+            return Err(syn::Error::new(
+                sig_span,
+                "Attribute function cannot have a `profile_name`",
+            ));
+        }
+        if nondeterministic {
+            return Err(syn::Error::new(
+                sig_span,
+                "Attribute function cannot be `nondeterministic`",
+            ));
+        }
         Ok(StarStmt::Attr(StarAttr {
             name: func.sig.ident,
             this,
@@ -446,10 +519,15 @@ pub(crate) fn parse_fun(func: ItemFn, module_kind: ModuleKind) -> syn::Result<St
             args,
             heap,
             eval,
+            call_site,
             return_type,
             starlark_ty_custom_function,
             special_builtin_function,
             speculative_exec_safe,
+            speculative_exec_safe_allow_eval,
+            nondeterministic,
+            profile_name,
+            is_async,
             body: *func.block,
             source,
             docstring,
@@ -543,11 +621,12 @@ fn parse_fn_output(return_type: &ReturnType, span: Span, has_v: bool) -> syn::Re
     check_lifetimes_in_return_type(return_type, has_v)?;
     match return_type {
         ReturnType::Default => Err(syn::Error::new(span, "Function must have a return type")),
-        ReturnType::Type(_, x) => match is_anyhow_or_starlark_result(x) {
+        ReturnType::Type(_, x) => match is_supported_result(x) {
             true => Ok((**x).clone()),
             false => Err(syn::Error::new(
                 return_type.span(),
-                "Function return type must be either `anyhow::Result<...>` or `starlark::Result<...>`",
+                "Function return type must be either `anyhow::Result<...>`, `starlark::Result<...>` \
+                or `Result<..., E>` where `E: NativeCallableError`",
             )),
         },
     }
@@ -589,6 +668,8 @@ enum StarArgOrSpecial {
     Eval(SpecialParam),
     /// `&Heap`.
     Heap(SpecialParam),
+    /// `Option<FileSpan>`.
+    CallSite(SpecialParam),
 }
 
 /// Function parameter is `eval: &mut Evaluator`.
@@ -626,6 +707,24 @@ fn is_heap(param: &SimpleParam, attrs: &FnParamAttrs) -> syn::Result<Option<Spec
     }
 }
 
+/// Function parameter is `call_site: Option<FileSpan>`.
+fn is_call_site(param: &SimpleParam, attrs: &FnParamAttrs) -> syn::Result<Option<SpecialParam>> {
+    match unpack_option(&param.ty) {
+        Some(ty) if is_type_name(ty, "FileSpan") => {
+            if !attrs.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    &param.ident,
+                    "`Option<FileSpan>` parameter cannot have attributes",
+                ));
+            }
+            Ok(Some(SpecialParam {
+                param: param.clone(),
+            }))
+        }
+        _ => Ok(None),
+    }
+}
+
 fn parse_this_param(param: &SimpleParam, attrs: &FnParamAttrs) -> syn::Result<ThisParam> {
     let FnParamAttrs {
         default,
@@ -713,6 +812,14 @@ fn parse_arg(
             ));
         }
         return Ok(StarArgOrSpecial::Eval(eval));
+    } else if let Some(call_site) = is_call_site(&param, &param_attrs)? {
+        if this {
+            return Err(syn::Error::new(
+                span,
+                "Receiver parameter cannot be `Option<FileSpan>`",
+            ));
+        }
+        return Ok(StarArgOrSpecial::CallSite(call_site));
     }
 
     if this {
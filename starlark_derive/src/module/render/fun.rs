@@ -17,8 +17,11 @@
 
 use std::iter;
 
+use proc_macro2::Delimiter;
 use proc_macro2::Ident;
+use proc_macro2::Spacing;
 use proc_macro2::TokenStream;
+use proc_macro2::TokenTree;
 use quote::format_ident;
 use quote::quote;
 use syn::Expr;
@@ -32,6 +35,7 @@ use crate::module::simple_param::SimpleParam;
 use crate::module::typ::RegularParams;
 use crate::module::typ::SpecialParam;
 use crate::module::typ::StarArg;
+use crate::module::typ::StarArgPassStyle;
 use crate::module::typ::StarArgSource;
 use crate::module::typ::StarFun;
 use crate::module::typ::StarFunSource;
@@ -106,6 +110,23 @@ impl StarFun {
         }
     }
 
+    /// Call site function parameter, statement computing it and call argument.
+    fn call_site_param_arg(&self) -> (Option<SimpleParam>, Option<syn::Stmt>, Option<syn::Expr>) {
+        if let Some(SpecialParam { param }) = &self.call_site {
+            (
+                Some(param.clone()),
+                Some(syn::parse_quote! {
+                    let __call_site = eval.call_stack_top_location();
+                }),
+                Some(syn::parse_quote! {
+                    __call_site
+                }),
+            )
+        } else {
+            (None, None, None)
+        }
+    }
+
     /// `this` param if needed and call argument.
     fn this_param_arg(
         &self,
@@ -198,6 +219,12 @@ impl StarFun {
                     "methods cannot have a `ty_custom_function` attribute",
                 ));
             }
+            if self.profile_name.is_some() {
+                return Err(syn::Error::new(
+                    self.span(),
+                    "methods cannot have a `profile_name` attribute",
+                ));
+            }
             Ok(syn::parse_quote! {
                 #[allow(clippy::redundant_closure)]
                 globals_builder.set_method(
@@ -231,7 +258,19 @@ impl StarFun {
 pub(crate) fn render_fun(x: StarFun) -> syn::Result<syn::Stmt> {
     let (this_outer_param, this_inner_param, this_prepare, this_arg) = x.this_param_arg();
     let (eval_param, eval_arg) = x.eval_param_arg();
+    if x.speculative_exec_safe && !x.speculative_exec_safe_allow_eval {
+        if let Some(eval_param) = &eval_param {
+            return Err(syn::Error::new(
+                eval_param.ident.span(),
+                "`speculative_exec_safe` functions cannot take `&mut Evaluator`, \
+                because speculative evaluation must not mutate the evaluator; \
+                use `speculative_exec_safe(allow_eval)` if the evaluator is only used \
+                for scratch state or to call other speculative-safe code",
+            ));
+        }
+    }
     let (heap_param, heap_arg) = x.heap_param_arg();
+    let (call_site_param, call_site_prepare, call_site_arg) = x.call_site_param_arg();
     let (binding_params, prepare, binding_args) = x.binding_params_arg()?;
 
     let trait_name = x.trait_name();
@@ -244,6 +283,7 @@ pub(crate) fn render_fun(x: StarFun) -> syn::Result<syn::Stmt> {
     let StarFun {
         attrs,
         return_type,
+        is_async,
         body,
         ..
     } = x;
@@ -253,13 +293,15 @@ pub(crate) fn render_fun(x: StarFun) -> syn::Result<syn::Stmt> {
         .chain(binding_params)
         .chain(eval_param)
         .chain(heap_param)
+        .chain(call_site_param)
         .collect();
 
     let invoke_args = iter::empty()
         .chain(this_arg)
         .chain(binding_args)
         .chain(eval_arg)
-        .chain(heap_arg);
+        .chain(heap_arg)
+        .chain(call_site_arg);
 
     let param_types: Vec<_> = invoke_params.iter().map(|p| &p.ty).collect();
 
@@ -272,23 +314,27 @@ pub(crate) fn render_fun(x: StarFun) -> syn::Result<syn::Stmt> {
         }
     };
 
-    let impl_struct: syn::ItemImpl = syn::parse_quote! {
-        impl #struct_name {
-            // TODO(nga): copy lifetime parameter from declaration,
-            //   so the warning would be precise.
-            #[allow(clippy::extra_unused_lifetimes)]
-            #( #attrs )*
-            fn invoke_impl<'v>(
-                #( #invoke_params, )*
-            ) -> #return_type {
-                #body
-            }
+    let asyncness = is_async.then(|| quote! { async });
 
-            // When function signature declares return type as `anyhow::Result<impl AllocValue>`,
-            // we cannot call `T::starlark_type_repr` to render documentation, because there's no T.
-            // Future Rust will provide syntax `type ReturnType = impl AllocValue`:
-            // https://github.com/rust-lang/rfcs/pull/2515
-            // Until then we use this hack as a workaround.
+    let return_type_starlark_type_repr: syn::ImplItemFn = if is_async {
+        // Future returned by `async fn` cannot be named, so take the type from the signature.
+        syn::parse_quote! {
+            fn return_type_starlark_type_repr<'v>() -> starlark::typing::Ty {
+                fn get_impl<'v, T: starlark::values::AllocValue<'v>, E>(
+                    _r: std::marker::PhantomData<std::result::Result<T, E>>,
+                ) -> starlark::typing::Ty {
+                    <T as starlark::values::type_repr::StarlarkTypeRepr>::starlark_type_repr()
+                }
+                get_impl(std::marker::PhantomData::<#return_type>)
+            }
+        }
+    } else {
+        // When function signature declares return type as `anyhow::Result<impl AllocValue>`,
+        // we cannot call `T::starlark_type_repr` to render documentation, because there's no T.
+        // Future Rust will provide syntax `type ReturnType = impl AllocValue`:
+        // https://github.com/rust-lang/rfcs/pull/2515
+        // Until then we use this hack as a workaround.
+        syn::parse_quote! {
             #[allow(dead_code)] // Function is not used when return type is specified explicitly.
             fn return_type_starlark_type_repr() -> starlark::typing::Ty {
                 fn get_impl<'v, T: starlark::values::AllocValue<'v>, E>(
@@ -303,6 +349,32 @@ pub(crate) fn render_fun(x: StarFun) -> syn::Result<syn::Stmt> {
         }
     };
 
+    let impl_struct: syn::ItemImpl = syn::parse_quote! {
+        impl #struct_name {
+            // TODO(nga): copy lifetime parameter from declaration,
+            //   so the warning would be precise.
+            #[allow(clippy::extra_unused_lifetimes)]
+            #( #attrs )*
+            #asyncness fn invoke_impl<'v>(
+                #( #invoke_params, )*
+            ) -> #return_type {
+                #body
+            }
+
+            #return_type_starlark_type_repr
+        }
+    };
+
+    let invoke_impl_call: syn::Expr = if is_async {
+        syn::parse_quote! {
+            starlark::__derive_refs::blocking::block_on(eval, Self::invoke_impl(#( #invoke_args, )*))?
+        }
+    } else {
+        syn::parse_quote! {
+            Self::invoke_impl(#( #invoke_args, )*)
+        }
+    };
+
     let impl_trait: syn::ItemImpl = syn::parse_quote! {
         impl #trait_name for #struct_name {
             #[allow(non_snake_case)] // Starlark doesn't have this convention
@@ -313,10 +385,11 @@ pub(crate) fn render_fun(x: StarFun) -> syn::Result<syn::Stmt> {
                 parameters: &starlark::eval::Arguments<'v, '_>,
             ) -> starlark::Result<starlark::values::Value<'v>> {
                 #this_prepare
+                #call_site_prepare
                 #prepare
-                match Self::invoke_impl(#( #invoke_args, )*) {
+                match #invoke_impl_call {
                     Ok(v) => Ok(eval.heap().alloc(v)),
-                    Err(e) => Err(starlark::__derive_refs::invoke_macro_error::InvokeMacroError::into_starlark_error(e)),
+                    Err(e) => Err(starlark::errors::NativeCallableError::into_starlark_error(e)),
                 }
             }
         }
@@ -340,6 +413,22 @@ struct Bindings {
 // Given __args and __signature (if render_signature was Some)
 // create bindings for all the arguments
 fn render_binding(x: &StarFun) -> syn::Result<Bindings> {
+    let no_positional_check = match &x.args {
+        RegularParams::Unpack(args)
+            if args.iter().all(|a| {
+                matches!(
+                    a.pass_style,
+                    StarArgPassStyle::NamedOnly | StarArgPassStyle::Kwargs
+                )
+            }) =>
+        {
+            let name_str = x.name_str();
+            quote! {
+                starlark::__derive_refs::parse_args::no_positional_args(#name_str, parameters)?;
+            }
+        }
+        _ => TokenStream::new(),
+    };
     match (&x.args, &x.source) {
         (RegularParams::Arguments(arguments), StarFunSource::Arguments) => Ok(Bindings {
             prepare: TokenStream::new(),
@@ -355,10 +444,11 @@ fn render_binding(x: &StarFun) -> syn::Result<Bindings> {
         (RegularParams::Unpack(args), StarFunSource::Signature { count }) => {
             let bind_args: Vec<BindingArg> = args
                 .iter()
-                .map(render_binding_arg)
+                .map(|arg| render_binding_arg(arg, 0))
                 .collect::<syn::Result<_>>()?;
             Ok(Bindings {
                 prepare: quote! {
+                    #no_positional_check
                     let __args: [_; #count] =
                         starlark::__derive_refs::parse_args::parse_signature(
                             &self.signature, parameters, eval.heap())?;
@@ -376,10 +466,11 @@ fn render_binding(x: &StarFun) -> syn::Result<Bindings> {
         ) => {
             let bind_args = args
                 .iter()
-                .map(render_binding_arg)
+                .map(|arg| render_binding_arg(arg, *required))
                 .collect::<syn::Result<_>>()?;
             Ok(Bindings {
                 prepare: quote! {
+                    #no_positional_check
                     let (__required, __optional): ([_; #required], [_; #optional]) =
                         starlark::__derive_refs::parse_args::parse_positional(
                             &parameters, eval.heap())?;
@@ -397,10 +488,11 @@ fn render_binding(x: &StarFun) -> syn::Result<Bindings> {
         ) => {
             let bind_args = args
                 .iter()
-                .map(render_binding_arg)
+                .map(|arg| render_binding_arg(arg, *required))
                 .collect::<syn::Result<_>>()?;
             Ok(Bindings {
                 prepare: quote! {
+                    #no_positional_check
                     let (__required, __optional, s_kwargs_value): ([_; #required], [_; #optional], _) =
                         starlark::__derive_refs::parse_args::parse_positional_kwargs_alloc(
                             &parameters, eval.heap())?;
@@ -423,27 +515,45 @@ impl BindingArg {
 }
 
 /// Convert an expression of type `Value` to an expression of type of parameter.
-fn render_unpack_value(value: syn::Expr, arg: &StarArg) -> syn::Expr {
+///
+/// `position` is the index of a positional-only parameter, used in error messages.
+fn render_unpack_value(value: syn::Expr, arg: &StarArg, position: Option<usize>) -> syn::Expr {
     if arg.is_value() {
         // If we already have a `Value`, no need to unpack it.
         value
     } else {
         let name_str = ident_string(&arg.param.ident);
-        syn::parse_quote! {
-            starlark::__derive_refs::parse_args::check_unpack(#name_str, #value)?
+        match position {
+            Some(position) => syn::parse_quote! {
+                starlark::__derive_refs::parse_args::check_unpack_positional(#name_str, #position, #value)?
+            },
+            None => syn::parse_quote! {
+                starlark::__derive_refs::parse_args::check_unpack(#name_str, #value)?
+            },
         }
     }
 }
 
 /// Convert an expression of type `Option<Value>` to an expression of type of parameter.
-fn render_unpack_option_value(option_value: syn::Expr, arg: &StarArg) -> syn::Expr {
+///
+/// `position` is the index of a positional-only parameter, used in error messages.
+fn render_unpack_option_value(
+    option_value: syn::Expr,
+    arg: &StarArg,
+    position: Option<usize>,
+) -> syn::Expr {
     let name_str = ident_string(&arg.param.ident);
     if arg.is_option_value() {
         // If we already have a `Option<Value>`, no need to unpack it.
         option_value
     } else if arg.is_option() {
-        syn::parse_quote! {
-            starlark::__derive_refs::parse_args::check_optional(#name_str, #option_value)?
+        match position {
+            Some(position) => syn::parse_quote! {
+                starlark::__derive_refs::parse_args::check_optional_positional(#name_str, #position, #option_value)?
+            },
+            None => syn::parse_quote! {
+                starlark::__derive_refs::parse_args::check_optional(#name_str, #option_value)?
+            },
         }
     } else if arg.is_value() {
         // We call `check_required` even if `default` is set because for `Value`,
@@ -452,8 +562,13 @@ fn render_unpack_option_value(option_value: syn::Expr, arg: &StarArg) -> syn::Ex
             starlark::__derive_refs::parse_args::check_required(#name_str, #option_value)?
         }
     } else if let Some(default) = &arg.default {
-        syn::parse_quote! {
-            starlark::__derive_refs::parse_args::check_defaulted(#name_str, #option_value, || #default)?
+        match position {
+            Some(position) => syn::parse_quote! {
+                starlark::__derive_refs::parse_args::check_defaulted_positional(#name_str, #position, #option_value, || #default)?
+            },
+            None => syn::parse_quote! {
+                starlark::__derive_refs::parse_args::check_defaulted(#name_str, #option_value, || #default)?
+            },
         }
     } else {
         syn::parse_quote! {
@@ -462,19 +577,24 @@ fn render_unpack_option_value(option_value: syn::Expr, arg: &StarArg) -> syn::Ex
     }
 }
 
-// Create a binding for an argument given. If it requires an index, take from the index
-fn render_binding_arg(arg: &StarArg) -> syn::Result<BindingArg> {
+// Create a binding for an argument given. If it requires an index, take from the index.
+// `required` is the number of required positional-only parameters (for `Positional` source).
+fn render_binding_arg(arg: &StarArg, required: usize) -> syn::Result<BindingArg> {
     let next: syn::Expr = match &arg.source {
         StarArgSource::Argument(i) => {
-            render_unpack_option_value(syn::parse_quote! { __args[#i] }, arg)
-        }
-        StarArgSource::Optional(i) => {
-            render_unpack_option_value(syn::parse_quote! { __optional[#i] }, arg)
+            render_unpack_option_value(syn::parse_quote! { __args[#i] }, arg, None)
         }
+        StarArgSource::Optional(i) => render_unpack_option_value(
+            syn::parse_quote! { __optional[#i] },
+            arg,
+            Some(required + i),
+        ),
         StarArgSource::Required(i) => {
-            render_unpack_value(syn::parse_quote! { __required[#i] }, arg)
+            render_unpack_value(syn::parse_quote! { __required[#i] }, arg, Some(*i))
+        }
+        StarArgSource::Kwargs => {
+            render_unpack_value(syn::parse_quote! { s_kwargs_value }, arg, None)
         }
-        StarArgSource::Kwargs => render_unpack_value(syn::parse_quote! { s_kwargs_value }, arg),
         s => {
             return Err(syn::Error::new(
                 arg.span,
@@ -559,18 +679,20 @@ fn render_regular_native_callable_param(arg: &StarArg) -> syn::Result<syn::Expr>
             syn::parse_quote! { starlark::__derive_refs::param_spec::NativeCallableParamDefaultValue::Optional },
         ),
         (None, false) => render_none(),
-        (Some(default), _) => {
+        (Some(default_expr), _) => {
             // For things that are type Value, we put them on the frozen heap.
             // For things that aren't type value, use optional and then next_opt/unwrap
             // to avoid the to/from value conversion.
             let default = if arg.is_value() {
-                Some(syn::parse_quote! { globals_builder.alloc(#default) })
+                Some(syn::parse_quote! { globals_builder.alloc(#default_expr) })
             } else {
-                render_default_as_frozen_value(default)
+                render_default_as_frozen_value(default_expr)
             };
             render_some(match default {
                 None => {
-                    syn::parse_quote! { starlark::__derive_refs::param_spec::NativeCallableParamDefaultValue::Optional }
+                    // Still show something meaningful in the docs.
+                    let default = render_default_as_source_text(default_expr);
+                    syn::parse_quote! { starlark::__derive_refs::param_spec::NativeCallableParamDefaultValue::Expr(#default) }
                 }
                 Some(_) => {
                     syn::parse_quote! { starlark::__derive_refs::param_spec::NativeCallableParamDefaultValue::Value(#default) }
@@ -655,11 +777,15 @@ fn render_native_callable_components(x: &StarFun) -> syn::Result<TokenStream> {
 
     let return_type_str = render_starlark_return_type(x);
     let speculative_exec_safe = x.speculative_exec_safe;
+    let nondeterministic = x.nondeterministic;
+    let profile_name = render_option(x.profile_name.as_ref().map(|s| syn::parse_quote! { #s }));
     Ok(quote!(
         {
             let param_spec = #param_spec;
             starlark::__derive_refs::components::NativeCallableComponents {
                 speculative_exec_safe: #speculative_exec_safe,
+                nondeterministic: #nondeterministic,
+                profile_name: #profile_name,
                 rust_docstring: #docs,
                 param_spec,
                 return_type: #return_type_str,
@@ -770,6 +896,89 @@ fn parameter_spec_args(star_args: &[StarArg]) -> syn::Result<ParametersSpecArgs>
     })
 }
 
+/// Source text of a default expression, for documentation of the defaults
+/// which [`render_default_as_frozen_value`] cannot convert.
+fn render_default_as_source_text(default: &Expr) -> String {
+    let mut out = String::new();
+    write_source_text(&mut out, quote!(#default));
+    out
+}
+
+/// Print tokens spaced the way Rust code is usually formatted: no spaces in paths,
+/// calls and indexing, spaces around binary operators and after commas.
+fn write_source_text(out: &mut String, tokens: TokenStream) {
+    // Whether the previous token ends an operand, so an operator after it is binary.
+    let mut after_operand = false;
+    // Whether the previous token should be separated from the next one.
+    let mut space = false;
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Punct(p) => {
+                let mut op = p.as_char().to_string();
+                let mut spacing = p.spacing();
+                while spacing == Spacing::Joint {
+                    let Some(TokenTree::Punct(next)) = tokens.peek() else {
+                        break;
+                    };
+                    op.push(next.as_char());
+                    spacing = next.spacing();
+                    tokens.next();
+                }
+                match op.as_str() {
+                    "," | ";" | ":" => {
+                        out.push_str(&op);
+                        space = true;
+                    }
+                    "::" | "." | "?" | "<" | ">" => {
+                        out.push_str(&op);
+                        space = false;
+                    }
+                    _ if after_operand && !matches!(op.as_str(), "!" | ".." | "..=") => {
+                        out.push(' ');
+                        out.push_str(&op);
+                        space = true;
+                    }
+                    // Prefix operator, or `!` of a macro call.
+                    _ => {
+                        if space && !after_operand {
+                            out.push(' ');
+                        }
+                        out.push_str(&op);
+                        space = false;
+                    }
+                }
+                after_operand = false;
+            }
+            TokenTree::Group(g) => {
+                // Calls and indexing are not separated from the callee.
+                if space && (g.delimiter() == Delimiter::Brace || !after_operand) {
+                    out.push(' ');
+                }
+                let mut inner = String::new();
+                write_source_text(&mut inner, g.stream());
+                match g.delimiter() {
+                    Delimiter::Parenthesis => out.push_str(&format!("({inner})")),
+                    Delimiter::Bracket => out.push_str(&format!("[{inner}]")),
+                    Delimiter::Brace if inner.is_empty() => out.push_str("{}"),
+                    Delimiter::Brace => out.push_str(&format!("{{ {inner} }}")),
+                    Delimiter::None => out.push_str(&inner),
+                }
+                after_operand = true;
+                space = true;
+            }
+            TokenTree::Ident(_) | TokenTree::Literal(_) => {
+                if space {
+                    out.push(' ');
+                }
+                out.push_str(&token.to_string());
+                after_operand = true;
+                space = true;
+            }
+        }
+    }
+}
+
 /// We have an argument that the user wants to use as a default.
 /// That _might_ have a valid `FrozenValue` representation, if so, it would be great to use for documentation.
 /// Try and synthesise it if we can.
@@ -74,11 +74,21 @@ pub(crate) struct StarFun {
     pub heap: Option<SpecialParam>,
     /// Has `&mut Evaluator` parameter.
     pub eval: Option<SpecialParam>,
+    /// Has `Option<FileSpan>` parameter.
+    pub call_site: Option<SpecialParam>,
     /// `anyhow::Result<T>`.
     pub return_type: Type,
     pub starlark_ty_custom_function: Option<Expr>,
     pub special_builtin_function: Option<Expr>,
     pub speculative_exec_safe: bool,
+    /// Speculative-safe despite taking `&mut Evaluator`.
+    pub speculative_exec_safe_allow_eval: bool,
+    /// Cannot be called when the evaluator enforces determinism.
+    pub nondeterministic: bool,
+    /// Name in call stacks and profiles, if different from the function name.
+    pub profile_name: Option<syn::LitStr>,
+    /// Declared as `async fn`, the future is resolved with the evaluator blocking handle.
+    pub is_async: bool,
     pub body: Block,
     pub source: StarFunSource,
     pub docstring: Option<String>,
@@ -42,6 +42,39 @@ struct StarlarkValueAttrs {
     unpack_value: bool,
     /// Implement `StarlarkTypeRepr` for `&T`.
     starlark_type_repr: bool,
+    /// `repr = path`: function `fn(&Self, &mut String)` used to implement `collect_repr`.
+    repr: Option<syn::Path>,
+    /// `eq = path`: function `fn(&Self, &Self) -> bool` used to implement `equals`.
+    eq: Option<syn::Path>,
+    /// `iterate = path`: function `fn(&Self, &'v Heap) -> impl IntoIterator<Item = Value<'v>>`
+    /// used to implement `iterate_collect`.
+    iterate: Option<syn::Path>,
+    /// `add = path`, `bit_or = path` etc: functions `fn(&Self, &Self, &'v Heap) -> starlark::Result<Value<'v>>`
+    /// used to implement binary operators when both operands are of this type.
+    bin_ops: Vec<(syn::Ident, syn::Path)>,
+}
+
+/// Binary operators which can be specified as `starlark_value` attributes:
+/// name, operator, whether the trait method returns `Option` to fall back to `radd`/`rmul`.
+const BIN_OPS: &[(&str, &str, bool)] = &[
+    ("add", "+", true),
+    ("sub", "-", false),
+    ("mul", "*", true),
+    ("div", "/", false),
+    ("floor_div", "//", false),
+    ("percent", "%", false),
+    ("bit_and", "&", false),
+    ("bit_or", "|", false),
+    ("bit_xor", "^", false),
+];
+
+impl StarlarkValueAttrs {
+    /// Function specified for binary operator `name`.
+    fn bin_op(&self, name: &str) -> Option<&syn::Path> {
+        self.bin_ops
+            .iter()
+            .find_map(|(n, path)| if n == name { Some(path) } else { None })
+    }
 }
 
 impl syn::parse::Parse for StarlarkValueAttrs {
@@ -53,6 +86,10 @@ impl syn::parse::Parse for StarlarkValueAttrs {
             typ,
             unpack_value: false,
             starlark_type_repr: false,
+            repr: None,
+            eq: None,
+            iterate: None,
+            bin_ops: Vec::new(),
         };
 
         loop {
@@ -69,10 +106,27 @@ impl syn::parse::Parse for StarlarkValueAttrs {
                 attrs.unpack_value = true;
             } else if name == "StarlarkTypeRepr" {
                 attrs.starlark_type_repr = true;
+            } else if name == "repr" {
+                input.parse::<syn::Token![=]>()?;
+                attrs.repr = Some(input.parse::<syn::Path>()?);
+            } else if name == "eq" {
+                input.parse::<syn::Token![=]>()?;
+                attrs.eq = Some(input.parse::<syn::Path>()?);
+            } else if name == "iterate" {
+                input.parse::<syn::Token![=]>()?;
+                attrs.iterate = Some(input.parse::<syn::Path>()?);
+            } else if BIN_OPS.iter().any(|(op, ..)| name == op) {
+                input.parse::<syn::Token![=]>()?;
+                if attrs.bin_op(&name.to_string()).is_some() {
+                    return Err(syn::Error::new_spanned(name, "duplicate attribute"));
+                }
+                let path = input.parse::<syn::Path>()?;
+                attrs.bin_ops.push((name, path));
             } else {
                 return Err(syn::Error::new_spanned(
                     name,
-                    "unknown attribute, allowed attribute is `UnpackValue`, `StarlarkTypeRepr`",
+                    "unknown attribute, allowed attribute is `UnpackValue`, `StarlarkTypeRepr`, `repr`, `eq`, `iterate`, \
+                     or a binary operator (`add`, `sub`, `mul`, `div`, `floor_div`, `percent`, `bit_and`, `bit_or`, `bit_xor`)",
                 ));
             }
         }
@@ -238,7 +292,10 @@ impl ImplStarlarkValue {
 
     fn bin_op_arm(&self, bin_op: &str, impl_name: &str) -> Option<syn::Arm> {
         let bin_op = syn::Ident::new(bin_op, self.span());
-        if self.has_fn(impl_name) || (impl_name == "bit_or" && self.has_fn("eval_type")) {
+        if self.has_fn(impl_name)
+            || self.attrs.bin_op(impl_name).is_some()
+            || (impl_name == "bit_or" && self.has_fn("eval_type"))
+        {
             Some(syn::parse_quote_spanned! {
                 self.span()=>
                 starlark::typing::TypingBinOp::#bin_op => {
@@ -355,6 +412,12 @@ impl ImplStarlarkValue {
                 "types with `eval_type` implemented can only have generated `bit_or`",
             ));
         }
+        if let Some((name, _)) = self.attrs.bin_ops.iter().find(|(n, _)| n == "bit_or") {
+            return Err(syn::Error::new_spanned(
+                name,
+                "types with `eval_type` implemented can only have generated `bit_or`",
+            ));
+        }
         Ok(Some(syn::parse2(quote_spanned! { self.span() =>
             fn bit_or(&self, other: starlark::values::Value<'v>, heap: &'v starlark::values::Heap) -> starlark::Result<starlark::values::Value<'v>> {
                 starlark::values::typing::macro_refs::starlark_value_bit_or_for_type(self, other, heap)
@@ -362,6 +425,130 @@ impl ImplStarlarkValue {
         })?))
     }
 
+    /// `fn collect_repr()` delegating to the function given in `repr = ...`.
+    fn collect_repr(&self) -> syn::Result<Option<syn::ImplItem>> {
+        let Some(repr) = &self.attrs.repr else {
+            return Ok(None);
+        };
+        if let Some(collect_repr) = self.find_fn("collect_repr") {
+            return Err(syn::Error::new_spanned(
+                collect_repr.sig.ident,
+                "`collect_repr` cannot be implemented when `repr` attribute is specified",
+            ));
+        }
+        Ok(Some(syn::parse2(quote_spanned! { self.span() =>
+            fn collect_repr(&self, collector: &mut String) {
+                #repr(self, collector)
+            }
+        })?))
+    }
+
+    /// `fn collect_str()` using `Display` when `repr = ...` is specified,
+    /// so `str()` does not follow the custom `repr`.
+    fn collect_str(&self) -> syn::Result<Option<syn::ImplItem>> {
+        if self.attrs.repr.is_none() || self.has_fn("collect_str") {
+            return Ok(None);
+        }
+        Ok(Some(syn::parse2(quote_spanned! { self.span() =>
+            fn collect_str(&self, collector: &mut String) {
+                std::fmt::Write::write_fmt(collector, format_args!("{}", self)).unwrap()
+            }
+        })?))
+    }
+
+    /// `fn equals()` delegating to the function given in `eq = ...`.
+    fn equals(&self) -> syn::Result<Option<syn::ImplItem>> {
+        let Some(eq) = &self.attrs.eq else {
+            return Ok(None);
+        };
+        if let Some(equals) = self.find_fn("equals") {
+            return Err(syn::Error::new_spanned(
+                equals.sig.ident,
+                "`equals` cannot be implemented when `eq` attribute is specified",
+            ));
+        }
+        let lt = &self.lifetime_param;
+        Ok(Some(syn::parse2(quote_spanned! { self.span() =>
+            fn equals(&self, other: starlark::values::Value<#lt>) -> starlark::Result<bool> {
+                match starlark::values::ValueLike::downcast_ref::<Self>(other) {
+                    ::std::option::Option::Some(other) => ::std::result::Result::Ok(#eq(self, other)),
+                    ::std::option::Option::None => ::std::result::Result::Ok(false),
+                }
+            }
+        })?))
+    }
+
+    /// `fn iterate_collect()` delegating to the function given in `iterate = ...`.
+    fn iterate_collect(&self) -> syn::Result<Option<syn::ImplItem>> {
+        let Some(iterate) = &self.attrs.iterate else {
+            return Ok(None);
+        };
+        for name in ["iterate", "iterate_collect"] {
+            if let Some(f) = self.find_fn(name) {
+                return Err(syn::Error::new_spanned(
+                    f.sig.ident,
+                    format!("`{name}` cannot be implemented when `iterate` attribute is specified"),
+                ));
+            }
+        }
+        let lt = &self.lifetime_param;
+        Ok(Some(syn::parse2(quote_spanned! { self.span() =>
+            fn iterate_collect(
+                &self,
+                heap: &#lt starlark::values::Heap,
+            ) -> starlark::Result<::std::vec::Vec<starlark::values::Value<#lt>>> {
+                ::std::result::Result::Ok(::std::iter::IntoIterator::into_iter(#iterate(self, heap)).collect())
+            }
+        })?))
+    }
+
+    /// Binary operator functions delegating to the functions given in `add = ...` etc.
+    /// When the other operand is not of this type, the operator is reported as unsupported.
+    fn bin_ops(&self) -> syn::Result<Vec<syn::ImplItem>> {
+        let lt = &self.lifetime_param;
+        let mut items = Vec::new();
+        for (name, path) in &self.attrs.bin_ops {
+            if let Some(f) = self.find_fn(&name.to_string()) {
+                return Err(syn::Error::new_spanned(
+                    f.sig.ident,
+                    format!("`{name}` cannot be implemented when `{name}` attribute is specified"),
+                ));
+            }
+            let Some((_, op, returns_option)) = BIN_OPS.iter().find(|(n, ..)| name == n) else {
+                return Err(syn::Error::new_spanned(name, "unknown binary operator"));
+            };
+            let item = if *returns_option {
+                quote_spanned! { name.span() =>
+                    fn #name(
+                        &self,
+                        other: starlark::values::Value<#lt>,
+                        heap: &#lt starlark::values::Heap,
+                    ) -> ::std::option::Option<starlark::Result<starlark::values::Value<#lt>>> {
+                        let other = starlark::values::ValueLike::downcast_ref::<Self>(other)?;
+                        ::std::option::Option::Some(#path(self, other, heap))
+                    }
+                }
+            } else {
+                quote_spanned! { name.span() =>
+                    fn #name(
+                        &self,
+                        other: starlark::values::Value<#lt>,
+                        heap: &#lt starlark::values::Heap,
+                    ) -> starlark::Result<starlark::values::Value<#lt>> {
+                        match starlark::values::ValueLike::downcast_ref::<Self>(other) {
+                            ::std::option::Option::Some(other) => #path(self, other, heap),
+                            ::std::option::Option::None => {
+                                starlark::values::ValueError::unsupported_with(self, #op, other)
+                            }
+                        }
+                    }
+                }
+            };
+            items.push(syn::parse2(item)?);
+        }
+        Ok(items)
+    }
+
     /// `ValueLike<'v>`?
     fn path_is_value_like(&self, path: &syn::Path) -> syn::Result<bool> {
         let Some(last) = path.segments.last() else {
@@ -513,6 +700,11 @@ fn derive_starlark_value_impl(
     let rbin_op_ty = impl_starlark_value.rbin_op_ty()?;
     let attr_ty = impl_starlark_value.attr_ty()?;
     let bit_or = impl_starlark_value.bit_or()?;
+    let collect_repr = impl_starlark_value.collect_repr()?;
+    let collect_str = impl_starlark_value.collect_str()?;
+    let equals = impl_starlark_value.equals()?;
+    let iterate_collect = impl_starlark_value.iterate_collect()?;
+    let bin_ops = impl_starlark_value.bin_ops()?;
     let canonical = impl_starlark_value.canonical_member()?;
 
     input.items.splice(
@@ -525,6 +717,11 @@ fn derive_starlark_value_impl(
         .into_iter()
         .chain(attr_ty)
         .chain(bit_or)
+        .chain(collect_repr)
+        .chain(collect_str)
+        .chain(equals)
+        .chain(iterate_collect)
+        .chain(bin_ops)
         .chain(bin_op_ty)
         .chain(rbin_op_ty)
         .chain(canonical),
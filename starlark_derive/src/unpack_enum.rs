@@ -0,0 +1,151 @@
+/*
+ * Copyright 2019 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use quote::quote_spanned;
+use syn::parse::ParseStream;
+use syn::spanned::Spanned;
+use syn::Attribute;
+use syn::DeriveInput;
+use syn::Fields;
+use syn::LitStr;
+use syn::Token;
+
+use crate::util::DeriveInputUtil;
+
+pub(crate) fn derive_unpack_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    match derive_unpack_enum_impl(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// `FastMode` -> `fast_mode`, `HTTPServer` -> `http_server`.
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut r = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            // Start a new word after a lowercase letter or digit,
+            // or at the last capital of an acronym followed by a lowercase letter.
+            let prev_lower =
+                i != 0 && (chars[i - 1].is_lowercase() || chars[i - 1].is_ascii_digit());
+            let acronym_end = i != 0
+                && chars[i - 1].is_uppercase()
+                && chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if prev_lower || acronym_end {
+                r.push('_');
+            }
+            r.extend(c.to_lowercase());
+        } else {
+            r.push(c);
+        }
+    }
+    r
+}
+
+/// Parse a `#[starlark(name = "...")]` annotation.
+fn extract_name(attrs: &[Attribute]) -> syn::Result<Option<LitStr>> {
+    syn::custom_keyword!(name);
+
+    let mut result = None;
+    for attr in attrs {
+        if !attr.path().is_ident("starlark") {
+            continue;
+        }
+        attr.parse_args_with(|input: ParseStream| {
+            let kw = input.parse::<name>()?;
+            if result.is_some() {
+                return Err(syn::Error::new_spanned(kw, "`name` was set twice"));
+            }
+            input.parse::<Token![=]>()?;
+            result = Some(input.parse::<LitStr>()?);
+            Ok(())
+        })?;
+    }
+    Ok(result)
+}
+
+fn derive_unpack_enum_impl(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let derive_input = DeriveInputUtil::new(&input)?;
+    let DeriveInputUtil::Enum(en) = derive_input else {
+        return Err(syn::Error::new(
+            input.span(),
+            "`UnpackEnum` can only be derived for enums",
+        ));
+    };
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "`UnpackEnum` cannot be derived for generic enums",
+        ));
+    }
+
+    let mut variants = Vec::new();
+    let mut names = Vec::new();
+    for variant in &en.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "`UnpackEnum` can only be derived for enums with unit variants",
+            ));
+        }
+        let name = match extract_name(&variant.attrs)? {
+            Some(name) => name.value(),
+            None => snake_case(&variant.ident.to_string()),
+        };
+        if names.contains(&name) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                format!("duplicate variant name `{name}`"),
+            ));
+        }
+        variants.push(&variant.ident);
+        names.push(name);
+    }
+
+    let ident = &input.ident;
+    let span = input.span();
+    Ok(quote_spanned! { span =>
+        impl starlark::values::type_repr::StarlarkTypeRepr for #ident {
+            type Canonical = <std::string::String as starlark::values::type_repr::StarlarkTypeRepr>::Canonical;
+
+            fn starlark_type_repr() -> starlark::typing::Ty {
+                <std::string::String as starlark::values::type_repr::StarlarkTypeRepr>::starlark_type_repr()
+            }
+        }
+
+        impl<'v> starlark::values::UnpackValue<'v> for #ident {
+            type Error = std::convert::Infallible;
+
+            fn unpack_value_impl(value: starlark::values::Value<'v>) -> std::result::Result<std::option::Option<Self>, Self::Error> {
+                std::result::Result::Ok(match value.unpack_str() {
+                    #( std::option::Option::Some(#names) => std::option::Option::Some(#ident::#variants), )*
+                    _ => std::option::Option::None,
+                })
+            }
+        }
+
+        impl<'v> starlark::values::AllocValue<'v> for #ident {
+            fn alloc_value(self, _heap: &'v starlark::values::Heap) -> starlark::values::Value<'v> {
+                match self {
+                    #( #ident::#variants => starlark::const_frozen_string!(#names).to_value(), )*
+                }
+            }
+        }
+    })
+}
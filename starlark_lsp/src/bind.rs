@@ -38,6 +38,7 @@ use starlark_syntax::syntax::ast::ForP;
 use starlark_syntax::syntax::ast::IdentP;
 use starlark_syntax::syntax::ast::LambdaP;
 use starlark_syntax::syntax::ast::Stmt;
+use starlark_syntax::syntax::ast::WithP;
 use starlark_syntax::syntax::module::AstModuleFields;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -322,6 +323,17 @@ fn stmt(x: &AstStmt, res: &mut Vec<Bind>) {
             stmt(body, res);
             flow(res)
         }
+        Stmt::With(WithP {
+            expr: e,
+            target,
+            body,
+        }) => {
+            expr(e, res);
+            if let Some(target) = target {
+                expr_lvalue(target, res);
+            }
+            stmt(body, res);
+        }
         Stmt::Load(load) => {
             for x in &load.args {
                 res.push(Bind::Set(
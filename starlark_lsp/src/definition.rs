@@ -436,7 +436,7 @@ impl LspModule {
 
     /// Get the list of symbols exported by this module.
     pub(crate) fn get_exported_symbols(&self) -> Vec<Symbol> {
-        self.ast.exported_symbols()
+        self.ast.exported_symbols_with_docs()
     }
 
     /// Get the list of symbols loaded by this module.
@@ -447,7 +447,7 @@ impl LspModule {
     /// Attempt to find an exported symbol with the given name.
     pub(crate) fn find_exported_symbol(&self, name: &str) -> Option<Symbol> {
         self.ast
-            .exported_symbols()
+            .exported_symbols_with_docs()
             .into_iter()
             .find(|symbol| symbol.name == name)
     }
@@ -70,7 +70,7 @@ impl From<SymbolKind> for CompletionItemKind {
     }
 }
 
-/// A symbol. Returned from [`AstModule::exported_symbols`].
+/// A symbol. Returned from [`AstModuleExportedSymbols::exported_symbols_with_docs`].
 #[derive(Debug, PartialEq, Clone)]
 pub(crate) struct Symbol {
     /// The name of the symbol.
@@ -103,11 +103,11 @@ impl From<Symbol> for CompletionItem {
 pub(crate) trait AstModuleExportedSymbols {
     /// Which symbols are exported by this module. These are the top-level assignments,
     /// including function definitions. Any symbols that start with `_` are not exported.
-    fn exported_symbols(&self) -> Vec<Symbol>;
+    fn exported_symbols_with_docs(&self) -> Vec<Symbol>;
 }
 
 impl AstModuleExportedSymbols for AstModule {
-    fn exported_symbols(&self) -> Vec<Symbol> {
+    fn exported_symbols_with_docs(&self) -> Vec<Symbol> {
         // Map since we only want to store the first of each export
         // IndexMap since we want the order to match the order they were defined in
         let mut result: SmallMap<&str, _> = SmallMap::new();
@@ -199,7 +199,7 @@ def _e(): pass
 d = 2
 "#,
         );
-        let res = modu.exported_symbols();
+        let res = modu.exported_symbols_with_docs();
         assert_eq!(
             res.map(|symbol| format!("{} {}", symbol.span, symbol.name)),
             &["X:3:5-6 b", "X:4:1-2 d"]
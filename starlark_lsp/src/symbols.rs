@@ -32,6 +32,7 @@ use starlark_syntax::syntax::ast::ForP;
 use starlark_syntax::syntax::ast::LoadArgP;
 use starlark_syntax::syntax::ast::ParameterP;
 use starlark_syntax::syntax::ast::StmtP;
+use starlark_syntax::syntax::ast::WithP;
 
 use crate::docs::get_doc_item_for_def;
 
@@ -101,6 +102,24 @@ pub(crate) fn find_symbols_at_location<P: AstPayload>(
                 });
                 walk(codemap, body, cursor_position, symbols);
             }
+            StmtP::With(WithP {
+                expr: _,
+                target,
+                body,
+            }) => {
+                if let Some(target) = target {
+                    target.visit_lvalue(|x| {
+                        symbols.entry(x.ident.clone()).or_insert_with(|| Symbol {
+                            name: x.ident.clone(),
+                            kind: SymbolKind::Variable,
+                            detail: None,
+                            doc: None,
+                            param: None,
+                        });
+                    });
+                }
+                walk(codemap, body, cursor_position, symbols);
+            }
             StmtP::Def(def) => {
                 // Peek into the function definition to find the docstring.
                 let doc = get_doc_item_for_def(def, codemap);
@@ -134,6 +134,24 @@ impl<K, V> SmallMap<K, V> {
         }
     }
 
+    /// Create a map from an iterator of entries whose keys are known to be unique.
+    ///
+    /// This skips the duplicate check performed by [`FromIterator`].
+    /// If the keys are not unique, the resulting map is not memory unsafe,
+    /// but it is logically inconsistent: lookups may return any of the duplicate entries.
+    #[inline]
+    pub fn from_iter_unique_unchecked<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self
+    where
+        K: Hash,
+    {
+        let iter = iter.into_iter();
+        let mut mp = Self::with_capacity(iter.size_hint().0);
+        for (k, v) in iter {
+            mp.insert_unique_unchecked(k, v);
+        }
+        mp
+    }
+
     /// Drop the index if the map is too small, and the index is not really needed.
     ///
     /// We don't allocate index prematurely when we add entries the map,
@@ -340,7 +358,7 @@ impl<K, V> SmallMap<K, V> {
         self.entries.get_index(index)
     }
 
-    /// The an entry index by a given key.
+    /// Find the index of an entry by a given key.
     #[inline]
     pub fn get_index_of<Q>(&self, key: &Q) -> Option<usize>
     where
@@ -396,6 +414,29 @@ impl<K, V> SmallMap<K, V> {
         self.contains_key_hashed(Hashed::new(key))
     }
 
+    /// Keys of this map which are not in the other map, in the order of this map.
+    pub fn key_difference<'a>(&'a self, other: &'a SmallMap<K, V>) -> impl Iterator<Item = &'a K>
+    where
+        K: Eq,
+    {
+        self.iter_hashed()
+            .filter(move |(k, _)| !other.contains_key_hashed(*k))
+            .map(|(k, _)| k.into_key())
+    }
+
+    /// Keys of this map which are also in the other map, in the order of this map.
+    pub fn key_intersection<'a>(
+        &'a self,
+        other: &'a SmallMap<K, V>,
+    ) -> impl Iterator<Item = &'a K>
+    where
+        K: Eq,
+    {
+        self.iter_hashed()
+            .filter(move |(k, _)| other.contains_key_hashed(*k))
+            .map(|(k, _)| k.into_key())
+    }
+
     /// Reserve capacity for at least `additional` more elements to be inserted.
     #[inline]
     pub fn reserve(&mut self, additional: usize)
@@ -516,6 +557,30 @@ impl<K, V> SmallMap<K, V> {
         self.insert_hashed_unique_unchecked(Hashed::new(key), val)
     }
 
+    /// Append already hashed entries, reserving space once using the iterator size hint.
+    ///
+    /// Keys are not checked for duplicates: the caller must ensure
+    /// they are unique and not already present in the map.
+    pub fn extend_hashed(&mut self, iter: impl IntoIterator<Item = (Hashed<K>, V)>) {
+        let iter = iter.into_iter();
+        match &mut self.index {
+            None => {
+                self.entries.extend_hashed(iter);
+                if self.entries.len() > NO_INDEX_THRESHOLD {
+                    self.create_index(self.entries.len());
+                }
+            }
+            Some(index) => {
+                let additional = iter.size_hint().0;
+                self.entries.reserve(additional);
+                index.reserve(additional, Self::hasher(&self.entries));
+                for (key, value) in iter {
+                    self.insert_hashed_unique_unchecked(key, value);
+                }
+            }
+        }
+    }
+
     /// Remove the entry for the key.
     ///
     /// Time complexity of this operation is *O(N)* where *N* is the number of entries in the map.
@@ -608,6 +673,60 @@ impl<K, V> SmallMap<K, V> {
         self.shift_remove_hashed_entry(Hashed::new(key))
     }
 
+    /// Remove the entry by index, moving the last entry into its place.
+    ///
+    /// This is an *O(1)* operation, but unlike
+    /// [`shift_remove_index`](SmallMap::shift_remove_index)
+    /// it does not preserve insertion order.
+    pub fn swap_remove_index_hashed(&mut self, i: usize) -> Option<(Hashed<K>, V)> {
+        if i >= self.len() {
+            return None;
+        }
+        if let Some(index) = &mut self.index {
+            let last = self.entries.len() - 1;
+            let hash = unsafe { self.entries.get_unchecked(i) }.0.hash();
+            match index.find_entry(hash.promote(), |&j| j == i) {
+                Ok(found) => {
+                    found.remove();
+                }
+                Err(_) => {
+                    if cfg!(debug_assertions) {
+                        unreachable!("The entry must be in the index")
+                    }
+                }
+            }
+            if i != last {
+                let last_hash = unsafe { self.entries.get_unchecked(last) }.0.hash();
+                if let Some(j) = index.find_mut(last_hash.promote(), |&j| j == last) {
+                    *j = i;
+                } else if cfg!(debug_assertions) {
+                    unreachable!("The last entry must be in the index")
+                }
+            }
+        }
+        Some(self.entries.swap_remove(i))
+    }
+
+    /// Remove the entry by index, moving the last entry into its place.
+    ///
+    /// This is an *O(1)* operation, but it does not preserve insertion order.
+    pub fn swap_remove_index(&mut self, i: usize) -> Option<(K, V)> {
+        let (key, value) = self.swap_remove_index_hashed(i)?;
+        Some((key.into_key(), value))
+    }
+
+    /// Remove the entry for the key, moving the last entry into its place.
+    ///
+    /// Unlike [`shift_remove`](SmallMap::shift_remove), this does not preserve insertion order,
+    /// but removal after lookup is *O(1)*.
+    pub fn swap_remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let i = self.get_index_of(key)?;
+        self.swap_remove_index(i).map(|(_k, v)| v)
+    }
+
     /// Get the entry (occupied or not) for the key.
     #[inline]
     pub fn entry_hashed(&mut self, key: Hashed<K>) -> Entry<'_, K, V>
@@ -628,6 +747,11 @@ impl<K, V> SmallMap<K, V> {
 
     /// Remove the last element.
     pub fn pop(&mut self) -> Option<(K, V)> {
+        self.pop_hashed()
+            .map(|(key, value)| (key.into_key(), value))
+    }
+
+    fn pop_hashed(&mut self) -> Option<(Hashed<K>, V)> {
         match self.entries.pop() {
             None => None,
             Some((key, value)) => {
@@ -644,11 +768,51 @@ impl<K, V> SmallMap<K, V> {
                         }
                     }
                 }
-                Some((key.into_key(), value))
+                Some((key, value))
             }
         }
     }
 
+    /// Split the map into two at the given index.
+    ///
+    /// Returns a newly allocated map containing the entries `[at, len)`,
+    /// and leaves the entries `[0, at)` in this map. Order is preserved in both maps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    pub fn split_off(&mut self, at: usize) -> SmallMap<K, V> {
+        assert!(
+            at <= self.len(),
+            "`at` split index (is {}) should be <= len (is {})",
+            at,
+            self.len()
+        );
+        let mut tail = Vec::with_capacity(self.len() - at);
+        while self.len() > at {
+            tail.push(self.pop_hashed().unwrap());
+        }
+        let mut other = SmallMap::with_capacity(tail.len());
+        for (key, value) in tail.into_iter().rev() {
+            other.insert_hashed_unique_unchecked(key, value);
+        }
+        other
+    }
+
+    /// Move all entries from `other` into this map, leaving `other` empty.
+    ///
+    /// Entries are appended in the order of `other`.
+    /// If a key is already present, its value is overwritten, but its position is kept.
+    pub fn append(&mut self, other: &mut SmallMap<K, V>)
+    where
+        K: Eq,
+    {
+        self.reserve(other.len());
+        for (key, value) in mem::take(other).into_iter_hashed() {
+            self.insert_hashed(key, value);
+        }
+    }
+
     /// Get the entry (occupied or not) for the key.
     #[inline]
     pub fn entry(&mut self, key: K) -> Entry<'_, K, V>
@@ -1097,6 +1261,100 @@ mod tests {
         assert_eq!(m.iter().next(), None);
     }
 
+    #[test]
+    fn from_iter_unique_unchecked() {
+        for n in [0, 3, 100] {
+            let m = SmallMap::from_iter_unique_unchecked((0..n).map(|i| (i, i * 2)));
+            assert_eq!(m, (0..n).map(|i| (i, i * 2)).collect::<SmallMap<_, _>>());
+            for i in 0..n {
+                assert_eq!(m.get(&i), Some(&(i * 2)));
+            }
+            assert_eq!(m.get(&n), None);
+        }
+    }
+
+    #[test]
+    fn get_index_matches_iteration_order() {
+        for n in [5, 100] {
+            let mut m: SmallMap<u32, u32> = (0..n).rev().map(|i| (i, i * 10)).collect();
+            for (i, (k, v)) in m.iter().enumerate() {
+                assert_eq!(Some((k, v)), m.get_index(i));
+                assert_eq!(Some(i), m.get_index_of(k));
+            }
+            assert_eq!(None, m.get_index(n as usize));
+
+            m.sort_keys();
+            for i in 0..n {
+                assert_eq!(Some((&i, &(i * 10))), m.get_index(i as usize));
+                assert_eq!(Some(i as usize), m.get_index_of(&i));
+            }
+            assert_eq!(None, m.get_index_of(&n));
+        }
+    }
+
+    #[test]
+    fn split_off() {
+        for n in [5, 40] {
+            for at in [0, 3, n] {
+                let mut m: SmallMap<u32, u32> = (0..n).map(|i| (i, i * 10)).collect();
+                let tail = m.split_off(at as usize);
+                m.state_check();
+                tail.state_check();
+                assert_eq!(
+                    (0..at).map(|i| (i, i * 10)).collect::<Vec<_>>(),
+                    m.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>()
+                );
+                assert_eq!(
+                    (at..n).map(|i| (i, i * 10)).collect::<Vec<_>>(),
+                    tail.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>()
+                );
+                for i in 0..n {
+                    assert_eq!(i < at, m.contains_key(&i));
+                    assert_eq!(i >= at, tail.contains_key(&i));
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "should be <= len")]
+    fn split_off_out_of_bounds() {
+        let mut m: SmallMap<u32, u32> = (0..3).map(|i| (i, i)).collect();
+        m.split_off(4);
+    }
+
+    #[test]
+    fn append() {
+        let mut m: SmallMap<u32, &str> = [(1, "a"), (2, "b"), (3, "c")].into_iter().collect();
+        let mut other: SmallMap<u32, &str> = (2..30).map(|i| (i, "x")).collect();
+        m.append(&mut other);
+        assert!(other.is_empty());
+        m.state_check();
+        assert_eq!(29, m.len());
+        assert_eq!(
+            vec![(1, "a"), (2, "x"), (3, "x"), (4, "x")],
+            m.iter().take(4).map(|(k, v)| (*k, *v)).collect::<Vec<_>>()
+        );
+        assert_eq!(Some(&"x"), m.get(&29));
+    }
+
+    #[test]
+    fn extend_hashed() {
+        let mut m = SmallMap::new();
+        m.extend_hashed((0..1000).map(|i| (Hashed::new(i), i * 2)));
+        assert_eq!(m.len(), 1000);
+        assert_eq!(m.capacity(), 1000);
+        assert_eq!(m.get(&999), Some(&1998));
+        m.assert_invariants();
+
+        // Extending a map which already has an index.
+        m.extend_hashed((1000..1010).map(|i| (Hashed::new(i), i * 2)));
+        assert_eq!(m.len(), 1010);
+        assert_eq!(m.get(&0), Some(&0));
+        assert_eq!(m.get(&1009), Some(&2018));
+        m.assert_invariants();
+    }
+
     #[test]
     #[allow(clippy::map_identity)]
     fn few_entries() {
@@ -1405,6 +1663,77 @@ mod tests {
         m.assert_invariants();
     }
 
+    #[test]
+    fn test_swap_remove_index() {
+        // Large enough so the index is used.
+        let mut m = (0..100).map(|i| (i, i * 10)).collect::<SmallMap<_, _>>();
+        assert_eq!(Some((5, 50)), m.swap_remove_index(5));
+        assert_eq!(None, m.get(&5));
+        // Last entry moved into the hole.
+        assert_eq!(Some((&99, &990)), m.get_index(5));
+        assert_eq!(Some(&990), m.get(&99));
+        assert_eq!(Some(&60), m.get(&6));
+        m.assert_invariants();
+    }
+
+    #[test]
+    fn test_swap_remove_vs_shift_remove() {
+        for n in [5, 100] {
+            let mut swapped = (0..n).map(|i| (i, i)).collect::<SmallMap<_, _>>();
+            let mut shifted = swapped.clone();
+            assert_eq!(Some(1), swapped.swap_remove(&1));
+            assert_eq!(Some(1), shifted.shift_remove(&1));
+            assert_eq!(None, swapped.swap_remove(&1));
+            swapped.assert_invariants();
+            shifted.assert_invariants();
+
+            let shifted_keys: Vec<_> = shifted.keys().copied().collect();
+            let swapped_keys: Vec<_> = swapped.keys().copied().collect();
+            assert_eq!(
+                [0].into_iter().chain(2..n).collect::<Vec<_>>(),
+                shifted_keys
+            );
+            assert_eq!(
+                [0, n - 1].into_iter().chain(2..n - 1).collect::<Vec<_>>(),
+                swapped_keys
+            );
+        }
+    }
+
+    #[test]
+    fn test_swap_remove_last() {
+        let mut m = (0..100).map(|i| (i, i * 10)).collect::<SmallMap<_, _>>();
+        assert_eq!(Some(990), m.swap_remove(&99));
+        assert_eq!(Some(&980), m.get(&98));
+        assert_eq!(99, m.len());
+        m.assert_invariants();
+    }
+
+    #[test]
+    fn test_key_difference_intersection() {
+        for n in [10, 100] {
+            // Keys `0..n` and `n/2..n + n/2`.
+            let a = (0..n).map(|i| (i, "a")).collect::<SmallMap<_, _>>();
+            let b = (n / 2..n + n / 2).map(|i| (i, "b")).collect::<SmallMap<_, _>>();
+            assert_eq!(
+                (0..n / 2).collect::<Vec<_>>(),
+                a.key_difference(&b).copied().collect::<Vec<_>>()
+            );
+            assert_eq!(
+                (n..n + n / 2).collect::<Vec<_>>(),
+                b.key_difference(&a).copied().collect::<Vec<_>>()
+            );
+            assert_eq!(
+                (n / 2..n).collect::<Vec<_>>(),
+                a.key_intersection(&b).copied().collect::<Vec<_>>()
+            );
+            assert_eq!(
+                (n / 2..n).collect::<Vec<_>>(),
+                b.key_intersection(&a).copied().collect::<Vec<_>>()
+            );
+        }
+    }
+
     #[test]
     fn test_json() {
         let mp = smallmap! {"a".to_owned() => 1, "b".to_owned() => 2};
@@ -346,6 +346,29 @@ impl<A, B> Vec2<A, B> {
         }
     }
 
+    /// Remove an element by index, replacing it with the last element.
+    ///
+    /// This is an `O(1)` operation, but it does not preserve the order of elements.
+    pub fn swap_remove(&mut self, index: usize) -> (A, B) {
+        assert!(index < self.len);
+        unsafe {
+            let (a, b) = self.read(index);
+            let last = self.len - 1;
+            ptr::copy(
+                self.aaa_ptr().as_ptr().add(last),
+                self.aaa_ptr().as_ptr().add(index),
+                1,
+            );
+            ptr::copy(
+                self.bbb_ptr().as_ptr().add(last),
+                self.bbb_ptr().as_ptr().add(index),
+                1,
+            );
+            self.len = last;
+            (a, b)
+        }
+    }
+
     /// Remove all elements.
     #[inline]
     pub fn clear(&mut self) {
@@ -489,6 +512,17 @@ impl<A, B> Vec2<A, B> {
         }
     }
 
+    /// Iterate over the elements, with mutable access to both components.
+    #[inline]
+    pub fn iter_mut(
+        &mut self,
+    ) -> impl ExactSizeIterator<Item = (&mut A, &mut B)> + DoubleEndedIterator {
+        // `aaa` and `bbb` are disjoint, so both can be borrowed mutably at once.
+        let aaa = unsafe { slice::from_raw_parts_mut(self.aaa_ptr().as_ptr(), self.len) };
+        let bbb = unsafe { slice::from_raw_parts_mut(self.bbb_ptr().as_ptr(), self.len) };
+        aaa.iter_mut().zip(bbb.iter_mut())
+    }
+
     pub(crate) fn sort_insertion_by<F>(&mut self, mut compare: F)
     where
         F: FnMut((&A, &B), (&A, &B)) -> Ordering,
@@ -741,6 +775,42 @@ mod tests {
         assert_eq!(Some((&3, &4)), v.get(1));
     }
 
+    #[test]
+    fn test_swap_remove() {
+        let mut v = Vec2::new();
+        v.push(1, 2);
+        v.push(2, 3);
+        v.push(3, 4);
+        v.push(4, 5);
+        assert_eq!((2, 3), v.swap_remove(1));
+        assert_eq!(3, v.len());
+        assert_eq!(Some((&1, &2)), v.get(0));
+        assert_eq!(Some((&4, &5)), v.get(1));
+        assert_eq!(Some((&3, &4)), v.get(2));
+        assert_eq!((3, 4), v.swap_remove(2));
+        assert_eq!(2, v.len());
+        assert_eq!(Some((&4, &5)), v.last());
+    }
+
+    #[test]
+    fn test_iter_mut() {
+        let mut v = Vec2::new();
+        v.push(1, "a".to_owned());
+        v.push(2, "b".to_owned());
+        v.push(3, "c".to_owned());
+        for (a, b) in v.iter_mut() {
+            *a *= 10;
+            b.push('!');
+        }
+        assert_eq!(
+            vec![(&10, &"a!".to_owned()), (&20, &"b!".to_owned()), (&30, &"c!".to_owned())],
+            v.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(3, v.iter_mut().len());
+        let (a, b) = v.iter_mut().next_back().unwrap();
+        assert_eq!((&mut 30, &mut "c!".to_owned()), (a, b));
+    }
+
     #[test]
     fn test_first() {
         let mut v: Vec2<u32, u32> = Vec2::new();
@@ -133,6 +133,18 @@ impl<K, V> VecMap<K, V> {
         self.buckets.push((key.into_key(), value), hash);
     }
 
+    /// Append entries, reserving space once using the iterator size hint.
+    ///
+    /// Keys are not checked for duplicates: the caller must ensure
+    /// they are unique and not already present in the map.
+    pub(crate) fn extend_hashed(&mut self, iter: impl IntoIterator<Item = (Hashed<K>, V)>) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for (key, value) in iter {
+            self.insert_hashed_unique_unchecked(key, value);
+        }
+    }
+
     pub(crate) fn remove_hashed_entry<Q>(&mut self, key: Hashed<&Q>) -> Option<(K, V)>
     where
         Q: ?Sized + Equivalent<K>,
@@ -151,6 +163,15 @@ impl<K, V> VecMap<K, V> {
         (Hashed::new_unchecked(hash, key), value)
     }
 
+    /// Remove the entry by index, moving the last entry into its place.
+    ///
+    /// This is an `O(1)` operation, but it does not preserve insertion order.
+    #[inline]
+    pub(crate) fn swap_remove(&mut self, index: usize) -> (Hashed<K>, V) {
+        let ((key, value), hash) = self.buckets.swap_remove(index);
+        (Hashed::new_unchecked(hash, key), value)
+    }
+
     #[inline]
     pub(crate) fn pop(&mut self) -> Option<(Hashed<K>, V)> {
         let ((key, value), hash) = self.buckets.pop()?;
@@ -282,3 +303,27 @@ impl<K, V> VecMap<K, V> {
         self.buckets.retain(|(k, v), _| f(k, v));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::hashed::Hashed;
+    use crate::vec_map::VecMap;
+
+    #[test]
+    fn test_extend_hashed() {
+        let mut m = VecMap::new();
+        m.extend_hashed((0..1000).map(|i| (Hashed::new(i), i * 2)));
+        assert_eq!(1000, m.len());
+        // Size hint is exact, so a single allocation is enough.
+        assert_eq!(1000, m.capacity());
+        assert_eq!(Some((&7, &14)), m.get_index(7));
+        assert_eq!(Some(999), m.get_index_of_hashed(Hashed::new(&999)));
+
+        // Extending a non-empty map keeps existing entries.
+        m.extend_hashed((1000..1010).map(|i| (Hashed::new(i), i * 2)));
+        assert_eq!(1010, m.len());
+        assert!(m.capacity() >= 1010);
+        assert_eq!(Some((&0, &0)), m.get_index(0));
+        assert_eq!(Some((&1009, &2018)), m.get_index(1009));
+    }
+}
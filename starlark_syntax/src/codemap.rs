@@ -251,6 +251,19 @@ struct CodeMapData {
     source: String,
     /// Byte positions of line beginnings.
     lines: Vec<Pos>,
+    /// Files this codemap was merged from, ordered by position.
+    /// Empty for a codemap of a single file.
+    files: Vec<CodeMapFile>,
+}
+
+/// A file within a merged codemap.
+#[derive(Debug, Allocative)]
+struct CodeMapFile {
+    filename: String,
+    /// Position of the first byte of the file.
+    begin: Pos,
+    /// Line of the merged source where the file starts.
+    first_line: usize,
 }
 
 /// "Codemap" for `.rs` files.
@@ -312,16 +325,59 @@ impl Hash for CodeMap {
 impl CodeMap {
     /// Creates an new `CodeMap`.
     pub fn new(filename: String, source: String) -> CodeMap {
-        let mut lines = vec![Pos(0)];
-        lines.extend(source.match_indices('\n').map(|(p, _)| Pos(p as u32 + 1)));
+        let lines = Self::line_starts(&source);
+        CodeMap(CodeMapImpl::Real(Arc::new(CodeMapData {
+            filename,
+            source,
+            lines,
+            files: Vec::new(),
+        })))
+    }
 
+    /// Creates a `CodeMap` from several named sources, concatenated in order.
+    ///
+    /// Each source starts on a new line. Spans in the merged codemap are reported
+    /// with the name of the file they fall in and lines relative to that file,
+    /// see [`resolve_file_span`](CodeMap::resolve_file_span).
+    /// [`filename`](CodeMap::filename) is the name of the first file.
+    pub fn new_merged(files: Vec<(String, String)>) -> CodeMap {
+        let mut source = String::new();
+        let mut parts = Vec::with_capacity(files.len());
+        for (filename, text) in files {
+            if !source.is_empty() && !source.ends_with('\n') {
+                source.push('\n');
+            }
+            parts.push((filename, Pos(source.len() as u32)));
+            source.push_str(&text);
+        }
+        let filename = parts
+            .first()
+            .map(|(filename, _)| filename.clone())
+            .unwrap_or_default();
+        let lines = Self::line_starts(&source);
+        let files = parts
+            .into_iter()
+            .map(|(filename, begin)| CodeMapFile {
+                first_line: lines.partition_point(|&x| x <= begin) - 1,
+                filename,
+                begin,
+            })
+            .collect();
         CodeMap(CodeMapImpl::Real(Arc::new(CodeMapData {
             filename,
             source,
             lines,
+            files,
         })))
     }
 
+    /// Byte positions of line beginnings.
+    fn line_starts(source: &str) -> Vec<Pos> {
+        let mut lines = vec![Pos(0)];
+        lines.extend(source.match_indices('\n').map(|(p, _)| Pos(p as u32 + 1)));
+        lines
+    }
+
     pub fn empty_static() -> &'static CodeMap {
         static EMPTY_CODEMAP: Lazy<CodeMap> = Lazy::new(CodeMap::default);
         &EMPTY_CODEMAP
@@ -359,6 +415,49 @@ impl CodeMap {
         }
     }
 
+    /// The file of a merged codemap containing the position.
+    fn file_at(&self, pos: Pos) -> Option<&CodeMapFile> {
+        match &self.0 {
+            CodeMapImpl::Real(data) => {
+                let i = data.files.partition_point(|x| x.begin <= pos);
+                data.files.get(i.checked_sub(1)?)
+            }
+            CodeMapImpl::Native(_) => None,
+        }
+    }
+
+    /// Gets the name of the file containing the position.
+    ///
+    /// Same as [`filename`](CodeMap::filename) unless the codemap was created with
+    /// [`new_merged`](CodeMap::new_merged).
+    pub fn filename_at(&self, pos: Pos) -> &str {
+        match self.file_at(pos) {
+            Some(file) => &file.filename,
+            None => self.filename(),
+        }
+    }
+
+    /// Gets the name of the file containing the span, with lines relative to that file.
+    ///
+    /// Same as [`resolve_span`](CodeMap::resolve_span) with [`filename`](CodeMap::filename)
+    /// unless the codemap was created with [`new_merged`](CodeMap::new_merged).
+    pub fn resolve_file_span(&self, span: Span) -> ResolvedFileSpan {
+        ResolvedFileSpan {
+            file: self.filename_at(span.begin).to_owned(),
+            span: self.resolve_span_in_file(span),
+        }
+    }
+
+    /// Like `resolve_span`, but lines are relative to the file containing the span beginning.
+    fn resolve_span_in_file(&self, span: Span) -> ResolvedSpan {
+        let mut resolved = self.resolve_span(span);
+        if let Some(file) = self.file_at(span.begin) {
+            resolved.begin.line -= file.first_line;
+            resolved.end.line -= file.first_line;
+        }
+        resolved
+    }
+
     pub fn byte_at(&self, pos: Pos) -> u8 {
         self.source().as_bytes()[pos.0 as usize]
     }
@@ -460,6 +559,50 @@ impl CodeMap {
         ResolvedSpan::from_span(begin, end)
     }
 
+    /// Gets the Pos of a line and column, the inverse of [`resolve_span`](CodeMap::resolve_span).
+    ///
+    /// The line is 0-indexed, the column is 0-indexed in characters,
+    /// and may point just past the last character of the line.
+    ///
+    /// Returns None if the line or column is out of range.
+    pub fn offset_at(&self, line: usize, column: usize) -> Option<Pos> {
+        match &self.0 {
+            CodeMapImpl::Real(_) => {
+                let line_span = self.line_span_opt(line)?;
+                // `\r` of `\r\n` is counted as a column by `resolve_span`, so only trim `\n`.
+                let text = self.source_span(line_span).trim_end_matches('\n');
+                let byte_col = match text.char_indices().nth(column) {
+                    Some((i, _)) => i,
+                    None if text.chars().count() == column => text.len(),
+                    None => return None,
+                };
+                Some(line_span.begin + byte_col as u32)
+            }
+            CodeMapImpl::Native(data) => {
+                if line != data.start.line {
+                    return None;
+                }
+                let col = column.checked_sub(data.start.column)?;
+                if col > NativeCodeMap::SOURCE.len() {
+                    return None;
+                }
+                Some(Pos(col as u32))
+            }
+        }
+    }
+
+    /// Gets the Span of a line and column range, the inverse of [`resolve_span`](CodeMap::resolve_span).
+    ///
+    /// Returns None if either end is out of range, or the end is before the beginning.
+    pub fn span_at_line_col(&self, span: ResolvedSpan) -> Option<Span> {
+        let begin = self.offset_at(span.begin.line, span.begin.column)?;
+        let end = self.offset_at(span.end.line, span.end.column)?;
+        if begin > end {
+            return None;
+        }
+        Some(Span::new(begin, end))
+    }
+
     /// Gets the source text of a line.
     ///
     /// The string returned does not include the terminating \r or \n characters.
@@ -536,7 +679,7 @@ impl<'a> fmt::Display for FileSpanRef<'a> {
     /// Formats the span as `filename:start_line:start_column: end_line:end_column`,
     /// or if the span is zero-length, `filename:line:column`, with a 1-indexed line and column.
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(f, "{}:{}", self.file.filename(), self.resolve_span())
+        write!(f, "{}:{}", self.filename(), self.resolve_span())
     }
 }
 
@@ -550,8 +693,8 @@ impl fmt::Display for FileSpan {
 
 impl<'a> FileSpanRef<'a> {
     /// Filename of this reference.
-    pub fn filename(&self) -> &str {
-        self.file.filename()
+    pub fn filename(&self) -> &'a str {
+        self.file.filename_at(self.span.begin)
     }
 
     /// Convert to the owned span.
@@ -564,7 +707,7 @@ impl<'a> FileSpanRef<'a> {
 
     /// Resolve span offsets to lines and columns.
     pub fn resolve_span(&self) -> ResolvedSpan {
-        self.file.resolve_span(self.span)
+        self.file.resolve_span_in_file(self.span)
     }
 
     /// Resolve the span.
@@ -583,7 +726,7 @@ impl FileSpan {
 
     /// Filename of this span.
     pub fn filename(&self) -> &str {
-        self.file.filename()
+        self.file.filename_at(self.span.begin)
     }
 
     /// Resolve the span.
@@ -606,10 +749,7 @@ impl FileSpan {
 
     /// Resolve the span to lines and columns.
     pub fn resolve(&self) -> ResolvedFileSpan {
-        ResolvedFileSpan {
-            file: self.file.filename().to_owned(),
-            span: self.file.resolve_span(self.span),
-        }
+        self.file.resolve_file_span(self.span)
     }
 }
 
@@ -824,6 +964,67 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_merged() {
+        let codemap = CodeMap::new_merged(vec![
+            ("a.star".to_owned(), "x = 1\ny = 2".to_owned()),
+            ("b.star".to_owned(), "z = 3\nw = x\n".to_owned()),
+        ]);
+        assert_eq!(codemap.source(), "x = 1\ny = 2\nz = 3\nw = x\n");
+        assert_eq!(codemap.filename(), "a.star");
+
+        let y = codemap.file_span(Span::new(Pos(6), Pos(7)));
+        assert_eq!(y.source_span(), "y");
+        assert_eq!(y.filename(), "a.star");
+        assert_eq!(y.to_string(), "a.star:2:1-2");
+
+        let x = codemap.file_span(Span::new(Pos(22), Pos(23)));
+        assert_eq!(x.source_span(), "x");
+        assert_eq!(x.filename(), "b.star");
+        assert_eq!(x.to_string(), "b.star:2:5-6");
+        assert_eq!(
+            x.resolve(),
+            ResolvedFileSpan::_testing_parse("b.star:2:5-6")
+        );
+        // Lines of the whole codemap are still available.
+        assert_eq!(codemap.resolve_span(x.span).begin.line, 3);
+    }
+
+    #[test]
+    fn test_offset_at() {
+        let source = "abcd\nefghij\r\n\n°N 汉语";
+        let codemap = CodeMap::new("test.star".to_owned(), source.to_owned());
+
+        assert_eq!(codemap.offset_at(0, 0), Some(Pos(0)));
+        assert_eq!(codemap.offset_at(0, 4), Some(Pos(4)));
+        assert_eq!(codemap.offset_at(0, 5), None);
+        assert_eq!(codemap.offset_at(1, 6), Some(Pos(11)));
+        assert_eq!(codemap.offset_at(1, 7), Some(Pos(12)));
+        assert_eq!(codemap.offset_at(1, 8), None);
+        assert_eq!(codemap.offset_at(2, 0), Some(Pos(13)));
+        assert_eq!(codemap.offset_at(2, 1), None);
+        assert_eq!(codemap.offset_at(3, 3), Some(Pos(18)));
+        assert_eq!(codemap.offset_at(3, 5), Some(Pos(24)));
+        assert_eq!(codemap.offset_at(3, 6), None);
+        assert_eq!(codemap.offset_at(4, 0), None);
+
+        // Round trip with `resolve_span`.
+        for (i, _) in source.char_indices() {
+            let span = Span::new(Pos(i as u32), codemap.full_span().end);
+            assert_eq!(
+                codemap.span_at_line_col(codemap.resolve_span(span)),
+                Some(span),
+                "{i}"
+            );
+        }
+
+        let span = ResolvedSpan::from_span(
+            ResolvedPos { line: 1, column: 2 },
+            ResolvedPos { line: 0, column: 2 },
+        );
+        assert_eq!(codemap.span_at_line_col(span), None);
+    }
+
     #[test]
     fn test_line_col_span_display_point() {
         let line_col = ResolvedPos { line: 0, column: 0 };
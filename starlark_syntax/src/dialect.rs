@@ -63,6 +63,17 @@ pub struct Dialect {
     ///
     /// [Starlark spec proposal](https://github.com/bazelbuild/starlark/issues/91).
     pub enable_f_strings: bool,
+    /// Are `{1, 2}` set literals supported?
+    /// Disabled by default.
+    pub enable_set_literals: bool,
+    /// Are `with expr as target:` statements supported?
+    /// The value must implement the context manager protocol,
+    /// i.e. have `__enter__` and `__exit__` methods.
+    /// Disabled by default.
+    pub enable_with_statements: bool,
+    /// Are underscores allowed to separate digits in numbers, e.g. `1_000_000` or `0x_FF`?
+    /// Disabled by default.
+    pub enable_numeric_underscores: bool,
     /// Like `#[non_exhaustive]`, but allows struct expression.
     ///
     /// [Explanation](https://github.com/rust-lang/rust-clippy/issues/6559).
@@ -92,6 +103,9 @@ impl Dialect {
         enable_load_reexport: true, // But they plan to change it
         enable_top_level_stmt: false,
         enable_f_strings: false,
+        enable_set_literals: false,
+        enable_with_statements: false,
+        enable_numeric_underscores: false,
         _non_exhaustive: (),
     };
 
@@ -107,6 +121,9 @@ impl Dialect {
         enable_load_reexport: true,
         enable_top_level_stmt: true,
         enable_f_strings: false,
+        enable_set_literals: false,
+        enable_with_statements: false,
+        enable_numeric_underscores: false,
         _non_exhaustive: (),
     };
 
@@ -122,6 +139,9 @@ impl Dialect {
         enable_load_reexport: true,
         enable_top_level_stmt: true,
         enable_f_strings: true,
+        enable_set_literals: true,
+        enable_with_statements: true,
+        enable_numeric_underscores: true,
         _non_exhaustive: (),
     };
 }
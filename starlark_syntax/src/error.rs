@@ -18,6 +18,8 @@
 use std::fmt;
 use std::mem;
 
+use starlark_map::small_map::SmallMap;
+
 use crate::call_stack::CallStack;
 use crate::codemap::CodeMap;
 use crate::codemap::FileSpan;
@@ -149,6 +151,14 @@ impl Error {
         }
     }
 
+    /// Named arguments passed to `fail`, if this error was produced by `fail` with any.
+    pub fn fail_context(&self) -> Option<&SmallMap<String, String>> {
+        match self.kind() {
+            ErrorKind::Fail(e) => e.downcast_ref::<FailWithContext>().map(|e| &e.context),
+            _ => None,
+        }
+    }
+
     /// Change error kind to internal error.
     pub fn into_internal_error(self) -> Error {
         if let ErrorKind::Internal(_) = self.kind() {
@@ -159,6 +169,26 @@ impl Error {
     }
 }
 
+/// Payload of [`ErrorKind::Fail`] produced by `fail(msg, **context)`.
+///
+/// Errors outlive the heap, so the context values are stored
+/// as strings, formatted like the positional arguments of `fail`.
+#[derive(Debug)]
+pub struct FailWithContext {
+    /// The message formatted from the positional arguments of `fail`.
+    pub message: String,
+    /// The named arguments of `fail`.
+    pub context: SmallMap<String, String>,
+}
+
+impl fmt::Display for FailWithContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for FailWithContext {}
+
 fn fmt_impl(this: &Error, is_debug: bool, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     if this.has_diagnostic() {
         // Not showing the context trace without `{:#}` or `{:?}` is the same thing that anyhow does
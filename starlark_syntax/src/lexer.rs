@@ -20,6 +20,7 @@ use std::collections::VecDeque;
 use std::fmt;
 use std::fmt::Display;
 
+use dupe::Dupe;
 use logos::Logos;
 use num_bigint::BigInt;
 use num_traits::Num;
@@ -53,6 +54,14 @@ pub enum LexemeError {
     StartsZero(String),
     #[error("Parse error: failed to parse integer: `{0}`")]
     IntParse(String),
+    #[error("Parse error: integer literal has no digits, got `{0}`")]
+    IntNoDigits(String),
+    #[error("Parse error: underscores in integer literal must separate digits, got `{0}`")]
+    IntUnderscore(String),
+    #[error("Parse error: underscores in float literal must separate digits, got `{0}`")]
+    FloatUnderscore(String),
+    #[error("Parse error: underscores in numbers are not allowed in this dialect, got `{0}`")]
+    NumericUnderscoresDisabled(String),
     #[error("Comment span is computed incorrectly (internal error)")]
     CommentSpanComputedIncorrectly,
     #[error("Cannot parse `{0}` as an integer in base {1}")]
@@ -82,10 +91,14 @@ pub struct Lexer<'a> {
     parens: isize, // Number of parens we have seen
     lexer: logos::Lexer<'a, Token>,
     done: bool,
+    /// Are `with` and `as` keywords rather than reserved words?
+    enable_with_statements: bool,
+    /// Can underscores separate digits in numbers?
+    enable_numeric_underscores: bool,
 }
 
 impl<'a> Lexer<'a> {
-    pub fn new(input: &'a str, _dialect: &Dialect, codemap: CodeMap) -> Self {
+    pub fn new(input: &'a str, dialect: &Dialect, codemap: CodeMap) -> Self {
         let lexer = Token::lexer(input);
         let mut lexer2 = Self {
             codemap,
@@ -95,6 +108,8 @@ impl<'a> Lexer<'a> {
             lexer,
             parens: 0,
             done: false,
+            enable_with_statements: dialect.enable_with_statements,
+            enable_numeric_underscores: dialect.enable_numeric_underscores,
         };
         if let Err(e) = lexer2.calculate_indent() {
             lexer2.buffer.push_back(Err(e));
@@ -429,14 +444,58 @@ impl<'a> Lexer<'a> {
         )
     }
 
+    /// Parse the digits of an integer literal, without the radix prefix.
+    /// Single underscores may separate digits, or follow the radix prefix.
     fn int(&self, s: &str, radix: u32) -> Lexeme {
         let span = self.lexer.span();
+        if !s.bytes().any(|c| c != b'_') {
+            return self.err_now(LexemeError::IntNoDigits);
+        }
+        let digits;
+        let s = if s.contains('_') {
+            if !self.enable_numeric_underscores {
+                return self.err_now(LexemeError::NumericUnderscoresDisabled);
+            }
+            if s.ends_with('_') || s.contains("__") {
+                return self.err_now(LexemeError::IntUnderscore);
+            }
+            digits = s.replace('_', "");
+            &digits
+        } else {
+            s
+        };
         match TokenInt::from_str_radix(s, radix) {
             Ok(i) => Ok((span.start, Token::Int(i), span.end)),
             Err(_) => self.err_now(LexemeError::IntParse),
         }
     }
 
+    /// Parse a float literal. Single underscores may separate digits.
+    fn float(&self, s: &str) -> Lexeme {
+        let span = self.lexer.span();
+        let digits;
+        let s = if s.contains('_') {
+            if !self.enable_numeric_underscores {
+                return self.err_now(LexemeError::NumericUnderscoresDisabled);
+            }
+            let b = s.as_bytes();
+            let separates_digits = |i: usize| {
+                i > 0 && b[i - 1].is_ascii_digit() && b.get(i + 1).is_some_and(u8::is_ascii_digit)
+            };
+            if !(0..b.len()).filter(|&i| b[i] == b'_').all(separates_digits) {
+                return self.err_now(LexemeError::FloatUnderscore);
+            }
+            digits = s.replace('_', "");
+            &digits
+        } else {
+            s
+        };
+        match s.parse() {
+            Ok(f) => Ok((span.start, Token::Float(f), span.end)),
+            Err(_) => self.err_now(LexemeError::InvalidInput),
+        }
+    }
+
     pub fn next(&mut self) -> Option<Lexeme> {
         loop {
             // Note that this function doesn't always return - a few branches use `continue`
@@ -475,10 +534,14 @@ impl<'a> Lexer<'a> {
                             }
                         }
                         Token::Reserved => Some(self.err_now(LexemeError::ReservedKeyword)),
+                        Token::As | Token::With if !self.enable_with_statements => {
+                            Some(self.err_now(LexemeError::ReservedKeyword))
+                        }
                         Token::Error => Some(self.err_now(LexemeError::InvalidInput)),
                         Token::RawDecInt => {
                             let s = self.lexer.slice();
-                            if s.len() > 1 && &s[0..1] == "0" {
+                            // Like `0`, `0_0` is fine, but other digits cannot follow a leading 0.
+                            if s.starts_with('0') && s.bytes().any(|c| c != b'0' && c != b'_') {
                                 return Some(self.err_now(LexemeError::StartsZero));
                             }
                             Some(self.int(s, 10))
@@ -499,6 +562,8 @@ impl<'a> Lexer<'a> {
                             Some(self.int(&s[2..], 2))
                         }
                         Token::Int(..) => unreachable!("Lexer does not produce Int tokens"),
+                        Token::RawFloat => Some(self.float(self.lexer.slice())),
+                        Token::Float(..) => unreachable!("Lexer does not produce Float tokens"),
                         Token::RawDoubleQuote => {
                             let raw = self.lexer.span().len() == 2;
                             self.parse_double_quoted_string(raw)
@@ -659,8 +724,7 @@ pub enum Token {
     RawFStringDoubleQuote,
 
     #[regex(
-        "as|\
-        assert|\
+        "assert|\
         async|\
         await|\
         class|\
@@ -675,7 +739,6 @@ pub enum Token {
         raise|\
         try|\
         while|\
-        with|\
         yield"
     )]
     Reserved, // One of the reserved keywords
@@ -685,20 +748,24 @@ pub enum Token {
     , |lex| lex.slice().to_owned())]
     Identifier(String), // An identifier
 
-    #[regex("[0-9]+")]
+    // Underscores and missing digits are validated when converting to `Int`.
+    #[regex("[0-9][0-9_]*")]
     RawDecInt,
-    #[regex("0[xX][A-Fa-f0-9]+")]
+    #[regex("0[xX][A-Fa-f0-9_]*")]
     RawHexInt,
-    #[regex("0[bB][01]+")]
+    #[regex("0[bB][01_]*")]
     RawBinInt,
-    #[regex("0[oO][0-7]+")]
+    #[regex("0[oO][0-7_]*")]
     RawOctInt,
 
     Int(TokenInt), // An integer literal (123, 0x1, 0b1011, 0o755, ...)
 
-    #[regex("[0-9]+\\.[0-9]*([eE][-+]?[0-9]+)?", |lex| lex.slice().parse::<f64>())]
-    #[regex("[0-9]+[eE][-+]?[0-9]+", |lex| lex.slice().parse::<f64>())]
-    #[regex("\\.[0-9]+([eE][-+]?[0-9]+)?", |lex| lex.slice().parse::<f64>())]
+    // Underscores are validated when converting to `Float`.
+    #[regex("[0-9][0-9_]*\\.([0-9][0-9_]*)?([eE][-+]?[0-9][0-9_]*)?")]
+    #[regex("[0-9][0-9_]*[eE][-+]?[0-9][0-9_]*")]
+    #[regex("\\.[0-9][0-9_]*([eE][-+]?[0-9][0-9_]*)?")]
+    RawFloat,
+
     Float(f64), // A float literal (3.14, .3, 1e6, 0.)
 
     String(String), // A string literal
@@ -708,6 +775,8 @@ pub enum Token {
     // Keywords
     #[token("and")]
     And,
+    #[token("as")]
+    As,
     #[token("break")]
     Break,
     #[token("continue")]
@@ -736,6 +805,8 @@ pub enum Token {
     Pass,
     #[token("return")]
     Return,
+    #[token("with")]
+    With,
     // Symbols
     #[token(",")]
     Comma,
@@ -827,7 +898,111 @@ pub enum Token {
     ClosingRound,
 }
 
+/// Broad category of a [`Token`], for example for syntax highlighting.
+#[derive(Debug, Clone, Copy, Dupe, Eq, PartialEq, Hash)]
+pub enum TokenKind {
+    /// Keyword, including reserved keywords.
+    Keyword,
+    /// Identifier.
+    Identifier,
+    /// String or f-string literal.
+    String,
+    /// Integer or float literal.
+    Number,
+    /// Operator, punctuation or bracket.
+    Operator,
+    /// Comment.
+    Comment,
+    /// Newline, indentation or dedentation.
+    Layout,
+}
+
 impl Token {
+    /// Category of this token.
+    pub fn kind(&self) -> TokenKind {
+        match self {
+            Token::And
+            | Token::As
+            | Token::Break
+            | Token::Continue
+            | Token::Def
+            | Token::Elif
+            | Token::Else
+            | Token::For
+            | Token::If
+            | Token::In
+            | Token::Lambda
+            | Token::Load
+            | Token::Not
+            | Token::Or
+            | Token::Pass
+            | Token::Return
+            | Token::With
+            | Token::Reserved => TokenKind::Keyword,
+            Token::Identifier(_) => TokenKind::Identifier,
+            Token::String(_)
+            | Token::FString(_)
+            | Token::RawSingleQuote
+            | Token::RawDoubleQuote
+            | Token::RawFStringSingleQuote
+            | Token::RawFStringDoubleQuote => TokenKind::String,
+            Token::Int(_)
+            | Token::Float(_)
+            | Token::RawFloat
+            | Token::RawDecInt
+            | Token::RawHexInt
+            | Token::RawOctInt
+            | Token::RawBinInt => TokenKind::Number,
+            Token::Comment(_) => TokenKind::Comment,
+            Token::Indent | Token::Dedent | Token::Newline | Token::Tabs | Token::Error => {
+                TokenKind::Layout
+            }
+            Token::Comma
+            | Token::Semicolon
+            | Token::Colon
+            | Token::PlusEqual
+            | Token::MinusEqual
+            | Token::StarEqual
+            | Token::SlashEqual
+            | Token::SlashSlashEqual
+            | Token::PercentEqual
+            | Token::EqualEqual
+            | Token::BangEqual
+            | Token::LessEqual
+            | Token::GreaterEqual
+            | Token::StarStar
+            | Token::MinusGreater
+            | Token::Equal
+            | Token::LessThan
+            | Token::GreaterThan
+            | Token::Minus
+            | Token::Plus
+            | Token::Star
+            | Token::Percent
+            | Token::Slash
+            | Token::SlashSlash
+            | Token::Dot
+            | Token::Ampersand
+            | Token::Pipe
+            | Token::Caret
+            | Token::LessLess
+            | Token::GreaterGreater
+            | Token::Tilde
+            | Token::AmpersandEqual
+            | Token::PipeEqual
+            | Token::CaretEqual
+            | Token::LessLessEqual
+            | Token::GreaterGreaterEqual
+            | Token::Ellipsis
+            | Token::OpeningSquare
+            | Token::OpeningCurly
+            | Token::OpeningRound
+            | Token::ClosingSquare
+            | Token::ClosingCurly
+            | Token::ClosingRound => TokenKind::Operator,
+        }
+    }
+
     /// Used for testing
     #[cfg(test)]
     pub fn unlex(&self) -> String {
@@ -872,6 +1047,7 @@ impl Display for Token {
             Token::Dedent => write!(f, "end of indentation block"),
             Token::Newline => write!(f, "new line"),
             Token::And => write!(f, "keyword 'and'"),
+            Token::As => write!(f, "keyword 'as'"),
             Token::Else => write!(f, "keyword 'else'"),
             Token::Load => write!(f, "keyword 'load'"),
             Token::Break => write!(f, "keyword 'break'"),
@@ -885,6 +1061,7 @@ impl Display for Token {
             Token::Pass => write!(f, "keyword 'pass'"),
             Token::Elif => write!(f, "keyword 'elif'"),
             Token::Return => write!(f, "keyword 'return'"),
+            Token::With => write!(f, "keyword 'with'"),
             Token::Lambda => write!(f, "keyword 'lambda'"),
             Token::Comma => write!(f, "symbol ','"),
             Token::Semicolon => write!(f, "symbol ';'"),
@@ -936,6 +1113,7 @@ impl Display for Token {
             Token::RawHexInt => write!(f, "hexadecimal integer literal"),
             Token::RawOctInt => write!(f, "octal integer literal"),
             Token::RawBinInt => write!(f, "binary integer literal"),
+            Token::RawFloat => write!(f, "float literal"),
             Token::Float(n) => write!(f, "float literal '{}'", n),
             Token::String(s) => write!(f, "string literal {:?}", s),
             Token::RawSingleQuote => write!(f, "starting '"),
@@ -957,6 +1135,24 @@ impl<'a> Iterator for Lexer<'a> {
     }
 }
 
+/// Lex a Starlark file into tokens, without parsing it.
+///
+/// Comments are included, and so are the layout tokens ([`TokenKind::Layout`])
+/// the parser uses for newlines and indentation, which may have empty spans.
+/// Use [`Token::kind`] to categorize the tokens, for example for syntax highlighting.
+pub fn lex(filename: &str, src: &str, dialect: &Dialect) -> crate::Result<Vec<(Span, Token)>> {
+    let codemap = CodeMap::new(filename.to_owned(), src.to_owned());
+    Lexer::new(src, dialect, codemap)
+        .map(|lexeme| match lexeme {
+            Ok((begin, token, end)) => Ok((
+                Span::new(Pos::new(begin as u32), Pos::new(end as u32)),
+                token,
+            )),
+            Err(e) => Err(e.into_error()),
+        })
+        .collect()
+}
+
 pub fn lex_exactly_one_identifier(s: &str) -> Option<String> {
     let mut lexer = Token::lexer(s);
     match (lexer.next(), lexer.next()) {
@@ -967,7 +1163,12 @@ pub fn lex_exactly_one_identifier(s: &str) -> Option<String> {
 
 #[cfg(test)]
 mod tests {
+    use crate::codemap::Pos;
+    use crate::codemap::Span;
+    use crate::dialect::Dialect;
+    use crate::lexer::lex;
     use crate::lexer::lex_exactly_one_identifier;
+    use crate::lexer::TokenKind;
 
     #[test]
     fn test_is_valid_identifier() {
@@ -977,4 +1178,49 @@ mod tests {
         assert_eq!(lex_exactly_one_identifier("not"), None);
         assert_eq!(lex_exactly_one_identifier("123"), None);
     }
+
+    #[test]
+    fn test_lex_token_kinds() {
+        let src = "def f(x):\n    return x + 0x1F  # hex\ns = 'a'\n";
+        let tokens = lex("test.star", src, &Dialect::Standard).unwrap();
+        let kinds: Vec<(TokenKind, &str)> = tokens
+            .iter()
+            .filter(|(_, t)| t.kind() != TokenKind::Layout)
+            .map(|(span, t)| {
+                (
+                    t.kind(),
+                    &src[span.begin().get() as usize..span.end().get() as usize],
+                )
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (TokenKind::Keyword, "def"),
+                (TokenKind::Identifier, "f"),
+                (TokenKind::Operator, "("),
+                (TokenKind::Identifier, "x"),
+                (TokenKind::Operator, ")"),
+                (TokenKind::Operator, ":"),
+                (TokenKind::Keyword, "return"),
+                (TokenKind::Identifier, "x"),
+                (TokenKind::Operator, "+"),
+                (TokenKind::Number, "0x1F"),
+                (TokenKind::Comment, "# hex"),
+                (TokenKind::Identifier, "s"),
+                (TokenKind::Operator, "="),
+                (TokenKind::String, "'a'"),
+            ]
+        );
+        assert!(tokens.contains(&(
+            Span::new(Pos::new(9), Pos::new(10)),
+            crate::lexer::Token::Newline
+        )));
+    }
+
+    #[test]
+    fn test_lex_error() {
+        let err = lex("test.star", "x = 'unterminated", &Dialect::Standard).unwrap_err();
+        assert!(err.to_string().contains("unfinished string"), "{}", err);
+    }
 }
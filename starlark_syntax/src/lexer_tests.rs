@@ -138,10 +138,21 @@ fn test_int_lit() {
 0x7F 0x7d
 0B1011 0b1010
 0o755 0O753
+1_000_000 0x_FF 0b1010_1010 0o7_5_5 0_0
 "#,
     );
     // Starlark requires us to ban leading zeros (confusion with implicit octal)
-    lexer_fail_golden_test("int_lit", &["x = 01"]);
+    lexer_fail_golden_test(
+        "int_lit",
+        &[
+            "x = 01",
+            "x = 0_1",
+            "x = 0x",
+            "x = 0b_",
+            "x = 1__000",
+            "x = 1000_",
+        ],
+    );
 }
 
 #[test]
@@ -191,7 +202,7 @@ fn test_number_collated_with_keywords_or_identifier() {
 fn test_reserved() {
     lexer_fail_golden_test(
         "reserved",
-        &"as import is class nonlocal del raise except try finally while from with global yield"
+        &"import is class nonlocal del raise except try finally while from global yield"
             .split_whitespace()
             .collect::<Vec<&str>>(),
     );
@@ -407,8 +418,24 @@ fn test_float_lit() {
 1e10 1e+10 1e-10
 1.1e10 1.1e+10 1.1e-10
 0. .123 3.14 .2e3 1E+4
+1_000.5 1_0.0_1e1_0 .5_5
 "#,
     );
+    lexer_fail_golden_test(
+        "float_lit",
+        &["x = 1_.5", "x = 1.5_", "x = 1_e5", "x = 1.5e1__0"],
+    );
+}
+
+#[test]
+fn test_numeric_underscores_dialect() {
+    for program in ["1_000", "0x_FF", "0_0", "1_000.5"] {
+        let err = crate::lexer::lex("x", program, &Dialect::Standard).unwrap_err();
+        assert!(
+            err.to_string().contains("not allowed in this dialect"),
+            "{program}: {err}"
+        );
+    }
 }
 
 #[test]
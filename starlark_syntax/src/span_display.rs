@@ -35,7 +35,8 @@ pub fn span_display<'a>(
     color: bool,
 ) -> impl Display + 'a {
     fn convert_span_to_slice<'a>(span: FileSpanRef<'a>) -> Slice<'a> {
-        let region = span.resolve_span();
+        // Lines of the whole codemap, to look up line spans.
+        let region = span.file.resolve_span(span.span);
 
         // we want the source_span to capture any whitespace ahead of the diagnostic span to
         // get the column numbers correct in the DisplayList, and any trailing source code
@@ -53,8 +54,8 @@ pub fn span_display<'a>(
 
         Slice {
             source,
-            line_start: 1 + region.begin.line,
-            origin: Some(span.file.filename()),
+            line_start: 1 + span.resolve_span().begin.line,
+            origin: Some(span.filename()),
             fold: false,
             annotations: vec![SourceAnnotation {
                 label: "",
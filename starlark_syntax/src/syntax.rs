@@ -18,14 +18,17 @@
 //! The AST of Starlark as [`AstModule`], along with a [`parse`](AstModule::parse) function.
 
 pub use module::AstModule;
+pub use module::TextEdit;
 pub use parser::AstLoad;
 
 pub use crate::dialect::Dialect;
 pub use crate::dialect::DialectTypes;
+pub use crate::lexer::lex;
 
 pub mod ast;
 pub mod call;
 pub mod def;
+mod desugar;
 #[cfg(test)]
 mod grammar_tests;
 pub mod grammar_util;
@@ -34,6 +37,7 @@ pub mod module;
 pub mod parser;
 pub mod payload_map;
 pub(crate) mod state;
+pub mod symbols;
 #[cfg(test)]
 mod testcases;
 pub mod top_level_stmts;
@@ -182,6 +182,8 @@ pub enum ExprP<P: AstPayload> {
     Call(Box<AstExprP<P>>, CallArgsP<P>),
     Index(Box<(AstExprP<P>, AstExprP<P>)>),
     Index2(Box<(AstExprP<P>, AstExprP<P>, AstExprP<P>)>),
+    /// `x[a, b, c, ...]` with three or more indices, only allowed in type expressions.
+    IndexN(Box<(AstExprP<P>, Vec<AstExprP<P>>)>),
     Slice(
         Box<AstExprP<P>>,
         Option<Box<AstExprP<P>>>,
@@ -199,6 +201,8 @@ pub enum ExprP<P: AstPayload> {
     If(Box<(AstExprP<P>, AstExprP<P>, AstExprP<P>)>), // Order: condition, v1, v2 <=> v1 if condition else v2
     List(Vec<AstExprP<P>>),
     Dict(Vec<(AstExprP<P>, AstExprP<P>)>),
+    /// Set literal, never empty (`{}` is a dict).
+    Set(Vec<AstExprP<P>>),
     ListComprehension(Box<AstExprP<P>>, Box<ForClauseP<P>>, Vec<ClauseP<P>>),
     DictComprehension(
         Box<(AstExprP<P>, AstExprP<P>)>,
@@ -372,6 +376,15 @@ pub struct ForP<P: AstPayload> {
     pub body: Box<AstStmtP<P>>,
 }
 
+#[derive(Debug, Clone)]
+pub struct WithP<P: AstPayload> {
+    /// The context manager.
+    pub expr: AstExprP<P>,
+    /// Target for the result of `__enter__`, if there is `as`.
+    pub target: Option<AstAssignTargetP<P>>,
+    pub body: Box<AstStmtP<P>>,
+}
+
 #[derive(Debug, Clone)]
 pub struct FStringP<P: AstPayload> {
     /// A format string containing a `{}` marker for each expression to interpolate.
@@ -393,6 +406,7 @@ pub enum StmtP<P: AstPayload> {
     If(AstExprP<P>, Box<AstStmtP<P>>),
     IfElse(AstExprP<P>, Box<(AstStmtP<P>, AstStmtP<P>)>),
     For(ForP<P>),
+    With(WithP<P>),
     Def(DefP<P>),
     Load(LoadP<P>),
 }
@@ -510,7 +524,20 @@ impl Display for AstLiteral {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             AstLiteral::Int(i) => write!(f, "{}", &i.node),
-            AstLiteral::Float(n) => write!(f, "{}", &n.node),
+            AstLiteral::Float(n) if n.node.is_nan() => f.write_str("float(\"nan\")"),
+            // Overflowing literals parse as infinity.
+            AstLiteral::Float(n) if n.node.is_infinite() => {
+                f.write_str(if n.node > 0.0 { "1e999" } else { "(-1e999)" })
+            }
+            AstLiteral::Float(n) => {
+                let s = n.node.to_string();
+                f.write_str(&s)?;
+                // Make sure the literal is still parsed as a float, e.g. `1.0` rather than `1`.
+                if !s.contains(['.', 'e', 'E']) {
+                    f.write_str(".0")?;
+                }
+                Ok(())
+            }
             AstLiteral::String(s) => fmt_string_literal(f, &s.node),
             AstLiteral::Ellipsis => f.write_str("..."),
         }
@@ -555,8 +582,14 @@ impl Display for Expr {
                 let (a, i0, i1) = &**a_i0_i1;
                 write!(f, "{}[{}, {}]", a.node, i0.node, i1.node)
             }
+            Expr::IndexN(a_is) => {
+                let (a, is) = &**a_is;
+                write!(f, "{}[", a.node)?;
+                comma_separated_fmt(f, is, |x, f| write!(f, "{}", x.node), false)?;
+                f.write_str("]")
+            }
             Expr::Slice(e, i1, i2, i3) => {
-                write!(f, "{}[]", e.node)?;
+                write!(f, "{}[", e.node)?;
                 if let Some(x) = i1 {
                     write!(f, "{}:", x.node)?
                 } else {
@@ -568,7 +601,7 @@ impl Display for Expr {
                 if let Some(x) = i3 {
                     write!(f, ":{}", x.node)?
                 }
-                Ok(())
+                f.write_str("]")
             }
             Expr::Identifier(s) => Display::fmt(&s.node, f),
             Expr::Not(e) => write!(f, "(not {})", e.node),
@@ -590,6 +623,11 @@ impl Display for Expr {
                 comma_separated_fmt(f, v, |x, f| write!(f, "{}: {}", x.0.node, x.1.node), false)?;
                 f.write_str("}")
             }
+            Expr::Set(v) => {
+                f.write_str("{")?;
+                comma_separated_fmt(f, v, |x, f| write!(f, "{}", x.node), false)?;
+                f.write_str("}")
+            }
             Expr::ListComprehension(e, for_, c) => {
                 write!(f, "[{}", e.node)?;
                 write!(f, "{}", for_)?;
@@ -610,7 +648,8 @@ impl Display for Expr {
             Expr::Literal(x) => write!(f, "{}", x),
             Expr::FString(x) => {
                 // Write out the desugared form.
-                write!(f, "{}.format(", x.format.node)?;
+                fmt_string_literal(f, &x.format.node)?;
+                f.write_str(".format(")?;
                 comma_separated_fmt(f, &x.expressions, |x, f| write!(f, "{}", x.node), false)?;
                 f.write_str(")")
             }
@@ -738,6 +777,14 @@ impl Stmt {
                 writeln!(f, "{}for {} in {}:", tab, var.node, over.node)?;
                 body.node.fmt_with_tab(f, tab + "  ")
             }
+            Stmt::With(WithP { expr, target, body }) => {
+                write!(f, "{}with {}", tab, expr.node)?;
+                if let Some(target) = target {
+                    write!(f, " as {}", target.node)?;
+                }
+                writeln!(f, ":")?;
+                body.node.fmt_with_tab(f, tab + "  ")
+            }
             Stmt::Def(DefP {
                 name,
                 params,
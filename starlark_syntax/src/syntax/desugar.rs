@@ -0,0 +1,216 @@
+/*
+ * Copyright 2019 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Rewrite comprehensions into loops.
+//!
+//! A comprehension like `[f(x) for x in xs if p(x)]` becomes
+//!
+//! ```python
+//! def __comprehension_0(__comprehension_iter):
+//!     __comprehension_result = []
+//!     for x in __comprehension_iter:
+//!         if p(x):
+//!             __comprehension_result.append(f(x))
+//!     return __comprehension_result
+//! ```
+//!
+//! defined just before the statement containing the comprehension,
+//! which is replaced with `__comprehension_0(xs)`.
+//! As in the original comprehension, the first iterable is evaluated in the enclosing scope,
+//! and the loop variables are local to the generated function.
+
+use std::mem;
+
+use crate::codemap::Span;
+use crate::codemap::Spanned;
+use crate::syntax::ast::ArgumentP;
+use crate::syntax::ast::AssignIdentP;
+use crate::syntax::ast::AssignP;
+use crate::syntax::ast::AssignTargetP;
+use crate::syntax::ast::AstAssignIdent;
+use crate::syntax::ast::AstExpr;
+use crate::syntax::ast::AstStmt;
+use crate::syntax::ast::CallArgsP;
+use crate::syntax::ast::ClauseP;
+use crate::syntax::ast::DefP;
+use crate::syntax::ast::Expr;
+use crate::syntax::ast::ForClauseP;
+use crate::syntax::ast::ForP;
+use crate::syntax::ast::IdentP;
+use crate::syntax::ast::ParameterP;
+use crate::syntax::ast::Stmt;
+use crate::syntax::ast::StmtP;
+use crate::syntax::uniplate::VisitMut;
+
+const ITER_PARAM: &str = "__comprehension_iter";
+const RESULT_VAR: &str = "__comprehension_result";
+
+pub(crate) fn desugar_comprehensions(stmt: &mut AstStmt) {
+    let mut counter = 0;
+    desugar_stmt(stmt, &mut counter);
+}
+
+fn desugar_stmt(stmt: &mut AstStmt, counter: &mut usize) {
+    let mut defs = Vec::new();
+    stmt.visit_children_mut(|x| match x {
+        VisitMut::Stmt(x) => desugar_stmt(x, counter),
+        VisitMut::Expr(x) => desugar_expr(x, counter, &mut defs),
+    });
+    if !defs.is_empty() {
+        let original = mem::replace(&mut stmt.node, StmtP::Pass);
+        defs.push(spanned(stmt.span, original));
+        stmt.node = StmtP::Statements(defs);
+    }
+}
+
+fn desugar_expr(expr: &mut AstExpr, counter: &mut usize, defs: &mut Vec<AstStmt>) {
+    match &expr.node {
+        // Lambda body is an expression, there is no place to define a function in it.
+        Expr::Lambda(_) => {}
+        Expr::ListComprehension(..) | Expr::DictComprehension(..) => {
+            let node = mem::replace(&mut expr.node, Expr::Tuple(Vec::new()));
+            let (mut def, call) = comprehension_to_def(expr.span, node, counter);
+            // Comprehensions nested in the element or in the clauses.
+            desugar_stmt(&mut def, counter);
+            defs.push(def);
+            expr.node = call;
+            // Comprehensions nested in the first iterable, which is now the call argument.
+            expr.visit_expr_mut(|x| desugar_expr(x, counter, defs));
+        }
+        _ => expr.visit_expr_mut(|x| desugar_expr(x, counter, defs)),
+    }
+}
+
+fn spanned<T>(span: Span, node: T) -> Spanned<T> {
+    Spanned { span, node }
+}
+
+fn ident(span: Span, name: &str) -> AstExpr {
+    spanned(
+        span,
+        Expr::Identifier(spanned(
+            span,
+            IdentP {
+                ident: name.to_owned(),
+                payload: (),
+            },
+        )),
+    )
+}
+
+fn assign_ident(span: Span, name: &str) -> AstAssignIdent {
+    spanned(
+        span,
+        AssignIdentP {
+            ident: name.to_owned(),
+            payload: (),
+        },
+    )
+}
+
+/// Return the function definition and the call expression replacing the comprehension.
+fn comprehension_to_def(span: Span, comprehension: Expr, counter: &mut usize) -> (AstStmt, Expr) {
+    let (init, store, first, clauses) = match comprehension {
+        Expr::ListComprehension(x, first, clauses) => {
+            let append = spanned(
+                span,
+                Expr::Dot(
+                    Box::new(ident(span, RESULT_VAR)),
+                    spanned(span, "append".to_owned()),
+                ),
+            );
+            let store = Stmt::Expression(spanned(
+                span,
+                Expr::Call(
+                    Box::new(append),
+                    CallArgsP {
+                        args: vec![spanned(x.span, ArgumentP::Positional(*x))],
+                    },
+                ),
+            ));
+            (Expr::List(Vec::new()), store, first, clauses)
+        }
+        Expr::DictComprehension(k_v, first, clauses) => {
+            let (k, v) = *k_v;
+            let store = Stmt::Assign(AssignP {
+                lhs: spanned(
+                    span,
+                    AssignTargetP::Index(Box::new((ident(span, RESULT_VAR), k))),
+                ),
+                ty: None,
+                rhs: v,
+            });
+            (Expr::Dict(Vec::new()), store, first, clauses)
+        }
+        _ => unreachable!("not a comprehension"),
+    };
+
+    let mut body = spanned(span, store);
+    for clause in clauses.into_iter().rev() {
+        let stmt = match clause {
+            ClauseP::For(ForClauseP { var, over }) => Stmt::For(ForP {
+                var,
+                over,
+                body: Box::new(body),
+            }),
+            ClauseP::If(cond) => Stmt::If(cond, Box::new(body)),
+        };
+        body = spanned(span, stmt);
+    }
+    let ForClauseP { var, over } = *first;
+    let for_stmt = Stmt::For(ForP {
+        var,
+        over: ident(span, ITER_PARAM),
+        body: Box::new(body),
+    });
+
+    let name = format!("__comprehension_{}", counter);
+    *counter += 1;
+
+    let def_body = Stmt::Statements(vec![
+        spanned(
+            span,
+            Stmt::Assign(AssignP {
+                lhs: spanned(
+                    span,
+                    AssignTargetP::Identifier(assign_ident(span, RESULT_VAR)),
+                ),
+                ty: None,
+                rhs: spanned(span, init),
+            }),
+        ),
+        spanned(span, for_stmt),
+        spanned(span, Stmt::Return(Some(ident(span, RESULT_VAR)))),
+    ]);
+    let def = Stmt::Def(DefP {
+        name: assign_ident(span, &name),
+        params: vec![spanned(
+            span,
+            ParameterP::Normal(assign_ident(span, ITER_PARAM), None, None),
+        )],
+        return_type: None,
+        body: Box::new(spanned(span, def_body)),
+        payload: (),
+    });
+    let call = Expr::Call(
+        Box::new(ident(span, &name)),
+        CallArgsP {
+            args: vec![spanned(over.span, ArgumentP::Positional(over))],
+        },
+    );
+    (spanned(span, def), call)
+}
@@ -170,6 +170,44 @@ fn test_top_level_statements() {
     assert_eq!(parse("pass"), "pass\n");
 }
 
+#[test]
+fn test_fstring() {
+    // f-strings are desugared to `.format` calls at parse time.
+    assert_eq!(parse("f'a{x}b'"), "\"a{}b\".format(x)\n");
+    assert_eq!(parse("f'{x!r}{y!s}'"), "\"{!r}{}\".format(x, y)\n");
+    // Escaped braces stay escaped in the format string.
+    assert_eq!(parse("f'{{}}{x}{{x}}'"), "\"{{}}{}{{x}}\".format(x)\n");
+    // Any expression can be interpolated.
+    assert_eq!(parse("f'a{1+2}b'"), "\"a{}b\".format((1 + 2))\n");
+    assert_eq!(parse("f'{x.y[0]!r}'"), "\"{!r}\".format(x.y[0])\n");
+    parse_fail("fstring_invalid_expression", "f'a{x = 1}b'");
+    parse_fail_with_dialect("fstring_disabled", &Dialect::Standard, "f'{x}'");
+}
+
+#[test]
+fn test_set_literal() {
+    assert_eq!(parse("{1, 2}"), "{1, 2}\n");
+    assert_eq!(parse("{1,}"), "{1}\n");
+    assert_eq!(parse("{}"), "{}\n");
+    assert_eq!(parse("{1: 2}"), "{1: 2}\n");
+    parse_fail_with_dialect("set_literal_disabled", &Dialect::Standard, "{1, 2}");
+}
+
+#[test]
+fn test_with() {
+    assert_eq!(parse("with f():\n  pass\n"), "with f():\n  pass\n");
+    assert_eq!(
+        parse("with f() as x:\n  g(x)\n"),
+        "with f() as x:\n  g(x)\n"
+    );
+    assert_eq!(
+        parse("with f() as (x, y): g(x)\n"),
+        "with f() as (x, y):\n  g(x)\n"
+    );
+    parse_fail_with_dialect("with_disabled", &Dialect::Standard, "with f():\n  pass\n");
+    parse_fail_with_dialect("as_disabled", &Dialect::Standard, "as = 1\n");
+}
+
 #[test]
 fn test_top_level_def_with_docstring() {
     assert_eq!(
@@ -369,8 +407,11 @@ fn test_assignment_type_annotation() {
 #[test]
 fn test_test_list_in_index_expr() {
     assert_eq!(parse("x[1, 2]"), "x[1, 2]\n");
+    parse("def f(x: tuple[int, str, bool]) -> list[tuple[int, str, bool]]: pass");
+    parse("x: tuple[int, str, bool] = y");
 
     parse_fail("list_in_index_expr", "x[1, 2] = 3");
+    parse_fails("index_n_outside_type", &["x[1, 2, 3]", "x[1, 2, 3] = 4"]);
 }
 
 pub fn parse(program: &str) -> String {
@@ -17,6 +17,8 @@
 
 //! Code called by the parser to handle complex cases not handled by the grammar.
 
+use dupe::Dupe;
+
 use crate::codemap::CodeMap;
 use crate::codemap::Pos;
 use crate::codemap::Span;
@@ -25,7 +27,8 @@ use crate::dot_format_parser::FormatConv;
 use crate::dot_format_parser::FormatParser;
 use crate::dot_format_parser::FormatToken;
 use crate::eval_exception::EvalException;
-use crate::lexer::lex_exactly_one_identifier;
+use crate::lexer::Lexer;
+use crate::lexer::Token;
 use crate::lexer::TokenFString;
 use crate::slice_vec_ext::VecExt;
 use crate::syntax::ast::AssignIdentP;
@@ -42,9 +45,7 @@ use crate::syntax::ast::AstString;
 use crate::syntax::ast::AstTypeExpr;
 use crate::syntax::ast::Comma;
 use crate::syntax::ast::Expr;
-use crate::syntax::ast::ExprP;
 use crate::syntax::ast::FStringP;
-use crate::syntax::ast::IdentP;
 use crate::syntax::ast::LoadArgP;
 use crate::syntax::ast::LoadP;
 use crate::syntax::ast::Stmt;
@@ -52,6 +53,7 @@ use crate::syntax::ast::StmtP;
 use crate::syntax::ast::ToAst;
 use crate::syntax::ast::TypeExpr;
 use crate::syntax::ast::TypeExprP;
+use crate::syntax::grammar::StarlarkParser;
 use crate::syntax::state::ParserState;
 use crate::syntax::type_expr::TypeExprUnpackP;
 use crate::syntax::DialectTypes;
@@ -223,12 +225,12 @@ pub(crate) fn fstring(
                 let capture_begin = begin + content_start_offset + pos;
                 let capture_end = capture_begin + capture.len();
 
-                let ident = match lex_exactly_one_identifier(capture) {
-                    Some(ident) => ident,
+                let expr = match fstring_expr(capture, capture_begin, parser_state) {
+                    Some(expr) => expr,
                     None => {
                         parser_state.error(
                             Span::new(Pos::new(capture_begin as _), Pos::new(capture_end as _)),
-                            format_args!("Not a valid identifier: `{capture}`"),
+                            format_args!("Not a valid expression: `{capture}`"),
                         );
                         // Might as well keep going here. This doesn't compromise the parsing of
                         // the rest of the format string.
@@ -236,10 +238,6 @@ pub(crate) fn fstring(
                     }
                 };
 
-                let expr = ExprP::Identifier(
-                    IdentP { ident, payload: () }.ast(capture_begin, capture_end),
-                )
-                .ast(capture_begin, capture_end);
                 expressions.push(expr);
                 // Positional format.
                 match conv {
@@ -267,6 +265,34 @@ pub(crate) fn fstring(
     .ast(begin, end)
 }
 
+/// Parse the expression interpolated in an f-string, which starts at `begin` in the file.
+fn fstring_expr(capture: &str, begin: usize, parser_state: &ParserState) -> Option<AstExpr> {
+    // Parenthesize the expression like Python does, so it can have surrounding whitespace
+    // and span multiple lines. The parentheses take the place of the braces.
+    let input = format!("({capture})");
+    let begin = begin - 1;
+    let lexer = Lexer::new(&input, parser_state.dialect, parser_state.codemap.dupe());
+    let mut errors = Vec::new();
+    let stmt = StarlarkParser::new()
+        .parse(
+            &mut ParserState {
+                codemap: parser_state.codemap,
+                dialect: parser_state.dialect,
+                errors: &mut errors,
+            },
+            // Shift the tokens, so the positions of the expression are its positions in the file.
+            lexer.filter_map(|token| match token {
+                Ok((_, Token::Comment(_), _)) => None,
+                token => Some(token.map(|(l, t, r)| (l + begin, t, r + begin))),
+            }),
+        )
+        .ok()?;
+    match stmt.node {
+        StmtP::Expression(expr) if errors.is_empty() => Some(expr),
+        _ => None,
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
 enum DialectError {
     #[error("type annotations are not allowed in this dialect")]
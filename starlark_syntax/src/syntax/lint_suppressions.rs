@@ -66,6 +66,30 @@ impl LintSuppressions {
             })
             == Some(true)
     }
+
+    /// Beginnings of the suppression comments before `pos` which affect code after it.
+    pub(crate) fn crossing(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.suppressions
+            .values()
+            .flatten()
+            .filter(move |x| x.token_span.begin() < pos && x.effective_span.end() > pos)
+            .map(|x| x.token_span.begin())
+    }
+
+    /// Drop the suppressions which affect code after `pos`.
+    pub(crate) fn retain_before(&mut self, pos: Pos) {
+        for xs in self.suppressions.values_mut() {
+            xs.retain(|x| x.token_span.end() <= pos && x.effective_span.end() <= pos);
+        }
+        self.suppressions.retain(|_, xs| !xs.is_empty());
+    }
+
+    /// Add the suppressions of `other`, e.g. found in a later part of the same module.
+    pub(crate) fn extend(&mut self, other: LintSuppressions) {
+        for (name, xs) in other.suppressions {
+            self.suppressions.entry(name).or_default().extend(xs);
+        }
+    }
 }
 
 /// State needed for parsing a block of comments
@@ -16,6 +16,7 @@
  */
 
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt::Write;
 use std::fs;
 use std::mem;
@@ -34,17 +35,25 @@ use crate::eval_exception::EvalException;
 use crate::lexer::Lexer;
 use crate::lexer::Token;
 use crate::syntax::ast::ArgumentP;
+use crate::syntax::ast::AstAssignIdent;
 use crate::syntax::ast::AstExpr;
 use crate::syntax::ast::AstStmt;
+use crate::syntax::ast::AstString;
 use crate::syntax::ast::CallArgsP;
+use crate::syntax::ast::DefP;
 use crate::syntax::ast::ExprP;
 use crate::syntax::ast::IdentP;
 use crate::syntax::ast::LoadArgP;
 use crate::syntax::ast::Stmt;
+use crate::syntax::desugar::desugar_comprehensions;
 use crate::syntax::grammar::StarlarkParser;
+use crate::syntax::grammar_util;
 use crate::syntax::lint_suppressions::LintSuppressions;
 use crate::syntax::lint_suppressions::LintSuppressionsBuilder;
 use crate::syntax::state::ParserState;
+use crate::syntax::symbols::Symbol;
+use crate::syntax::symbols::SymbolKind;
+use crate::syntax::top_level_stmts::top_level_stmts;
 use crate::syntax::validate::validate_module;
 use crate::syntax::AstLoad;
 use crate::syntax::Dialect;
@@ -106,6 +115,15 @@ fn parse_error_add_span(
     )
 }
 
+/// A change to the source of a module, see [`reparse`](AstModule::reparse).
+#[derive(Debug, Clone)]
+pub struct TextEdit {
+    /// The replaced text, as a span of the source before the edit.
+    pub span: Span,
+    /// The text to put in its place.
+    pub text: String,
+}
+
 /// A representation of a Starlark module abstract syntax tree.
 ///
 /// Created with either [`parse`](AstModule::parse) or [`parse_file`](AstModule::parse_file),
@@ -210,9 +228,73 @@ impl AstModule {
     /// assert_eq!(span.to_string(), "filename:2:11");
     /// ```
     pub fn parse(filename: &str, content: String, dialect: &Dialect) -> crate::Result<Self> {
-        let typecheck = content.contains("@starlark-rust: typecheck");
-        let codemap = CodeMap::new(filename.to_owned(), content);
-        let lexer = Lexer::new(codemap.source(), dialect, codemap.dupe());
+        Self::parse_impl(CodeMap::new(filename.to_owned(), content), dialect, None)
+    }
+
+    /// Parse several files as one Starlark module, as if they were concatenated in order.
+    /// Each file starts on a new line.
+    ///
+    /// Errors and spans are reported with the name of the file they occur in,
+    /// and with lines relative to that file. For example:
+    ///
+    /// ```
+    /// use starlark_syntax::syntax::AstModule;
+    /// use starlark_syntax::syntax::Dialect;
+    ///
+    /// let err: starlark_syntax::Error = AstModule::parse_merged(
+    ///     vec![
+    ///         ("a.star".to_owned(), "x = 1".to_owned()),
+    ///         ("b.star".to_owned(), "\n(unmatched".to_owned()),
+    ///     ],
+    ///     &Dialect::Standard,
+    /// )
+    /// .unwrap_err();
+    /// assert_eq!(err.span().unwrap().to_string(), "b.star:2:11");
+    /// ```
+    pub fn parse_merged(files: Vec<(String, String)>, dialect: &Dialect) -> crate::Result<Self> {
+        Self::parse_impl(CodeMap::new_merged(files), dialect, None)
+    }
+
+    /// Like [`parse`](AstModule::parse), but also return the comments found in the module,
+    /// in source order. Each comment is spanned over the whole comment, including the leading `#`,
+    /// while the text excludes the `#`.
+    ///
+    /// Comments are not part of the AST, so tools like formatters need to reattach them
+    /// using their spans.
+    pub fn parse_with_comments(
+        filename: &str,
+        content: String,
+        dialect: &Dialect,
+    ) -> crate::Result<(Self, Vec<Spanned<String>>)> {
+        let mut comments = Vec::new();
+        let module = Self::parse_impl(
+            CodeMap::new(filename.to_owned(), content),
+            dialect,
+            Some(&mut comments),
+        )?;
+        Ok((module, comments))
+    }
+
+    fn parse_impl(
+        codemap: CodeMap,
+        dialect: &Dialect,
+        comments: Option<&mut Vec<Spanned<String>>>,
+    ) -> crate::Result<Self> {
+        let typecheck = codemap.source().contains("@starlark-rust: typecheck");
+        let (statement, lint_suppressions) =
+            Self::parse_statement(&codemap, codemap.source(), dialect, comments)?;
+        AstModule::create(codemap, statement, dialect, typecheck, lint_suppressions)
+    }
+
+    /// Parse `input`, which must have the same length as the source of `codemap`,
+    /// and only differ from it by having some of the leading text blanked out.
+    fn parse_statement(
+        codemap: &CodeMap,
+        input: &str,
+        dialect: &Dialect,
+        mut comments: Option<&mut Vec<Spanned<String>>>,
+    ) -> crate::Result<(AstStmt, LintSuppressions)> {
+        let lexer = Lexer::new(input, dialect, codemap.dupe());
         // Store lint suppressions found during parsing
         let mut lint_suppressions_builder = LintSuppressionsBuilder::new();
         // Keep track of block of comments, used for accumulating lint suppressions
@@ -220,20 +302,26 @@ impl AstModule {
         let mut errors = Vec::new();
         match StarlarkParser::new().parse(
             &mut ParserState {
-                codemap: &codemap,
+                codemap,
                 dialect,
                 errors: &mut errors,
             },
             lexer.filter(|token| match token {
                 // Filter out comment tokens and accumulate lint suppressions
                 Ok((start, Token::Comment(comment), end)) => {
-                    lint_suppressions_builder.parse_comment(&codemap, comment, *start, *end);
+                    lint_suppressions_builder.parse_comment(codemap, comment, *start, *end);
+                    if let Some(comments) = &mut comments {
+                        comments.push(Spanned {
+                            span: Span::new(Pos::new(*start as u32), Pos::new(*end as u32)),
+                            node: comment.clone(),
+                        });
+                    }
                     in_comment_block = true;
                     false
                 }
                 _ => {
                     if in_comment_block {
-                        lint_suppressions_builder.end_of_comment_block(&codemap);
+                        lint_suppressions_builder.end_of_comment_block(codemap);
                         in_comment_block = false;
                     }
                     true
@@ -244,18 +332,98 @@ impl AstModule {
                 if let Some(err) = errors.into_iter().next() {
                     return Err(err.into_error());
                 }
-                Ok(AstModule::create(
-                    codemap,
-                    v,
-                    dialect,
-                    typecheck,
-                    lint_suppressions_builder.build(),
-                )?)
+                Ok((v, lint_suppressions_builder.build()))
             }
-            Err(p) => Err(parse_error_add_span(p, codemap.source().len(), &codemap)),
+            Err(p) => Err(parse_error_add_span(p, codemap.source().len(), codemap)),
         }
     }
 
+    /// Apply an edit to the source of the module, and parse the result.
+    ///
+    /// Only the affected part of the module is parsed again: top-level statements
+    /// which end before the line of the edit are moved into the returned module as they are,
+    /// while the statement containing the edit and all the statements after it are reparsed.
+    /// When the edit is not inside a statement, the statement before it is reparsed too,
+    /// because the edit can add indented lines to its body.
+    /// The result is the same as parsing the edited source from scratch, except that a module
+    /// created with [`parse_merged`](AstModule::parse_merged) becomes a single file.
+    ///
+    /// Panics if the span of the edit is not within the source of the module.
+    pub fn reparse(self, edit: TextEdit) -> crate::Result<AstModule> {
+        let old = self.codemap.source();
+        let source = [
+            &old[..edit.span.begin().get() as usize],
+            &edit.text,
+            &old[edit.span.end().get() as usize..],
+        ]
+        .concat();
+        let codemap = CodeMap::new(self.codemap.filename().to_owned(), source);
+
+        let mut stmts = match self.statement.node {
+            Stmt::Statements(stmts) => stmts,
+            _ => return Self::parse_impl(codemap, &self.dialect, None),
+        };
+
+        // The text before the edit is unchanged, so its positions are the same in both codemaps.
+        let line_start = |pos: Pos| self.codemap.line_span(self.codemap.find_line(pos)).begin();
+        let mut region_start = line_start(edit.span.begin());
+        let mut keep = stmts.partition_point(|x| x.span.end() <= region_start);
+        // An edit between statements, or at the start of the line of a statement,
+        // can add indented lines to the body of the statement before it.
+        if stmts
+            .get(keep)
+            .is_none_or(|x| edit.span.begin() <= line_start(x.span.begin()))
+        {
+            keep = keep.saturating_sub(1);
+        }
+        loop {
+            // Start from the beginning of the first reparsed statement,
+            // and of any suppression comment before it which applies to the reparsed code.
+            let mut start = region_start;
+            if let Some(x) = stmts.get(keep) {
+                start = start.min(line_start(x.span.begin()));
+            }
+            if let Some(pos) = self.lint_suppressions.crossing(start).min() {
+                start = start.min(line_start(pos));
+            }
+            let k = stmts.partition_point(|x| x.span.end() <= start);
+            if start == region_start && k == keep {
+                break;
+            }
+            region_start = start;
+            keep = k;
+        }
+        stmts.truncate(keep);
+        let mut lint_suppressions = self.lint_suppressions;
+        lint_suppressions.retain_before(region_start);
+
+        // Blank out the kept text with newlines, so positions and lines in the region are unchanged.
+        let region_start = region_start.get() as usize;
+        let input = "\n".repeat(region_start) + &codemap.source()[region_start..];
+        let (region, region_suppressions) =
+            Self::parse_statement(&codemap, &input, &self.dialect, None)?;
+        lint_suppressions.extend(region_suppressions);
+
+        let begin = match stmts.first() {
+            Some(x) => x.span.begin(),
+            None => region.span.begin(),
+        };
+        let end = region.span.end().max(begin);
+        match region.node {
+            Stmt::Statements(xs) => stmts.extend(xs),
+            _ => stmts.push(region),
+        }
+        let statement = grammar_util::statements(stmts, begin.get() as usize, end.get() as usize);
+        let typecheck = codemap.source().contains("@starlark-rust: typecheck");
+        AstModule::create(
+            codemap,
+            statement,
+            &self.dialect,
+            typecheck,
+            lint_suppressions,
+        )
+    }
+
     /// Return the file names of all the `load` statements in the module.
     /// If the [`Dialect`] had [`enable_load`](Dialect::enable_load) set to [`false`] this will be an empty list.
     pub fn loads(&self) -> Vec<AstLoad> {
@@ -291,6 +459,78 @@ impl AstModule {
         loads
     }
 
+    /// List the top-level bindings of the module: `def` statements, assignments and
+    /// symbols brought in by `load`, in the order in which they appear.
+    ///
+    /// Bindings nested under top-level `if` or `for` statements are included,
+    /// but nothing inside a `def` body is.
+    pub fn symbols(&self) -> Vec<Symbol> {
+        fn ident(x: &AstAssignIdent, kind: SymbolKind, codemap: &CodeMap, res: &mut Vec<Symbol>) {
+            res.push(Symbol {
+                name: x.ident.clone(),
+                kind,
+                span: codemap.file_span(x.span),
+            });
+        }
+
+        fn stmt(x: &AstStmt, codemap: &CodeMap, res: &mut Vec<Symbol>) {
+            match &**x {
+                Stmt::Assign(assign) => assign
+                    .lhs
+                    .visit_lvalue(|x| ident(x, SymbolKind::Variable, codemap, res)),
+                Stmt::Def(DefP { name, .. }) => ident(name, SymbolKind::Function, codemap, res),
+                Stmt::Load(load) => {
+                    for LoadArgP { local, .. } in &load.args {
+                        ident(local, SymbolKind::Load, codemap, res)
+                    }
+                }
+                // Visit statements, but don't descend under def - only top-level statements are interesting
+                _ => x.visit_stmt(|x| stmt(x, codemap, res)),
+            }
+        }
+
+        let mut res = Vec::new();
+        stmt(&self.statement, &self.codemap, &mut res);
+        res
+    }
+
+    /// Names which other modules can `load` from this one: the top-level assignments
+    /// and `def` statements whose names do not start with `_`, each listed once
+    /// at its first binding.
+    ///
+    /// Symbols brought in by `load` are not re-exported, so are not included.
+    pub fn exported_symbols(&self) -> Vec<AstString> {
+        let mut seen = HashSet::new();
+        let mut res = Vec::new();
+        let mut add = |x: &AstAssignIdent| {
+            if !x.ident.starts_with('_') && seen.insert(x.ident.clone()) {
+                res.push(Spanned {
+                    node: x.ident.clone(),
+                    span: x.span,
+                });
+            }
+        };
+        for x in top_level_stmts(&self.statement) {
+            match &**x {
+                Stmt::Assign(assign) => assign.lhs.visit_lvalue(&mut add),
+                Stmt::AssignModify(dest, _, _) => dest.visit_lvalue(&mut add),
+                Stmt::Def(def) => add(&def.name),
+                _ => {}
+            }
+        }
+        res
+    }
+
+    /// Render the module back to Starlark source code.
+    ///
+    /// The output uses canonical formatting (two-space indentation, spaces around operators,
+    /// explicit parentheses around binary operators) rather than the original layout,
+    /// and does not include comments. Parsing the output with the same [`Dialect`]
+    /// produces an equivalent module.
+    pub fn unparse(&self) -> String {
+        self.statement.to_string()
+    }
+
     /// Look up a [`Span`] contained in this module to a [`FileSpan`].
     pub fn file_span(&self, x: Span) -> FileSpan {
         self.codemap.file_span(x)
@@ -314,6 +554,29 @@ impl AstModule {
         res
     }
 
+    /// Mutably visit the statements directly contained in the module, i.e. the top-level statements.
+    /// Replacing a node keeps its original span unless the callback changes it.
+    ///
+    /// Use [`visit_stmt_mut`](crate::syntax::ast::StmtP::visit_stmt_mut) inside the callback
+    /// to descend further.
+    pub fn visit_stmt_mut(&mut self, mut f: impl FnMut(&mut AstStmt)) {
+        match &mut self.statement.node {
+            Stmt::Statements(_) => self.statement.visit_stmt_mut(f),
+            // A module with a single statement is not wrapped in `Statements`.
+            _ => f(&mut self.statement),
+        }
+    }
+
+    /// Mutably visit the outermost expressions of all statements in the module,
+    /// including statements nested inside `def`, `if` and `for`.
+    /// Replacing a node keeps its original span unless the callback changes it.
+    ///
+    /// Use [`visit_expr_mut`](crate::syntax::ast::ExprP::visit_expr_mut) inside the callback
+    /// to descend into subexpressions.
+    pub fn visit_expr_mut(&mut self, f: impl FnMut(&mut AstExpr)) {
+        self.statement.visit_expr_mut(f)
+    }
+
     /// Function to help people who want to write deeper AST transformations in Starlark.
     /// Likely to break type checking and LSP support to some extent.
     ///
@@ -357,7 +620,19 @@ impl AstModule {
             x.visit_expr_mut(|x| f(x, replace));
         }
 
-        self.statement.visit_expr_mut(|x| f(x, replace));
+        self.visit_expr_mut(|x| f(x, replace));
+    }
+
+    /// Rewrite list and dict comprehensions into equivalent `for` loops.
+    ///
+    /// Each comprehension becomes a call to a private function, defined just before
+    /// the statement containing the comprehension, which builds the result with nested loops.
+    /// The first iterable is evaluated in the enclosing scope and passed as an argument,
+    /// so comprehension variables stay local, as in the original code.
+    ///
+    /// Comprehensions inside `lambda` are left unchanged.
+    pub fn desugar_comprehensions(&mut self) {
+        desugar_comprehensions(&mut self.statement)
     }
 
     /// Check if a given Lint short_name and span is suppressed in this module
@@ -369,8 +644,32 @@ impl AstModule {
 
 #[cfg(test)]
 mod tests {
+    use crate::codemap::Pos;
+    use crate::codemap::Span;
     use crate::slice_vec_ext::SliceExt;
+    use crate::syntax::ast::AstExpr;
+    use crate::syntax::ast::AstStmt;
+    use crate::syntax::ast::ExprP;
+    use crate::syntax::ast::Stmt;
     use crate::syntax::grammar_tests;
+    use crate::syntax::AstModule;
+    use crate::syntax::Dialect;
+    use crate::syntax::TextEdit;
+
+    #[test]
+    fn test_parse_merged_error() {
+        let err = AstModule::parse_merged(
+            vec![
+                ("a.star".to_owned(), "x = 1\ny = 2".to_owned()),
+                ("b.star".to_owned(), "z = 3\nw = (".to_owned()),
+            ],
+            &Dialect::Standard,
+        )
+        .unwrap_err();
+        let message = format!("{:#}", err);
+        assert!(message.contains("b.star:2:"), "{}", message);
+        assert!(message.contains("2 | w = ("), "{}", message);
+    }
 
     #[test]
     fn test_locations() {
@@ -384,4 +683,206 @@ mod tests {
         assert_eq!(&get("foo"), "1:1-4");
         assert_eq!(&get("foo\ndef x():\n   pass"), "1:1-4 2:1-3:8 3:4-8");
     }
+
+    #[test]
+    fn test_visit_expr_mut() {
+        fn rename(x: &mut AstExpr) {
+            if let ExprP::Identifier(ident) = &mut x.node {
+                if ident.node.ident == "x" {
+                    ident.node.ident = "y".to_owned();
+                }
+            }
+            x.visit_expr_mut(rename);
+        }
+
+        let mut module =
+            grammar_tests::parse_ast("def f(x):\n    return x + g(x)\nz = [x for q in x]\n");
+        let span = match &module.statement.node {
+            Stmt::Statements(xs) => xs[1].span,
+            _ => unreachable!(),
+        };
+        module.visit_expr_mut(rename);
+        assert_eq!(
+            module.statement.to_string(),
+            "def f(x):\n  return (y + g(y))\nz = [y for q in y]\n"
+        );
+        // Spans are preserved.
+        match &module.statement.node {
+            Stmt::Statements(xs) => assert_eq!(xs[1].span, span),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_visit_stmt_mut() {
+        fn visit(code: &str) -> Vec<String> {
+            let mut module = grammar_tests::parse_ast(code);
+            let mut res = Vec::new();
+            module.visit_stmt_mut(|x| res.push(x.to_string()));
+            res
+        }
+
+        assert_eq!(visit("x = 1
+y = 2
+"), vec!["x = 1\n", "y = 2\n"]);
+        assert_eq!(visit("x = 1
+"), vec!["x = 1\n"]);
+        assert_eq!(visit("def f():
+    pass
+"), vec!["def f():\n  pass\n"]);
+    }
+
+    #[test]
+    fn test_unparse() {
+        let code = r#"
+load("a.bzl", "x", z = "y")
+def f(a, b = 1.0, *args, c: int = -3, **kwargs) -> str:
+    """Docs."""
+    if a and not b:
+        return "x\n"
+    elif a[1:] or a[::2]:
+        pass
+    else:
+        for i, j in b.items():
+            c += i * (j - 1) // 2
+            if c > 10:
+                break
+    return [k for k in a if k] + {k: v for (k, v) in x} + (lambda q: q)(1)
+y = (1,) + (x if x else {"a": [1, 2]})
+"#;
+        let unparsed = grammar_tests::parse_ast(code).unparse();
+        let reparsed = grammar_tests::parse_ast(&unparsed).unparse();
+        assert_eq!(unparsed, reparsed);
+        assert!(unparsed.contains("b = 1.0"), "{}", unparsed);
+        assert!(unparsed.contains("(a[1:] or a[::2])"), "{}", unparsed);
+        assert_eq!(
+            "x = [1e999, 1.0]",
+            grammar_tests::parse_ast("x = [1e999, 1.]").unparse().trim_end()
+        );
+    }
+
+    #[test]
+    fn test_symbols() {
+        let module =
+            grammar_tests::parse_ast("load('a.bzl', 'x')\ndef f():\n    inner = 1\ny = 2\n");
+        assert_eq!(
+            module
+                .symbols()
+                .map(|s| format!("{} {:?} {}", s.span.resolve_span(), s.kind, s.name)),
+            &["1:15-18 Load x", "2:5-6 Function f", "4:1-2 Variable y"]
+        );
+    }
+
+    #[test]
+    fn test_exported_symbols() {
+        let module = grammar_tests::parse_ast(
+            "load('a.bzl', 'loaded')\n_private = 1\ndef public():\n    inner = 1\npublic2 = _private\npublic2 = 3\n",
+        );
+        assert_eq!(
+            module
+                .exported_symbols()
+                .map(|s| format!("{} {}", module.file_span(s.span).resolve_span(), s.node)),
+            &["3:5-11 public", "5:1-8 public2"]
+        );
+    }
+
+    #[test]
+    fn test_parse_with_comments() {
+        let code = "# leading\nx = 1 # trailing\ndef f():\n    # inner\n    pass\n# last";
+        let (module, comments) =
+            AstModule::parse_with_comments("x.star", code.to_owned(), &Dialect::Standard).unwrap();
+        let comments = comments.map(|c| {
+            (
+                module.file_span(c.span).resolve_span().to_string(),
+                c.node.as_str(),
+            )
+        });
+        assert_eq!(
+            comments,
+            vec![
+                ("1:1-10".to_owned(), " leading"),
+                ("2:7-17".to_owned(), " trailing"),
+                ("4:5-12".to_owned(), " inner"),
+                ("6:1-7".to_owned(), " last"),
+            ]
+        );
+    }
+
+    /// Parse `code`, and return an edit replacing `old` by `new` in it,
+    /// with the result of parsing the edited code from scratch.
+    fn edit(code: &str, old: &str, new: &str) -> (AstModule, TextEdit, String) {
+        let module = AstModule::parse("x.star", code.to_owned(), &Dialect::Standard).unwrap();
+        let begin = code.find(old).unwrap() as u32;
+        let edit = TextEdit {
+            span: Span::new(Pos::new(begin), Pos::new(begin + old.len() as u32)),
+            text: new.to_owned(),
+        };
+        (module, edit, code.replacen(old, new, 1))
+    }
+
+    #[test]
+    fn test_reparse() {
+        fn def_body(module: &AstModule, i: usize) -> *const AstStmt {
+            match &module.statement.node {
+                Stmt::Statements(xs) => match &xs[i].node {
+                    Stmt::Def(def) => &*def.body,
+                    _ => unreachable!(),
+                },
+                _ => unreachable!(),
+            }
+        }
+
+        let (module, edit, code) = edit(
+            "def f():\n    return 1\n\ndef g():\n    return 2\n",
+            "2",
+            "[x + 3,\n  4]",
+        );
+        let body = def_body(&module, 0);
+        let module = module.reparse(edit).unwrap();
+        // The untouched function is reused.
+        assert_eq!(def_body(&module, 0), body);
+        let expected = grammar_tests::parse_ast(&code);
+        assert_eq!(module.unparse(), expected.unparse());
+        assert_eq!(
+            module.stmt_locations().map(|x| x.resolve_span()),
+            expected.stmt_locations().map(|x| x.resolve_span())
+        );
+    }
+
+    #[test]
+    fn test_reparse_extends_previous_statement() {
+        let (module, edit, code) = edit(
+            "def f():\n    return 1\n\ndef g():\n    return 2\n",
+            "\ndef g",
+            "    y = 1\n\ndef g",
+        );
+        let module = module.reparse(edit).unwrap();
+        let expected = grammar_tests::parse_ast(&code);
+        assert_eq!(module.unparse(), expected.unparse());
+        assert_eq!(
+            module.stmt_locations().map(|x| x.resolve_span()),
+            expected.stmt_locations().map(|x| x.resolve_span())
+        );
+    }
+
+    #[test]
+    fn test_reparse_keeps_suppressions() {
+        let (module, edit, code) = edit(
+            "x = 1\n# starlark-lint-disable foo\ndef g():\n    return 2\n",
+            "2",
+            "3",
+        );
+        let module = module.reparse(edit).unwrap();
+        let begin = code.find("def").unwrap() as u32;
+        let def = Span::new(Pos::new(begin), Pos::new(begin + 8));
+        assert!(module.is_suppressed("foo", def));
+        assert!(!module.is_suppressed("foo", Span::new(Pos::new(0), Pos::new(5))));
+    }
+
+    #[test]
+    fn test_reparse_error() {
+        let (module, edit, _) = edit("x = 1\ny = 2\n", "2", "(");
+        let err = module.reparse(edit).unwrap_err();
+        assert_eq!(err.span().unwrap().resolve_span().to_string(), "3:1");
+    }
 }
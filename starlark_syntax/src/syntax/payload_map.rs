@@ -38,6 +38,7 @@ use crate::syntax::ast::LoadP;
 use crate::syntax::ast::ParameterP;
 use crate::syntax::ast::StmtP;
 use crate::syntax::ast::TypeExprP;
+use crate::syntax::ast::WithP;
 
 pub trait AstPayloadFunction<A: AstPayload, B: AstPayload> {
     fn map_load(&mut self, import_path: &str, a: A::LoadPayload) -> B::LoadPayload;
@@ -109,6 +110,20 @@ impl<A: AstPayload> ForP<A> {
     }
 }
 
+impl<A: AstPayload> WithP<A> {
+    pub fn into_map_payload<B: AstPayload>(
+        self,
+        f: &mut impl AstPayloadFunction<A, B>,
+    ) -> WithP<B> {
+        let WithP { expr, target, body } = self;
+        WithP {
+            expr: expr.into_map_payload(f),
+            target: target.map(|target| target.into_map_payload(f)),
+            body: Box::new(body.into_map_payload(f)),
+        }
+    }
+}
+
 impl<A: AstPayload> StmtP<A> {
     pub fn into_map_payload<B: AstPayload>(
         self,
@@ -145,6 +160,7 @@ impl<A: AstPayload> StmtP<A> {
                 )
             }
             StmtP::For(fr) => StmtP::For(fr.into_map_payload(f)),
+            StmtP::With(w) => StmtP::With(w.into_map_payload(f)),
             StmtP::Def(DefP {
                 name,
                 params,
@@ -192,6 +208,13 @@ impl<A: AstPayload> ExprP<A> {
                     i1.into_map_payload(f),
                 )))
             }
+            ExprP::IndexN(a_is) => {
+                let (array, is) = *a_is;
+                ExprP::IndexN(Box::new((
+                    array.into_map_payload(f),
+                    is.into_map(|i| i.into_map_payload(f)),
+                )))
+            }
             ExprP::Slice(x, a, b, c) => ExprP::Slice(
                 Box::new(x.into_map_payload(f)),
                 a.map(|e| Box::new(e.into_map_payload(f))),
@@ -227,6 +250,7 @@ impl<A: AstPayload> ExprP<A> {
                 )))
             }
             ExprP::List(es) => ExprP::List(es.into_map(|e| e.into_map_payload(f))),
+            ExprP::Set(es) => ExprP::Set(es.into_map(|e| e.into_map_payload(f))),
             ExprP::Dict(kvs) => {
                 ExprP::Dict(kvs.into_map(|(k, v)| (k.into_map_payload(f), v.into_map_payload(f))))
             }
@@ -0,0 +1,42 @@
+/*
+ * Copyright 2019 The Starlark in Rust Authors.
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use dupe::Dupe;
+
+use crate::codemap::FileSpan;
+
+/// What kind of binding introduced a [`Symbol`].
+#[derive(Debug, Clone, Copy, Dupe, Eq, PartialEq, Hash)]
+pub enum SymbolKind {
+    /// A `def` statement.
+    Function,
+    /// An assignment, e.g. `x = 1` or `x, y = 1, 2`.
+    Variable,
+    /// A symbol brought into scope with `load`.
+    Load,
+}
+
+/// A top-level binding in a module. Returned from [`AstModule::symbols`](crate::syntax::AstModule::symbols).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Symbol {
+    /// The name of the symbol.
+    pub name: String,
+    /// What kind of binding it is.
+    pub kind: SymbolKind,
+    /// The location of the identifier being bound.
+    pub span: FileSpan,
+}
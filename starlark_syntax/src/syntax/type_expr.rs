@@ -64,16 +64,26 @@ pub struct TypePathP<'a, P: AstPayload> {
 pub enum TypeExprUnpackP<'a, P: AstPayload> {
     Ellipsis,
     Path(TypePathP<'a, P>),
-    /// `list[str]`.
-    Index(&'a AstIdentP<P>, Box<Spanned<TypeExprUnpackP<'a, P>>>),
+    /// `list[str]` or `typing.Literal["a"]`.
+    Index(
+        Spanned<TypePathP<'a, P>>,
+        Box<Spanned<TypeExprUnpackP<'a, P>>>,
+    ),
     /// `dict[str, int]` or `typing.Callable[[int], str]`.
     Index2(
         Spanned<TypePathP<'a, P>>,
         Box<Spanned<TypeExprUnpackP<'a, P>>>,
         Box<Spanned<TypeExprUnpackP<'a, P>>>,
     ),
+    /// `tuple[int, str, bool]` or `typing.Literal["a", "b", "c"]`.
+    IndexN(
+        Spanned<TypePathP<'a, P>>,
+        Vec<Spanned<TypeExprUnpackP<'a, P>>>,
+    ),
     /// List argument in `typing.Callable[[int], str]`.
     List(Vec<Spanned<TypeExprUnpackP<'a, P>>>),
+    /// String argument in `typing.Literal["a", "b"]`.
+    Literal(&'a str),
     Union(Vec<Spanned<TypeExprUnpackP<'a, P>>>),
     Tuple(Vec<Spanned<TypeExprUnpackP<'a, P>>>),
 }
@@ -154,6 +164,10 @@ impl<'a, P: AstPayload> TypeExprUnpackP<'a, P> {
                     node: TypeExprUnpackP::List(items),
                 })
             }
+            ExprP::Literal(AstLiteral::String(s)) => Ok(Spanned {
+                span,
+                node: TypeExprUnpackP::Literal(&s.node),
+            }),
             _ => TypeExprUnpackP::unpack(expr, codemap),
         }
     }
@@ -189,16 +203,18 @@ impl<'a, P: AstPayload> TypeExprUnpackP<'a, P> {
             ExprP::Call(..) => err("call"),
             ExprP::Index(a_i) => {
                 let (a, i) = &**a_i;
-                match &a.node {
-                    ExprP::Identifier(ident) => {
-                        let i = TypeExprUnpackP::unpack(i, codemap)?;
-                        Ok(Spanned {
-                            span,
-                            node: TypeExprUnpackP::Index(ident, Box::new(i)),
-                        })
+                let path = Self::unpack_path(a, codemap)?;
+                let i = match &i.node {
+                    // `typing.Literal["a"]`.
+                    ExprP::Literal(AstLiteral::String(_)) => {
+                        TypeExprUnpackP::unpack_argument(i, codemap)?
                     }
-                    _ => err("array indirection where array is not an identifier"),
-                }
+                    _ => TypeExprUnpackP::unpack(i, codemap)?,
+                };
+                Ok(Spanned {
+                    span,
+                    node: TypeExprUnpackP::Index(path, Box::new(i)),
+                })
             }
             ExprP::Index2(a_i0_i1) => {
                 let (a, i0, i1) = &**a_i0_i1;
@@ -210,6 +226,18 @@ impl<'a, P: AstPayload> TypeExprUnpackP<'a, P> {
                     node: TypeExprUnpackP::Index2(path, Box::new(i0), Box::new(i1)),
                 })
             }
+            ExprP::IndexN(a_is) => {
+                let (a, is) = &**a_is;
+                let path = Self::unpack_path(a, codemap)?;
+                let is = is
+                    .iter()
+                    .map(|i| TypeExprUnpackP::unpack_argument(i, codemap))
+                    .collect::<Result<_, _>>()?;
+                Ok(Spanned {
+                    span,
+                    node: TypeExprUnpackP::IndexN(path, is),
+                })
+            }
             ExprP::Slice(..) => err("slice"),
             ExprP::Identifier(..) => {
                 let path = Self::unpack_path(expr, codemap)?;
@@ -262,6 +290,7 @@ impl<'a, P: AstPayload> TypeExprUnpackP<'a, P> {
                 }
             }
             ExprP::Dict(..) => err("dict"),
+            ExprP::Set(..) => err("set"),
             ExprP::ListComprehension(..) => err("list comprehension"),
             ExprP::DictComprehension(..) => err("dict comprehension"),
             ExprP::FString(..) => err("f-string"),
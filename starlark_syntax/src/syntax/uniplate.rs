@@ -38,6 +38,7 @@ use crate::syntax::ast::LambdaP;
 use crate::syntax::ast::ParameterP;
 use crate::syntax::ast::StmtP;
 use crate::syntax::ast::TypeExprP;
+use crate::syntax::ast::WithP;
 
 pub enum Visit<'a, P: AstPayload> {
     Stmt(&'a AstStmtP<P>),
@@ -120,6 +121,13 @@ impl<P: AstPayload> StmtP<P> {
                 f(Visit::Expr(over));
                 f(Visit::Stmt(body));
             }
+            StmtP::With(WithP { expr, target, body }) => {
+                f(Visit::Expr(expr));
+                target
+                    .iter()
+                    .for_each(|x| x.visit_expr(|x| f(Visit::Expr(x))));
+                f(Visit::Stmt(body));
+            }
             // Nothing else contains nested statements
             StmtP::Break => {}
             StmtP::Continue => {}
@@ -174,6 +182,13 @@ impl<P: AstPayload> StmtP<P> {
                 f(VisitMut::Expr(over));
                 f(VisitMut::Stmt(body));
             }
+            StmtP::With(WithP { expr, target, body }) => {
+                f(VisitMut::Expr(expr));
+                target
+                    .iter_mut()
+                    .for_each(|x| x.visit_expr_mut(|x| f(VisitMut::Expr(x))));
+                f(VisitMut::Stmt(body));
+            }
             // Nothing else contains nested statements
             StmtP::Break => {}
             StmtP::Continue => {}
@@ -287,6 +302,32 @@ impl<P: AstPayload> StmtP<P> {
         result
     }
 
+    /// Visit all type expressions in this statement and its children.
+    pub fn visit_type_expr<'a>(&'a self, f: &mut impl FnMut(&'a AstTypeExprP<P>)) {
+        match self {
+            StmtP::Def(def) => {
+                for param in &def.params {
+                    if let (_, Some(ty), _) = param.split() {
+                        f(ty);
+                    }
+                }
+                if let Some(ty) = &def.return_type {
+                    f(ty);
+                }
+            }
+            StmtP::Assign(assign) => {
+                if let Some(ty) = &assign.ty {
+                    f(ty);
+                }
+            }
+            _ => {}
+        }
+        self.visit_children(|visit| match visit {
+            Visit::Stmt(stmt) => stmt.visit_type_expr(f),
+            Visit::Expr(expr) => expr.visit_type_expr(f),
+        })
+    }
+
     /// Visit all type expressions in this statement and its children.
     pub fn visit_type_expr_err_mut<E>(
         &mut self,
@@ -399,6 +440,11 @@ impl<P: AstPayload> ExprP<P> {
                 f(i0);
                 f(i1);
             }
+            ExprP::IndexN(a_is) => {
+                let (a, is) = &**a_is;
+                f(a);
+                is.iter().for_each(|x| f(x));
+            }
             ExprP::Slice(a, b, c, d) => {
                 f(a);
                 b.iter().for_each(|x| f(x));
@@ -429,7 +475,7 @@ impl<P: AstPayload> ExprP<P> {
                 f(b);
                 f(c);
             }
-            ExprP::List(x) => x.iter().for_each(|x| f(x)),
+            ExprP::List(x) | ExprP::Set(x) => x.iter().for_each(|x| f(x)),
             ExprP::Dict(x) => x.iter().for_each(|(x, y)| {
                 f(x);
                 f(y);
@@ -499,6 +545,11 @@ impl<P: AstPayload> ExprP<P> {
                 f(i0);
                 f(i1);
             }
+            ExprP::IndexN(a_is) => {
+                let (a, is) = &mut **a_is;
+                f(a);
+                is.iter_mut().for_each(|x| f(x));
+            }
             ExprP::Slice(a, b, c, d) => {
                 f(a);
                 b.iter_mut().for_each(|x| f(x));
@@ -529,7 +580,7 @@ impl<P: AstPayload> ExprP<P> {
                 f(b);
                 f(c);
             }
-            ExprP::List(x) => x.iter_mut().for_each(|x| f(x)),
+            ExprP::List(x) | ExprP::Set(x) => x.iter_mut().for_each(|x| f(x)),
             ExprP::Dict(x) => x.iter_mut().for_each(|(x, y)| {
                 f(x);
                 f(y);
@@ -553,6 +604,17 @@ impl<P: AstPayload> ExprP<P> {
         }
     }
 
+    fn visit_type_expr<'a>(&'a self, f: &mut impl FnMut(&'a AstTypeExprP<P>)) {
+        if let ExprP::Lambda(lambda) = self {
+            for param in &lambda.params {
+                if let (_, Some(ty), _) = param.split() {
+                    f(ty);
+                }
+            }
+        }
+        self.visit_expr(|expr| expr.visit_type_expr(f))
+    }
+
     fn visit_type_expr_err_mut<E>(
         &mut self,
         f: &mut impl FnMut(&mut AstTypeExprP<P>) -> Result<(), E>,
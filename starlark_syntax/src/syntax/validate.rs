@@ -17,6 +17,7 @@
 
 //! AST for parsed starlark files.
 
+use crate::codemap::Span;
 use crate::syntax::ast::AstArgument;
 use crate::syntax::ast::AstExpr;
 use crate::syntax::ast::AstLiteral;
@@ -29,6 +30,7 @@ use crate::syntax::ast::ForP;
 use crate::syntax::ast::LambdaP;
 use crate::syntax::ast::ParameterP;
 use crate::syntax::ast::Stmt;
+use crate::syntax::ast::WithP;
 use crate::syntax::call::CallArgsUnpack;
 use crate::syntax::def::DefParams;
 use crate::syntax::state::ParserState;
@@ -118,6 +120,13 @@ pub(crate) fn validate_module(stmt: &AstStmt, parser_state: &mut ParserState) {
                     f(body, parser_state, false, true, inside_def)
                 }
             }
+            Stmt::With(WithP { body, .. }) => {
+                if top_level && !parser_state.dialect.enable_top_level_stmt {
+                    parser_state.error(span, "`with` cannot be used outside `def` in this dialect")
+                } else {
+                    f(body, parser_state, false, inside_for, inside_def)
+                }
+            }
             Stmt::If(..) | Stmt::IfElse(..) => {
                 if top_level && !parser_state.dialect.enable_top_level_stmt {
                     parser_state.error(span, "`if` cannot be used outside `def` in this dialect")
@@ -149,7 +158,7 @@ pub(crate) fn validate_module(stmt: &AstStmt, parser_state: &mut ParserState) {
         }
     }
 
-    fn expr(x: &AstExpr, parser_state: &mut ParserState) {
+    fn expr(x: &AstExpr, parser_state: &mut ParserState, type_spans: &[Span]) {
         match &x.node {
             Expr::Literal(AstLiteral::Ellipsis) => {
                 if parser_state.dialect.enable_types == DialectTypes::Disable {
@@ -162,12 +171,30 @@ pub(crate) fn validate_module(stmt: &AstStmt, parser_state: &mut ParserState) {
                 }
                 validate_params(params, parser_state);
             }
+            Expr::Set(_) => {
+                if !parser_state.dialect.enable_set_literals {
+                    parser_state.error(x.span, "set literals are not allowed in this dialect");
+                }
+            }
+            Expr::IndexN(_) => {
+                if !type_spans
+                    .iter()
+                    .any(|t| t.begin() <= x.span.begin() && x.span.end() <= t.end())
+                {
+                    parser_state.error(
+                        x.span,
+                        "indexing with more than two indices is only allowed in type expressions",
+                    );
+                }
+            }
             _ => {}
         }
-        x.node.visit_expr(|x| expr(x, parser_state));
+        x.node.visit_expr(|x| expr(x, parser_state, type_spans));
     }
 
     f(stmt, parser_state, true, false, false);
 
-    stmt.visit_expr(|x| expr(x, parser_state));
+    let mut type_spans = Vec::new();
+    stmt.visit_type_expr(&mut |x| type_spans.push(x.span));
+    stmt.visit_expr(|x| expr(x, parser_state, &type_spans));
 }